@@ -1,3 +1,6 @@
 fn main() {
+    // get_app_info 진단용 빌드 타겟/프로파일 정보
+    println!("cargo:rustc-env=WHISPER_GUI_BUILD_TARGET={}", std::env::var("TARGET").unwrap_or_default());
+    println!("cargo:rustc-env=WHISPER_GUI_BUILD_PROFILE={}", std::env::var("PROFILE").unwrap_or_default());
     tauri_build::build()
-}
\ No newline at end of file
+}
@@ -21,23 +21,58 @@ pub async fn check_whisper_installation(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_installation_status(
+    service: State<'_, WhisperServiceState>
+) -> Result<InstallationStatus, String> {
+    let service = service.lock().await;
+    service.get_installation_status().await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn setup_whisper(
     app_handle: AppHandle,
+    version: Option<String>,
+    backend: Option<BuildBackend>,
     service: State<'_, WhisperServiceState>
 ) -> Result<String, String> {
+    let version = version.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
     let service = service.lock().await;
-    service.setup_whisper(Some(app_handle)).await
+    service.setup_whisper(Some(app_handle), version, backend).await
         .map_err(|e| {
             eprintln!("Setup whisper error: {:?}", e);
             format!("설치 오류: {}", e)
         })
 }
 
+#[tauri::command]
+pub async fn detect_gpu_backends(
+    service: State<'_, WhisperServiceState>
+) -> Result<GpuBackendDetection, String> {
+    let service = service.lock().await;
+    Ok(service.detect_gpu_backends().await)
+}
+
+#[tauri::command]
+pub async fn get_installed_whisper_version(
+    service: State<'_, WhisperServiceState>
+) -> Result<Option<String>, String> {
+    let service = service.lock().await;
+    Ok(service.get_installed_whisper_version().await)
+}
+
 #[tauri::command]
 pub async fn check_system_requirements() -> Result<String, String> {
     let mut requirements = Vec::new();
     
+    // 앱 빌드 정보 (버그 리포트용)
+    let app_info = crate::services::get_app_info();
+    requirements.push(format!(
+        "ℹ️ Whisper GUI {} ({}, {})",
+        app_info.app_version, app_info.target_triple, app_info.build_profile
+    ));
+    
     // Git 확인
     match tokio::process::Command::new("git").arg("--version").output().await {
         Ok(output) if output.status.success() => {
@@ -132,12 +167,60 @@ pub async fn read_transcription_result(
 
 #[tauri::command]
 pub async fn export_to_srt(
+    transcription: String,
+    output_path: String,
+    layout: Option<SubtitleLayout>,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.export_to_srt(&transcription, &output_path, layout.as_ref()).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_to_vtt(
+    transcription: String,
+    output_path: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.export_to_vtt(&transcription, &output_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_to_styled_vtt(
+    transcription: String,
+    output_path: String,
+    style: Option<VttStyleOptions>,
+    layout: Option<SubtitleLayout>,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.export_to_styled_vtt(&transcription, &output_path, &style.unwrap_or_default(), layout.as_ref()).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_to_markdown(
+    transcription: String,
+    output_path: String,
+    base_media_url: Option<String>,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.export_to_markdown(&transcription, &output_path, base_media_url.as_deref()).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_to_ass(
     transcription: String,
     output_path: String,
     service: State<'_, WhisperServiceState>
 ) -> Result<String, String> {
     let service = service.lock().await;
-    service.export_to_srt(&transcription, &output_path).await
+    service.export_to_ass(&transcription, &output_path).await
         .map_err(|e| e.to_string())
 }
 
@@ -173,6 +256,110 @@ pub async fn start_transcription_with_options(
     Ok(history_id)
 }
 
+#[tauri::command]
+pub async fn enqueue_transcription(
+    config: WhisperConfig,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.enqueue_transcription(&config).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn start_batch_transcription(
+    dir_path: String,
+    config: WhisperConfig,
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<Vec<String>, String> {
+    let service = service.lock().await;
+    service.start_batch_transcription(&dir_path, &config, app_handle).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_queue(
+    service: State<'_, WhisperServiceState>
+) -> Result<Vec<QueueEntry>, String> {
+    let service = service.lock().await;
+    Ok(service.get_queue())
+}
+
+#[tauri::command]
+pub async fn pause_queue(
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<QueueStatus, String> {
+    let service = service.lock().await;
+    Ok(service.pause_queue(&app_handle))
+}
+
+#[tauri::command]
+pub async fn resume_queue(
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<QueueStatus, String> {
+    let service = service.lock().await;
+    Ok(service.resume_queue(&app_handle))
+}
+
+#[tauri::command]
+pub async fn get_queue_status(
+    service: State<'_, WhisperServiceState>
+) -> Result<QueueStatus, String> {
+    let service = service.lock().await;
+    Ok(service.get_queue_status())
+}
+
+#[tauri::command]
+pub async fn schedule_transcription(
+    config: WhisperConfig,
+    run_at: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<ScheduledTranscription, String> {
+    let service = service.lock().await;
+    service.schedule_transcription(&config, &run_at).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_scheduled(
+    service: State<'_, WhisperServiceState>
+) -> Result<Vec<ScheduledTranscription>, String> {
+    let service = service.lock().await;
+    service.list_scheduled().await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_scheduled(
+    id: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<(), String> {
+    let service = service.lock().await;
+    service.cancel_scheduled(&id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn transcribe_screen_recording(
+    file_path: String,
+    config: WhisperConfig,
+    track_preference: AudioTrackPreference,
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let config = WhisperConfig {
+        input_file: file_path,
+        audio_track: Some(track_preference),
+        ..config
+    };
+    let service = service.lock().await;
+    service.start_transcription_with_options(&config, app_handle).await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn download_model_with_progress(
     model_name: String,
@@ -201,8 +388,9 @@ pub async fn validate_model(
     model_name: String,
     service: State<'_, WhisperServiceState>
 ) -> Result<bool, String> {
-    let service = service.lock().await;
-    service.validate_model(&model_name).await
+    // SHA256 계산 동안 서비스를 잠그지 않도록 검증에 필요한 것만 복사
+    let validator = service.lock().await.model_validator();
+    validator.validate(&model_name).await
         .map_err(|e| e.to_string())
 }
 
@@ -217,6 +405,105 @@ pub async fn repair_model(
     Ok(format!("Model {} repaired successfully", model_name))
 }
 
+#[tauri::command]
+pub async fn get_download_capabilities(
+    service: State<'_, WhisperServiceState>
+) -> Result<DownloadCapabilities, String> {
+    let service = service.lock().await;
+    Ok(service.get_download_capabilities().await)
+}
+
+#[tauri::command]
+pub async fn get_model_download_size(
+    model_name: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<ModelDownloadSize, String> {
+    let service = service.lock().await;
+    service.get_model_download_size(&model_name).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn suggest_output_formats(
+    input_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<OutputFormatSuggestion, String> {
+    let media_tools = history_service.lock().await.media_tools().clone();
+    let category = crate::utils::detect_media_category(&media_tools, &input_path).await;
+    let formats = crate::utils::export_formats::suggested_formats(category)
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+    
+    Ok(OutputFormatSuggestion { category, formats })
+}
+
+#[tauri::command]
+pub async fn analyze_audio(
+    file_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<AudioAnalysis, String> {
+    let media_tools = history_service.lock().await.media_tools().clone();
+    crate::utils::audio_analysis::analyze_audio(&media_tools, std::path::Path::new(&file_path)).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_model_provenance(model_name: String) -> Result<ModelProvenance, String> {
+    crate::utils::model_provenance::find_model_provenance(&model_name)
+        .ok_or_else(|| format!("No provenance information for model: {}", model_name))
+}
+
+#[tauri::command]
+pub async fn read_model_header(
+    model_name_or_path: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<ModelHeader, String> {
+    let service = service.lock().await;
+    service.read_model_header(&model_name_or_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn validate_all_models(
+    service: State<'_, WhisperServiceState>
+) -> Result<Vec<ModelValidation>, String> {
+    let (validator, model_names) = {
+        let service = service.lock().await;
+        let model_names = service.list_downloaded_models().await
+            .map_err(|e| e.to_string())?;
+        (service.model_validator(), model_names)
+    };
+    Ok(validator.validate_all(model_names).await)
+}
+
+#[tauri::command]
+pub async fn repair_all_invalid_models(
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<ModelRepairSummary, String> {
+    // 다운로드 중에는 서비스 잠금을 풀어 두므로 잠그지 않고 공유 상태를 넘김
+    crate::services::repair_all_invalid_models(service.inner().clone(), app_handle).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_batch_limits(batch_size: usize) -> Result<BatchLimitCheck, String> {
+    Ok(crate::utils::resource_limits::check_batch_limits(batch_size))
+}
+
+#[tauri::command]
+pub async fn quantize_model(
+    source_model: String,
+    quant_type: String,
+    app_handle: AppHandle,
+    service: State<'_, WhisperServiceState>
+) -> Result<String, String> {
+    let service = service.lock().await;
+    service.quantize_model(&source_model, &quant_type, app_handle).await
+        .map_err(|e| e.to_string())
+}
+
 // ===== 히스토리 관련 명령들 =====
 
 #[tauri::command]
@@ -261,6 +548,52 @@ pub async fn update_history_tags(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn bulk_tag(
+    query: HistoryQuery,
+    tags_to_add: Vec<String>,
+    tags_to_remove: Vec<String>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<usize, String> {
+    let service = history_service.lock().await;
+    service.bulk_tag(&query, &tags_to_add, &tags_to_remove).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_history_custom_field(
+    history_id: String,
+    key: String,
+    value: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let service = history_service.lock().await;
+    service.set_history_custom_field(&history_id, key, value).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_history_custom_fields(
+    history_id: String,
+    custom_fields: std::collections::HashMap<String, String>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let service = history_service.lock().await;
+    service.update_history_custom_fields(&history_id, custom_fields).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_history_custom_field(
+    history_id: String,
+    key: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let service = history_service.lock().await;
+    service.remove_history_custom_field(&history_id, &key).await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn update_history_notes(
     history_id: String,
@@ -292,6 +625,42 @@ pub async fn download_result_file(
     Ok(format!("File downloaded to: {}", save_path))
 }
 
+#[tauri::command]
+pub async fn batch_export(
+    history_ids: Vec<String>,
+    format: String,
+    dest_dir: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<Vec<BatchExportResult>, String> {
+    let service = service.lock().await;
+    service.batch_export(&history_ids, &format, std::path::Path::new(&dest_dir)).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_delivery_package(
+    history_id: String,
+    formats: Vec<String>,
+    output_path: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<DeliveryPackage, String> {
+    let service = service.lock().await;
+    service.export_delivery_package(&history_id, &formats, std::path::Path::new(&output_path)).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn translate_result(
+    history_id: String,
+    format: String,
+    target_language: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let service = service.lock().await;
+    service.translate_result(&history_id, &format, &target_language).await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_result_file_info(
     history_id: String,
@@ -302,4 +671,514 @@ pub async fn get_result_file_info(
         .map_err(|e| e.to_string())?;
     
     Ok(history.results)
-}
\ No newline at end of file
+}
+#[tauri::command]
+pub async fn diff_history_options(
+    history_id_a: String,
+    history_id_b: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<HistoryOptionsDiff, String> {
+    let service = history_service.lock().await;
+    service.diff_history_options(&history_id_a, &history_id_b).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_result_file_paths(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<std::collections::HashMap<String, std::path::PathBuf>, String> {
+    let service = history_service.lock().await;
+    service.get_result_file_paths(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_app_info() -> Result<AppInfo, String> {
+    Ok(crate::services::get_app_info())
+}
+
+#[tauri::command]
+pub async fn get_rerun_config(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<WhisperConfig, String> {
+    let service = history_service.lock().await;
+    service.get_rerun_config(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_language_mismatch(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<Option<LanguageCheck>, String> {
+    let service = history_service.lock().await;
+    service.check_language_mismatch(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_encoding_issues(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<EncodingCheck, String> {
+    let service = history_service.lock().await;
+    service.check_encoding_issues(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn regenerate_previews(
+    force: Option<bool>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<usize, String> {
+    let service = history_service.lock().await;
+    service.regenerate_previews(force.unwrap_or(false)).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_result_quality(
+    history_id: String,
+    format: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<ResultQuality, String> {
+    let service = history_service.lock().await;
+    service.check_result_quality(&history_id, &format).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn tail_result(
+    history_id: String,
+    format: String,
+    lines: usize,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<TailResult, String> {
+    let service = history_service.lock().await;
+    service.tail_result(&history_id, &format, lines).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_history_logs(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.get_history_logs(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_chapters(
+    history_id: String,
+    output_path: String,
+    min_gap_seconds: f64,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.export_chapters(&history_id, &output_path, min_gap_seconds).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_proofreading_doc(
+    history_id: String,
+    interval_seconds: f64,
+    output_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.export_proofreading_doc(&history_id, interval_seconds, &output_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_html_player(
+    history_id: String,
+    media_reference: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.export_html_player(&history_id, &media_reference).await
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_app_settings() -> Result<AppSettings, String> {
+    Ok(SettingsService::new().load())
+}
+
+#[tauri::command]
+pub async fn set_language_rules(
+    rules: std::collections::HashMap<String, std::collections::HashMap<String, String>>
+) -> Result<AppSettings, String> {
+    SettingsService::new().set_language_rules(rules).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_export_filename_template(template: String) -> Result<AppSettings, String> {
+    SettingsService::new().set_export_filename_template(&template).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_default_output_formats(formats: Vec<String>) -> Result<AppSettings, String> {
+    SettingsService::new().set_default_output_formats(formats).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_verify_after_download(enabled: bool) -> Result<AppSettings, String> {
+    SettingsService::new().set_verify_after_download(enabled).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn verify_model_with_sample(
+    model_name: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<ModelVerification, String> {
+    let service = service.lock().await;
+    Ok(service.verify_model_with_sample(&model_name).await)
+}
+
+#[tauri::command]
+pub async fn verify_model_checksum(
+    model_name: String,
+    service: State<'_, WhisperServiceState>
+) -> Result<ModelChecksum, String> {
+    let service = service.lock().await;
+    service.verify_model_checksum(&model_name).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_post_hook_command(command: Option<String>) -> Result<AppSettings, String> {
+    SettingsService::new().set_post_hook_command(command).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_translator_command(command: Option<String>) -> Result<AppSettings, String> {
+    SettingsService::new().set_translator_command(command).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_max_concurrent(max_concurrent: usize) -> Result<AppSettings, String> {
+    SettingsService::new().set_max_concurrent(max_concurrent).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_media_tool_paths(
+    ffmpeg_path: Option<String>,
+    ffprobe_path: Option<String>,
+    whisper_service: State<'_, WhisperServiceState>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<AppSettings, String> {
+    // 빈 문자열은 설정 해제로 취급
+    let to_path = |path: Option<String>| path.filter(|p| !p.trim().is_empty()).map(std::path::PathBuf::from);
+    let settings = SettingsService::new().set_media_tool_paths(to_path(ffmpeg_path), to_path(ffprobe_path)).await
+        .map_err(|e| e.to_string())?;
+    
+    // 서비스는 경로를 시작할 때 한 번만 읽으므로 바뀐 경로를 바로 반영
+    let media_tools = crate::utils::MediaTools::from_settings(&settings);
+    whisper_service.lock().await.set_media_tools(media_tools.clone());
+    history_service.lock().await.set_media_tools(media_tools);
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn set_custom_binary_path(path: Option<String>) -> Result<AppSettings, String> {
+    // 빈 문자열은 설정 해제로 취급
+    let path = path.filter(|p| !p.trim().is_empty()).map(std::path::PathBuf::from);
+    SettingsService::new().set_custom_binary_path(path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn migrate_data_directory(
+    new_path: String,
+    whisper_service: State<'_, WhisperServiceState>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<AppSettings, String> {
+    // 이동 중 다른 작업이 끼어들지 않도록 두 서비스를 모두 잠금
+    let mut whisper = whisper_service.lock().await;
+    let mut history = history_service.lock().await;
+    
+    // 실행 중인 변환은 완료 시 기존 위치에 결과/로그를 쓰므로 이동하지 않음
+    if whisper.has_pending_transcriptions() {
+        return Err("Cannot move the data directory while transcriptions are running or queued".to_string());
+    }
+    // 복구 중인 모델은 기존 models 디렉토리에 계속 쓰므로 이동하지 않음
+    if whisper.has_active_model_downloads() {
+        return Err("Cannot move the data directory while models are downloading or being repaired".to_string());
+    }
+    
+    let settings_service = SettingsService::new();
+    let old_path = settings_service.load().data_dir;
+    
+    let settings = settings_service.migrate_data_directory(std::path::Path::new(&new_path)).await
+        .map_err(|e| e.to_string())?;
+    
+    // 실행 중인 서비스의 경로만 새 위치로 전환 (대기열/캐시는 유지)
+    whisper.set_data_dir(settings.data_dir.clone());
+    history.set_data_dir(settings.data_dir.clone());
+    
+    // 새 위치가 확정된 뒤에만 기존 위치 삭제 (실패해도 데이터는 새 위치에 안전)
+    if let Err(e) = remove_old_data_directory(&old_path).await {
+        eprintln!("Failed to remove old data directory {:?}: {}", old_path, e);
+    }
+    
+    Ok(settings)
+}
+
+/// 기본 중복 판정 유사도 (0.0 ~ 1.0)
+const DEFAULT_DEDUP_SIMILARITY: f64 = 0.9;
+
+#[tauri::command]
+pub async fn deduplicate_segments(
+    history_id: String,
+    format: String,
+    similarity_threshold: Option<f64>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<DeduplicationReport, String> {
+    let service = history_service.lock().await;
+    service.deduplicate_segments(
+        &history_id,
+        &format,
+        similarity_threshold.unwrap_or(DEFAULT_DEDUP_SIMILARITY),
+    ).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn merge_segments(
+    history_id: String,
+    min_duration_seconds: f64,
+    max_gap_seconds: f64,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<SegmentMergeReport, String> {
+    let service = history_service.lock().await;
+    service.merge_segments(&history_id, min_duration_seconds, max_gap_seconds).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn merge_short_segments(
+    history_id: String,
+    format: String,
+    min_duration_ms: u64,
+    max_merged_chars: usize,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<SegmentMergeReport, String> {
+    let service = history_service.lock().await;
+    service.merge_short_segments(&history_id, &format, min_duration_ms, max_merged_chars).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn analyze_subtitle_coverage(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<SubtitleCoverage, String> {
+    let service = history_service.lock().await;
+    service.analyze_subtitle_coverage(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_speaking_rate(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<SpeakingRate, String> {
+    let service = history_service.lock().await;
+    service.get_speaking_rate(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_high_confidence(
+    history_id: String,
+    format: String,
+    min_confidence: f64,
+    output_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<ConfidenceExportReport, String> {
+    let service = history_service.lock().await;
+    service.export_high_confidence(&history_id, &format, min_confidence, &output_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn split_result(
+    history_id: String,
+    format: String,
+    options: SplitOptions,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<Vec<String>, String> {
+    let service = history_service.lock().await;
+    service.split_result(&history_id, &format, &options).await
+        .map(|paths| paths.iter().map(|p| p.to_string_lossy().to_string()).collect())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn transcribe_append(
+    history_id: String,
+    file_path: String,
+    service: State<'_, WhisperServiceState>,
+    app_handle: AppHandle
+) -> Result<(), String> {
+    let service = service.lock().await;
+    service.transcribe_append(&history_id, &file_path, app_handle).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_export_formats() -> Result<Vec<ExportFormatInfo>, String> {
+    Ok(crate::utils::export_formats::list_export_formats())
+}
+
+#[tauri::command]
+pub async fn get_segment_timing(
+    history_id: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<Vec<SegmentTiming>, String> {
+    let service = history_service.lock().await;
+    service.get_segment_timing(&history_id).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn search_all_transcripts(
+    term: String,
+    options: Option<TranscriptSearchOptions>,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<TranscriptSearchResponse, String> {
+    let service = history_service.lock().await;
+    service.search_all_transcripts(&term, &options.unwrap_or_default()).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_karaoke_ass(
+    history_id: String,
+    output_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.export_karaoke_ass(&history_id, &output_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_transcription_resources(
+    history_id: String,
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<TranscriptionResources, String> {
+    let service = whisper_service.lock().await;
+    service.get_transcription_resources(&history_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_transcription(
+    history_id: String,
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<(), String> {
+    let service = whisper_service.lock().await;
+    service.cancel_transcription(&history_id)
+        .map_err(|e| e.to_string())
+}
+
+/// 사유를 지정하지 않았을 때 기록할 메시지
+const DEFAULT_FORCE_FAIL_REASON: &str = "Marked as failed manually";
+
+#[tauri::command]
+pub async fn force_fail_history(
+    history_id: String,
+    reason: Option<String>,
+    cancelled: Option<bool>,
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let reason = reason.filter(|r| !r.trim().is_empty()).unwrap_or_else(|| DEFAULT_FORCE_FAIL_REASON.to_string());
+    let service = whisper_service.lock().await;
+    service.force_fail_history(&history_id, &reason, cancelled.unwrap_or(false)).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_transcription_eta(
+    history_id: String,
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<TranscriptionEta, String> {
+    let service = whisper_service.lock().await;
+    service.get_transcription_eta(&history_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn regenerate_exports(
+    history_id: String,
+    formats: Vec<String>,
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<TranscriptionHistory, String> {
+    let service = whisper_service.lock().await;
+    service.regenerate_exports(&history_id, &formats).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_download_script(
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<DownloadScriptStatus, String> {
+    let service = whisper_service.lock().await;
+    Ok(service.check_download_script().await)
+}
+
+#[tauri::command]
+pub async fn repair_download_script(
+    whisper_service: State<'_, WhisperServiceState>
+) -> Result<DownloadScriptStatus, String> {
+    let service = whisper_service.lock().await;
+    service.repair_download_script().await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn export_history_report(
+    query: HistoryQuery,
+    output_path: String,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<usize, String> {
+    let service = history_service.lock().await;
+    service.export_history_report(&query, &output_path).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn find_duplicate_histories(
+    history_service: State<'_, HistoryServiceState>
+) -> Result<Vec<DuplicateHistoryGroup>, String> {
+    let service = history_service.lock().await;
+    service.find_duplicate_histories().await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn generate_batch_summary(
+    history_ids: Vec<String>,
+    format: SummaryFormat,
+    history_service: State<'_, HistoryServiceState>
+) -> Result<String, String> {
+    let service = history_service.lock().await;
+    service.generate_batch_summary(&history_ids, format).await
+        .map_err(|e| e.to_string())
+}
@@ -9,16 +9,24 @@ mod services;
 mod utils;
 
 use commands::*;
-use services::{WhisperService, HistoryService};
+use services::{run_transcription_queue, run_transcription_scheduler, WhisperService, HistoryService, SettingsService};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 #[tokio::main]
 async fn main() {
-    let whisper_service = Arc::new(Mutex::new(WhisperService::new()));
-    let history_service = Arc::new(Mutex::new(HistoryService::new()));
+    let settings = SettingsService::new().load();
+    let whisper_service = Arc::new(Mutex::new(WhisperService::with_data_dir(settings.data_dir.clone())));
+    let history_service = Arc::new(Mutex::new(HistoryService::with_data_dir(settings.data_dir)));
+
+    let queue_service = whisper_service.clone();
 
     tauri::Builder::default()
+        .setup(move |app| {
+            tokio::spawn(run_transcription_queue(queue_service.clone(), app.handle()));
+            tokio::spawn(run_transcription_scheduler(queue_service, app.handle()));
+            Ok(())
+        })
         .manage(whisper_service)
         .manage(history_service)
         .invoke_handler(tauri::generate_handler![
@@ -33,6 +41,7 @@ async fn main() {
             start_transcription,
             read_transcription_result,
             export_to_srt,
+            export_to_vtt,
             export_to_fcpxml,
             get_whisper_options,
             start_transcription_with_options,
@@ -46,7 +55,88 @@ async fn main() {
             update_history_tags,
             update_history_notes,
             download_result_file,
-            get_result_file_info
+            get_result_file_info,
+            diff_history_options,
+            get_result_file_paths,
+            get_app_info,
+            get_rerun_config,
+            check_language_mismatch,
+            check_encoding_issues,
+            tail_result,
+            export_chapters,
+            get_app_settings,
+            migrate_data_directory,
+            set_history_custom_field,
+            remove_history_custom_field,
+            export_to_styled_vtt,
+            deduplicate_segments,
+            analyze_subtitle_coverage,
+            export_high_confidence,
+            split_result,
+            transcribe_append,
+            list_export_formats,
+            get_segment_timing,
+            export_to_markdown,
+            search_all_transcripts,
+            export_karaoke_ass,
+            get_transcription_eta,
+            regenerate_exports,
+            update_history_custom_fields,
+            check_download_script,
+            repair_download_script,
+            export_history_report,
+            export_to_ass,
+            find_duplicate_histories,
+            validate_all_models,
+            repair_all_invalid_models,
+            set_language_rules,
+            get_model_download_size,
+            generate_batch_summary,
+            set_media_tool_paths,
+            get_model_provenance,
+            analyze_audio,
+            suggest_output_formats,
+            get_history_logs,
+            batch_export,
+            set_export_filename_template,
+            check_result_quality,
+            get_download_capabilities,
+            set_post_hook_command,
+            get_speaking_rate,
+            get_transcription_resources,
+            set_default_output_formats,
+            transcribe_screen_recording,
+            regenerate_previews,
+            read_model_header,
+            export_delivery_package,
+            set_verify_after_download,
+            verify_model_with_sample,
+            cancel_transcription,
+            translate_result,
+            set_translator_command,
+            get_installation_status,
+            set_custom_binary_path,
+            verify_model_checksum,
+            quantize_model,
+            bulk_tag,
+            check_batch_limits,
+            export_proofreading_doc,
+            export_html_player,
+            merge_segments,
+            get_installed_whisper_version,
+            enqueue_transcription,
+            get_queue,
+            pause_queue,
+            resume_queue,
+            get_queue_status,
+            set_max_concurrent,
+            schedule_transcription,
+            list_scheduled,
+            cancel_scheduled,
+            detect_gpu_backends,
+            merge_short_segments,
+            start_batch_transcription,
+            force_fail_history
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -4,9 +4,11 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TranscriptionStatus {
     Idle,
+    Queued,     // 대기열에서 시작을 기다리는 중
     Running,
     Completed,
     Failed,
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,9 +44,21 @@ impl TranscriptionState {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressInfo {
-    pub progress: f32,
+    pub progress: f32,                  // 전처리를 포함한 전체 진행률 (0.0 ~ 1.0)
     pub current_time: Option<f32>,
     pub message: String,
+    #[serde(default)]
+    pub phase: TranscriptionPhase,      // 현재 진행 단계
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum TranscriptionPhase {
+    Queued,         // 실행 대기 중
+    Preprocessing,  // ffmpeg로 입력 변환 중
+    LoadingModel,   // whisper 모델 로딩 중
+    #[default]
+    Transcribing,   // whisper 변환 중
+    Finalizing,     // 결과 파일 수집/저장 중
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +89,41 @@ pub struct WhisperConfig {
     pub model: String,
     pub input_file: String,
     pub options: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub adaptive_threads: bool,     // 진행이 비정상적으로 느리면 스레드 수를 줄여 재시도
+    #[serde(default)]
+    pub export_formats: Vec<String>, // 완료 시 자막 결과에서 추가로 파생할 형식들 (txt, srt, vtt, fcpxml)
+    #[serde(default)]
+    pub preserve_source_mtime: bool, // 결과 파일의 수정 시간을 원본 미디어와 같게 맞춤
+    #[serde(default)]
+    pub process_priority: ProcessPriority, // whisper 프로세스의 OS 우선순위
+    #[serde(default)]
+    pub audio_track: Option<AudioTrackPreference>, // 여러 오디오 트랙 중 변환에 사용할 트랙 (None이면 ffmpeg 기본 선택)
+}
+
+/// 화면 녹화처럼 오디오 트랙이 여러 개인 입력에서 사용할 트랙
+/// (OBS 등은 보통 첫 번째 트랙에 시스템 소리, 두 번째 트랙에 마이크를 녹음)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AudioTrackPreference {
+    Mic,            // 두 번째 오디오 트랙
+    System,         // 첫 번째 오디오 트랙
+    Mix,            // 모든 오디오 트랙을 amix로 합침
+}
+
+/// 실제로 적용된 오디오 트랙 선택
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioTrackChoice {
+    pub preference: AudioTrackPreference,
+    pub audio_stream_count: usize,  // 입력 파일의 오디오 트랙 수
+    pub fallback: bool,             // 트랙이 하나뿐이라 선호 트랙 대신 유일한 트랙을 사용함
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ProcessPriority {
+    Low,            // Unix nice 10 / Windows BELOW_NORMAL
+    #[default]
+    Normal,
+    High,           // Unix nice -5 (권한 필요) / Windows ABOVE_NORMAL
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +141,7 @@ pub struct DownloadProgress {
 pub enum DownloadStatus {
     Starting,
     Downloading,
+    Verifying,      // 다운로드 후 SHA256 확인 중
     Completed,
     Failed,
     Cancelled,
@@ -103,6 +153,12 @@ pub struct TranscriptionResult {
     pub format: String,         // "txt", "srt", "vtt", "csv", "json", "lrc"
     pub file_size: u64,         // 파일 크기 (bytes)
     pub created_at: String,     // ISO 8601 timestamp
+    #[serde(default)]
+    pub partial: bool,          // 변환이 중간에 취소되어 일부만 담긴 파일인지 여부
+    #[serde(default)]
+    pub language: Option<String>, // 번역 결과의 대상 언어 (원본 결과는 None)
+    #[serde(default)]
+    pub variant: Option<String>,  // 원본에서 파생한 결과의 종류 (예: "dedup", 원본은 None)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,6 +176,36 @@ pub struct TranscriptionHistory {
     pub tags: Vec<String>,                  // 사용자 태그들
     pub notes: Option<String>,              // 사용자 메모
     pub error_message: Option<String>,      // 실패 시 에러 메시지
+    #[serde(default)]
+    pub warnings: Vec<HistoryWarning>,      // 결과 품질 관련 경고들
+    #[serde(default)]
+    pub thread_attempts: Vec<ThreadAttempt>, // adaptive_threads 사용 시 시도 기록 (마지막이 최종)
+    #[serde(default)]
+    pub custom_fields: std::collections::HashMap<String, String>, // 사용자 정의 메타데이터 (예: "client": "Acme")
+    #[serde(default)]
+    pub processed_until_seconds: Option<f64>, // 이어서 변환(transcribe_append)으로 처리된 마지막 오디오 위치 (초)
+    #[serde(default)]
+    pub segment_timings: Vec<SegmentTiming>, // 세그먼트별 처리 시간 (측정된 경우에만)
+    #[serde(default)]
+    pub input_file_size: Option<u64>,       // 변환 시작 시점의 원본 파일 크기 (bytes)
+    #[serde(default)]
+    pub applied_language_rule: Option<String>, // 적용된 언어 규칙 (자동 감지된 언어 코드)
+    #[serde(default)]
+    pub process_priority: ProcessPriority,  // 변환 시 사용한 프로세스 우선순위
+    #[serde(default)]
+    pub avg_confidence: Option<f32>,        // JSON 결과의 세그먼트 신뢰도 평균 (토큰 정보가 없으면 None)
+    #[serde(default)]
+    pub whisper_commit: Option<String>,     // 변환 시작 시점에 설치되어 있던 whisper.cpp 커밋 해시
+    #[serde(default)]
+    pub audio_track: Option<AudioTrackChoice>, // 오디오 트랙을 선택해 변환한 경우의 선택 결과
+    #[serde(default)]
+    pub preview: Option<String>,            // 목록 표시용 결과 텍스트 앞부분 (PREVIEW_MAX_CHARS자까지)
+    #[serde(default)]
+    pub loaded_model: Option<LoadedModelInfo>, // whisper가 실제로 읽은 모델 정보 (whisper_model_load 로그 기준)
+    #[serde(default)]
+    pub export_formats: Vec<String>,        // 변환 시 요청한 추가 파생 형식 (재실행 시 복원)
+    #[serde(default)]
+    pub preserve_source_mtime: bool,        // 결과 파일 수정 시간을 원본에 맞추도록 요청했는지 여부
 }
 
 impl TranscriptionHistory {
@@ -146,6 +232,21 @@ impl TranscriptionHistory {
             tags: Vec::new(),
             notes: None,
             error_message: None,
+            warnings: Vec::new(),
+            thread_attempts: Vec::new(),
+            custom_fields: std::collections::HashMap::new(),
+            processed_until_seconds: None,
+            segment_timings: Vec::new(),
+            input_file_size: None,
+            applied_language_rule: None,
+            process_priority: ProcessPriority::Normal,
+            avg_confidence: None,
+            whisper_commit: None,
+            audio_track: None,
+            preview: None,
+            loaded_model: None,
+            export_formats: Vec::new(),
+            preserve_source_mtime: false,
         }
     }
     
@@ -174,11 +275,27 @@ impl TranscriptionHistory {
         self
     }
     
+    pub fn mark_cancelled(mut self) -> Self {
+        use chrono::Utc;
+        
+        // 취소는 실패가 아니므로 error_message는 채우지 않음
+        self.status = TranscriptionStatus::Cancelled;
+        self.completed_at = Some(Utc::now().to_rfc3339());
+        self
+    }
+    
     pub fn add_result(mut self, result: TranscriptionResult) -> Self {
         self.results.push(result);
         self
     }
     
+    pub fn with_warning(mut self, warning: HistoryWarning) -> Self {
+        // 같은 코드의 경고는 최신 내용으로 교체
+        self.warnings.retain(|w| w.code != warning.code);
+        self.warnings.push(warning);
+        self
+    }
+    
     pub fn total_file_size(&self) -> u64 {
         self.results.iter().map(|r| r.file_size).sum()
     }
@@ -199,6 +316,8 @@ pub struct HistoryQuery {
     pub status_filter: Option<TranscriptionStatus>, // 상태별 필터
     pub date_from: Option<String>,    // 시작 날짜 (ISO 8601)
     pub date_to: Option<String>,      // 종료 날짜 (ISO 8601)
+    pub custom_field_filter: Option<(String, String)>, // 사용자 정의 필드 (키, 값) 일치 필터
+    pub needs_review_filter: Option<f32>, // 평균 신뢰도가 이 값보다 낮은 항목만 (신뢰도가 없는 항목은 제외)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -206,4 +325,647 @@ pub struct HistoryListResponse {
     pub items: Vec<TranscriptionHistory>,
     pub total_count: usize,
     pub has_more: bool,
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueChange {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryOptionsDiff {
+    pub model_changed: Option<ValueChange>,                           // 모델 변경 (a → b)
+    pub added: std::collections::HashMap<String, String>,             // b에만 있는 옵션
+    pub removed: std::collections::HashMap<String, String>,           // a에만 있는 옵션
+    pub changed: std::collections::HashMap<String, ValueChange>,      // 양쪽에 있지만 값이 다른 옵션
+    pub language_changed: Option<ValueChange>,                        // 언어 변경 (미지정 시 auto)
+    pub task_changed: Option<ValueChange>,                            // transcribe ↔ translate
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppInfo {
+    pub app_version: String,                // CARGO_PKG_VERSION
+    pub target_triple: String,              // 빌드 타겟 (예: aarch64-apple-darwin)
+    pub build_profile: String,              // debug / release
+    pub whisper_cpp_repo: String,           // whisper.cpp 저장소 URL
+    pub whisper_cpp_ref: Option<String>,    // 고정된 whisper.cpp ref (None이면 기본 브랜치)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryWarning {
+    pub code: String,       // 프론트엔드 번역용 코드 (예: "language_mismatch")
+    pub message: String,    // 상세 설명
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleSegment {
+    pub start: f64,     // 시작 시간 (초)
+    pub end: f64,       // 종료 시간 (초)
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextScript {
+    Latin,
+    Hangul,
+    Kana,
+    Han,
+    Cyrillic,
+    Arabic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageCheck {
+    pub requested_language: String,
+    pub expected_scripts: Vec<TextScript>,
+    pub detected_script: Option<TextScript>,
+    pub mismatch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodingCheck {
+    pub replacement_chars: usize,       // 결과 파일들에서 찾은 U+FFFD(또는 잘못된 UTF-8 바이트) 수
+    pub affected_formats: Vec<String>,  // 대체 문자가 나온 결과 형식들
+    pub possible_issue: bool,
+    pub rerun_config: Option<WhisperConfig>, // 문제가 있으면 줄 길이 제한 없이 다시 실행할 설정 (재실행할 수 없는 상태면 None)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultQuality {
+    pub history_id: String,
+    pub format: String,
+    pub is_empty: bool,                 // 파일 크기가 0
+    pub is_whitespace_only: bool,       // 내용이 공백/줄바꿈뿐
+    pub suspiciously_short: bool,       // 오디오 길이에 비해 텍스트가 지나치게 적음
+    pub repetition_ratio: f64,          // 앞에서 이미 나온 줄과 같은 줄의 비율 (0.0 ~ 1.0)
+    pub line_count: usize,              // 텍스트가 있는 줄(자막은 큐) 수
+    pub audio_duration_seconds: Option<f64>, // 판단에 사용한 오디오 길이
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadAttempt {
+    pub threads: u32,
+    pub aborted_as_slow: bool,  // 느린 진행으로 중단되었는지 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub history_id: String,
+    pub position: usize,                // 1부터 시작 (실행 중인 변환이 앞쪽)
+    pub status: TranscriptionStatus,    // Running 또는 Queued
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueStatus {
+    pub paused: bool,                   // true면 대기 중인 변환이 시작되지 않음
+    pub queued: usize,
+    pub running: usize,                 // 입력을 준비 중인 변환 포함
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchProgress {
+    pub completed: usize,               // 끝난 파일 수 (실패/취소 포함)
+    pub total: usize,
+    pub current_file: Option<String>,   // 실행 중인 파일 이름 (여러 개면 먼저 추가된 파일)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTranscription {
+    pub id: String,
+    pub config: WhisperConfig,
+    pub run_at: String,                 // 대기열에 넣을 시각 (RFC 3339)
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailResult {
+    pub lines: Vec<String>,     // 마지막 줄부터 역순
+    pub has_more: bool,         // 앞쪽에 더 많은 내용이 있는지 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    pub data_dir: PathBuf,              // 모델/결과/저장소/인덱스가 위치하는 루트 디렉토리
+    #[serde(default)]
+    pub language_rules: std::collections::HashMap<String, std::collections::HashMap<String, String>>, // 자동 감지된 언어 → 덮어쓸 옵션
+    #[serde(default)]
+    pub ffmpeg_path: Option<PathBuf>,   // 지정하지 않으면 PATH의 ffmpeg 사용
+    #[serde(default)]
+    pub ffprobe_path: Option<PathBuf>,  // 지정하지 않으면 PATH의 ffprobe 사용
+    #[serde(default = "AppSettings::default_export_filename_template")]
+    pub export_filename_template: String, // 일괄 내보내기 파일 이름 ({name}, {id}, {date}, {model}; 확장자 제외)
+    #[serde(default)]
+    pub post_hook_command: Option<String>, // 변환 완료 후 실행할 셸 명령 (주의: 임의 명령 실행, 기본값 꺼짐)
+    #[serde(default)]
+    pub default_output_formats: Vec<String>, // 출력 형식을 지정하지 않았을 때 사용할 형식 (비어 있으면 srt)
+    #[serde(default)]
+    pub verify_after_download: bool,    // 모델 다운로드 직후 샘플 오디오로 실제 변환이 되는지 확인
+    #[serde(default)]
+    pub translator_command: Option<String>, // 결과 번역에 사용할 셸 명령 (stdin 텍스트 → stdout 번역, 줄 수 유지)
+    #[serde(default)]
+    pub custom_binary_path: Option<PathBuf>, // 직접 빌드한 whisper-cli 경로 (유효하면 기본 탐색 대신 사용)
+    #[serde(default = "AppSettings::default_max_concurrent")]
+    pub max_concurrent: usize,          // 대기열에서 동시에 실행할 변환 수
+    #[serde(default)]
+    pub build_backend: Option<BuildBackend>, // whisper.cpp 빌드 가속 백엔드 (None이면 업스트림 기본값)
+}
+
+impl AppSettings {
+    pub fn default_data_dir() -> PathBuf {
+        let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        home_dir.join(".whisper-gui")
+    }
+    
+    pub fn default_export_filename_template() -> String {
+        "{name}".to_string()
+    }
+    
+    pub fn default_max_concurrent() -> usize {
+        1
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            data_dir: Self::default_data_dir(),
+            language_rules: std::collections::HashMap::new(),
+            ffmpeg_path: None,
+            ffprobe_path: None,
+            export_filename_template: Self::default_export_filename_template(),
+            post_hook_command: None,
+            default_output_formats: Vec::new(),
+            verify_after_download: false,
+            translator_command: None,
+            custom_binary_path: None,
+            max_concurrent: Self::default_max_concurrent(),
+            build_backend: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum VttPosition {
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum VttAlign {
+    Start,
+    Center,
+    End,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SubtitleLayout {
+    pub max_chars_per_line: Option<usize>, // 줄바꿈 기준 글자 수 (없으면 줄바꿈/분할하지 않음)
+    pub max_lines: Option<usize>,       // 큐당 최대 줄 수 (방송 자막은 보통 2, 넘으면 다음 큐로 분할)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VttStyleOptions {
+    pub position: Option<VttPosition>,  // 세로 위치 (line 설정)
+    pub align: Option<VttAlign>,        // 텍스트 정렬 (align 설정)
+    pub class: Option<String>,          // 큐 텍스트를 감쌀 CSS 클래스 (<c.class>)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeduplicationReport {
+    pub format: String,
+    pub output_path: String,            // 정리한 자막을 저장한 파일 (원본은 그대로 둠)
+    pub original_count: usize,          // 정리 전 세그먼트 수
+    pub remaining_count: usize,         // 정리 후 세그먼트 수
+    pub merged_count: usize,            // 앞 세그먼트에 병합된 중복 수
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentMergeReport {
+    pub format: String,
+    pub output_path: String,            // 병합 결과 파일 경로
+    pub original_count: usize,          // 병합 전 세그먼트 수
+    pub merged_count: usize,            // 병합 후 세그먼트 수
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpeakingPace {
+    Normal,
+    Fast,       // 지나치게 빠름 (인식 오류 가능성)
+    Slow,       // 지나치게 느림 (무음이 음성으로 잡혔을 가능성)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentSpeakingRate {
+    pub start: f64,
+    pub end: f64,
+    pub word_count: usize,
+    pub words_per_minute: f64,
+    pub pace: SpeakingPace,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakingRate {
+    pub overall_words_per_minute: f64,  // 큐가 있는 시간 기준 (큐 사이 공백 제외)
+    pub total_words: usize,
+    pub speaking_seconds: f64,
+    pub fast_count: usize,
+    pub slow_count: usize,
+    pub segments: Vec<SegmentSpeakingRate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleCoverage {
+    pub audio_duration_seconds: f64,
+    pub duration_estimated: bool,       // ffprobe를 쓸 수 없어 마지막 큐 종료 시간으로 추정했는지 여부
+    pub covered_seconds: f64,           // 큐가 덮는 시간 (겹치는 구간은 한 번만 계산)
+    pub coverage_ratio: f64,            // 0.0 ~ 1.0
+    pub largest_gap: Option<TimeRange>, // 자막이 없는 가장 긴 구간 (앞/뒤 무음 포함)
+    pub cue_count: usize,
+    pub cues_per_minute: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    pub confidence: Option<f64>,        // 토큰 확률 평균 (토큰 정보가 없으면 None)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceExportReport {
+    pub output_path: String,
+    pub kept_count: usize,
+    pub dropped_count: usize,           // 임계값 미만으로 제외된 세그먼트 수
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SplitStrategy {
+    MaxDuration(f64),                   // 파트당 최대 길이 (초)
+    MaxCues(usize),                     // 파트당 최대 큐 개수
+    MaxBytes(u64),                      // 파트당 최대 파일 크기 (bytes)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitOptions {
+    pub strategy: SplitStrategy,
+    pub output_dir: String,
+    #[serde(default)]
+    pub rebase_timings: bool,           // true면 각 파트가 0초부터 시작, false면 원본 시간 유지
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportFormatInfo {
+    pub id: String,                     // "txt", "srt", ...
+    pub display_name: String,
+    pub requires_timestamps: bool,
+    pub produced_by_whisper: bool,      // whisper.cpp 출력 플래그로 직접 생성되는지 여부
+    pub derivable: bool,                // 앱이 자막 결과에서 변환해 만들 수 있는지 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentTiming {
+    pub start: f64,                     // 오디오 구간 시작 (초)
+    pub end: f64,                       // 오디오 구간 끝 (초)
+    pub processing_seconds: f64,        // 직전 세그먼트 출력 이후 이 세그먼트가 출력되기까지 걸린 시간
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptSearchOptions {
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub regex: bool,                    // true면 검색어를 정규식으로 해석
+    #[serde(default)]
+    pub context_lines: usize,           // 일치한 줄 앞뒤로 포함할 줄 수
+    pub max_results: Option<usize>,     // 결과 최대 개수 (기본 200)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSearchHit {
+    pub history_id: String,
+    pub original_file_name: String,
+    pub format: String,                 // 검색한 결과 파일 형식
+    pub line_index: usize,              // 0부터 시작하는 줄(큐) 번호
+    pub line: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+    pub timestamp: Option<f64>,         // srt/vtt에서 찾은 경우 큐 시작 시간 (초)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSearchResponse {
+    pub hits: Vec<TranscriptSearchHit>,
+    pub truncated: bool,                // 결과 최대 개수에 도달해 검색을 멈췄는지 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedWord {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTimedLine {
+    pub start: f64,
+    pub end: f64,
+    pub words: Vec<TimedWord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionResources {
+    pub history_id: String,
+    pub pid: Option<u32>,
+    pub running: bool,                  // 프로세스가 이미 끝났으면 false
+    pub rss_bytes: Option<u64>,         // 상주 메모리
+    pub cpu_percent: Option<f32>,       // 직전 측정 이후 CPU 사용률 (여러 코어 사용 시 100% 초과 가능)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionEta {
+    pub history_id: String,
+    pub progress: f64,                  // 0.0 ~ 1.0 (오디오 길이를 알 수 없으면 0)
+    pub elapsed_seconds: f64,
+    pub remaining_seconds: Option<f64>, // 진행 속도를 아직 추정할 수 없으면 None
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadScriptStatus {
+    pub path: PathBuf,
+    pub exists: bool,
+    pub readable: bool,
+    pub model_count: usize,             // 스크립트에서 파싱한 모델 수
+    pub error: Option<String>,          // 읽기/파싱 실패 사유
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionSegmentEvent {
+    pub history_id: String,
+    pub start: f64,                     // 오디오 구간 시작 (초)
+    pub end: f64,                       // 오디오 구간 끝 (초)
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateHistoryGroup {
+    pub original_file_path: PathBuf,
+    pub input_file_size: Option<u64>,   // 크기를 알 수 없으면 경로만으로 묶음
+    pub history_ids: Vec<String>,       // 최신순
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelValidation {
+    pub model_name: String,
+    pub valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVerification {
+    pub model_name: String,
+    pub passed: bool,
+    pub error: Option<String>,
+    pub repairable: bool,       // 실패했고 다시 받을 수 있는 공식 모델이면 true (repair_model 제안용)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelChecksum {
+    pub model_name: String,
+    pub expected: Option<String>,       // 기대 SHA256 (알 수 없는 모델이면 None)
+    pub actual: String,                 // 내려받은 파일의 SHA256
+    pub matches: Option<bool>,          // 기대값을 모르면 None
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ModelRepairStatus {
+    Repairing,
+    Repaired,
+    Failed,
+    Unrepairable,   // 알려진 다운로드 URL이 없는 모델 (직접 추가한 모델 등)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRepairProgress {
+    pub model_name: String,
+    pub status: ModelRepairStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QuantizeStatus {
+    Building,       // quantize 도구가 없어 빌드하는 중
+    Quantizing,
+    Validating,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantizeProgress {
+    pub source_model: String,
+    pub target_model: String,           // 예: "large-v3-q5_0"
+    pub status: QuantizeStatus,
+    pub message: Option<String>,        // quantize 출력 줄 또는 에러 메시지
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelRepairSummary {
+    pub repaired: Vec<String>,
+    pub failed: Vec<ModelRepairProgress>,   // 실패 사유 포함
+    pub unrepairable: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DownloadTool {
+    Wget,
+    Curl,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadToolInfo {
+    pub tool: DownloadTool,
+    pub available: bool,
+    pub version: Option<String>,        // 예: "1.21.4"
+    pub supports_resume: bool,          // 도움말에 이어받기 옵션이 있는지 (서버의 Range 지원 여부는 아님)
+    pub supports_proxy: bool,           // 도움말에 프록시 옵션이 있는지 (http_proxy/https_proxy 환경 변수를 따름)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BuildBackend {
+    Cpu,            // 가속 없음 (macOS에서도 Metal 끔)
+    Metal,          // Apple GPU (macOS)
+    Cuda,           // NVIDIA GPU (CUDA Toolkit 필요)
+    OpenBlas,       // CPU BLAS 라이브러리
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuBackendDetection {
+    pub available: Vec<BuildBackend>,   // 이 시스템에서 빌드 가능한 백엔드
+    pub suggested: BuildBackend,        // 기본으로 제안하는 백엔드
+    pub nvidia_gpus: Vec<String>,       // nvidia-smi로 찾은 GPU 이름
+    pub cuda_toolkit: bool,             // nvcc 사용 가능 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationStatus {
+    pub installed: bool,
+    pub binary_path: Option<String>,    // 실제로 사용할 whisper 실행 파일
+    pub custom_binary_path: Option<String>, // 설정된 사용자 지정 바이너리 (없으면 None)
+    pub using_custom_binary: bool,      // 사용자 지정 바이너리가 --help 확인을 통과해 사용 중인지 여부
+    pub custom_binary_error: Option<String>, // 사용자 지정 바이너리를 쓸 수 없는 이유 (기본 탐색으로 대체됨)
+    pub installed_version: Option<String>, // 마지막 설치 시 기록된 whisper.cpp 버전 (.whisper-version)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadCapabilities {
+    pub tools: Vec<DownloadToolInfo>,
+    pub preferred: Option<DownloadTool>, // 다운로드에 실제로 사용하는 도구 (없으면 None)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDownloadSize {
+    pub model_name: String,
+    pub bytes: u64,
+    pub display: String,                // 사람이 읽기 쉬운 크기 (예: "2.9 GB")
+    pub estimated: bool,                // 서버에 연결할 수 없어 예상 크기를 사용했는지 여부
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    pub open_files_soft: Option<u64>,   // 열린 파일 수 soft 제한 (무제한이거나 알 수 없으면 None)
+    pub open_files_hard: Option<u64>,
+    pub open_files_in_use: Option<u64>, // 현재 열려 있는 디스크립터 수
+    pub max_processes: Option<u64>,     // 사용자당 프로세스 수 제한
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchLimitCheck {
+    pub batch_size: usize,
+    pub safe_concurrency: usize,        // 현재 제한에서 동시에 실행해도 안전한 변환 수
+    pub within_limits: bool,
+    pub warnings: Vec<String>,
+    pub limits: ResourceLimits,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExportResult {
+    pub history_id: String,
+    pub output_path: Option<String>,    // 실패하면 None
+    pub derived: bool,                  // 저장된 결과가 없어 다른 결과에서 변환했는지 여부
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryPackage {
+    pub output_path: String,            // 만들어진 zip 경로
+    pub included_formats: Vec<String>,
+    pub derived_formats: Vec<String>,   // 저장된 결과가 없어 다른 결과에서 변환한 형식들
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MediaCategory {
+    Video,
+    Audio,      // 강의, 팟캐스트 등 일반 음성
+    Music,      // 음악 메타데이터 태그가 있는 오디오
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputFormatSuggestion {
+    pub category: MediaCategory,
+    pub formats: Vec<String>,           // UI에서 미리 선택할 형식 ID
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioAnalysis {
+    pub file_path: String,
+    pub duration_seconds: Option<f64>,
+    pub mean_volume_db: Option<f64>,
+    pub max_volume_db: Option<f64>,
+    pub clipped_samples: u64,           // 0dB에 닿은 샘플 수
+    pub clipped_ratio: f64,             // 전체 샘플 중 클리핑 비율 (0.0 ~ 1.0)
+    pub silence_seconds: f64,
+    pub silence_ratio: f64,             // 전체 길이 중 무음 비율 (0.0 ~ 1.0)
+    pub quality: AudioQuality,
+    pub issues: Vec<AudioIssue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AudioQuality {
+    Good,
+    Fair,   // 변환은 가능하지만 정확도가 떨어질 수 있음
+    Poor,   // 대부분 무음이거나 심하게 클리핑됨
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AudioIssue {
+    MostlySilent,
+    HeavilyClipped,
+    TooQuiet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelProvenance {
+    pub model_name: String,
+    pub base_model: String,             // 양자화 변형이면 원본 모델 이름
+    pub quantized: bool,
+    pub original_author: String,        // 원본 가중치 제공자
+    pub source_url: String,
+    pub license: String,                // SPDX 식별자 (예: "MIT")
+    pub license_url: String,
+    pub repository_url: String,         // ggml 변환 모델 저장소
+    pub download_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ModelFileFormat {
+    Ggml,           // whisper.cpp 구형 바이너리 레이아웃
+    Gguf,
+}
+
+/// 변환 중 whisper_model_load 로그에서 읽은 모델 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadedModelInfo {
+    pub model_type: Option<String>,     // "type = 1 (tiny)"의 괄호 안 이름 (예: "tiny", "large v3")
+    pub n_vocab: Option<i32>,           // 51864면 영어 전용 모델
+    pub n_audio_layer: Option<i32>,
+    pub model_size_mb: Option<f64>,     // "model size = 77.11 MB"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelHeader {
+    pub file_path: String,
+    pub file_size: u64,
+    pub format: ModelFileFormat,
+    pub gguf_version: Option<u32>,
+    pub architecture: Option<String>,   // gguf general.architecture
+    pub n_vocab: Option<i32>,
+    pub n_audio_ctx: Option<i32>,
+    pub n_audio_state: Option<i32>,
+    pub n_audio_head: Option<i32>,
+    pub n_audio_layer: Option<i32>,
+    pub n_text_ctx: Option<i32>,
+    pub n_text_state: Option<i32>,
+    pub n_text_head: Option<i32>,
+    pub n_text_layer: Option<i32>,
+    pub n_mels: Option<i32>,
+    pub ftype: Option<String>,          // 가중치 자료형 (예: "f16", "q5_0")
+    pub model_type: Option<String>,     // 인코더 레이어 수로 추정한 크기 등급 (tiny ~ large)
+    pub multilingual: Option<bool>,     // 어휘 크기로 추정 (영어 전용 모델은 51864)
+    pub metadata: std::collections::HashMap<String, String>, // gguf 키-값 메타데이터 원본 (ggml은 비어 있음)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SummaryFormat {
+    Markdown,
+    Html,
+}
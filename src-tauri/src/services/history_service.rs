@@ -1,12 +1,23 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::Result;
 use serde_json;
 use crate::models::*;
+use crate::utils::{is_video_file, probe_media_duration, read_last_lines, MediaTools};
+use crate::services::settings_service::SettingsService;
+use crate::utils::ass::{default_ass_header, karaoke_dialogue};
+use crate::utils::model_header::{describe_model_mismatch, parse_model_load_log};
+use crate::utils::whisper_json::{average_confidence, parse_whisper_json_segments, parse_whisper_json_words};
+use crate::utils::language::{detect_dominant_script, expected_scripts_for_language};
+use crate::utils::subtitle::{
+    analyze_coverage, chapters_to_text, compute_speaking_rate, format_chapter_timestamp, format_srt_cue, format_vtt_cue, group_into_chapters, group_words_into_lines,
+    merge_duplicate_segments, merge_short_segments, parse_subtitle_segments, rebase_segments, segments_to_plain_text, segments_to_proofreading_doc,
+    segments_to_srt, segments_to_vtt, split_segments, VTT_HEADER,
+};
 
 /// 변환 히스토리 관리 서비스
 /// 
 /// 디렉토리 구조:
-/// ~/.whisper-gui/
+/// ~/.whisper-gui/             # 기본 데이터 디렉토리 (설정의 data_dir로 변경 가능)
 /// ├── whisper.cpp/          # 기존 whisper.cpp 저장소
 /// ├── models/               # 기존 모델 파일들
 /// ├── results/              # 변환 결과 저장소
@@ -24,12 +35,77 @@ pub struct HistoryService {
     pub whisper_gui_dir: PathBuf,
     pub results_dir: PathBuf,
     pub history_index_file: PathBuf,
+    search_cache: SearchCache,
+    media_tools: MediaTools,            // 설정의 ffmpeg/ffprobe 경로 (set_media_tools로 갱신)
 }
 
+/// deduplicate_segments로 만든 결과의 variant
+const DEDUP_RESULT_VARIANT: &str = "dedup";
+
+/// merge_segments로 만든 결과의 variant
+const MERGED_RESULT_VARIANT: &str = "merged";
+
+/// merge_short_segments로 만든 결과의 variant
+const SHORT_MERGED_RESULT_VARIANT: &str = "short-merged";
+
+/// 히스토리 디렉토리에 저장되는 whisper 프로세스 출력 로그
+pub const HISTORY_LOG_FILE: &str = "logs.txt";
+
+/// export_html_player가 files 디렉토리에 만드는 플레이어 파일
+const HTML_PLAYER_FILE: &str = "player.html";
+
+/// 짧은 큐를 합칠 때 허용하는 최대 간격 (초, 이보다 긴 침묵을 가로지르는 큐는 만들지 않음)
+const SHORT_SEGMENT_MAX_GAP_SECONDS: f64 = 1.0;
+
+/// 전체 검색 시 동시에 읽을 결과 파일 수
+const SEARCH_WORKERS: usize = 8;
+
+/// 전체 검색 결과 기본 최대 개수
+const DEFAULT_SEARCH_RESULT_CAP: usize = 200;
+
+/// 이 길이(초) 이상인 오디오에서만 "지나치게 짧은 결과"를 판단
+const SHORT_RESULT_MIN_AUDIO_SECONDS: f64 = 30.0;
+
+/// 오디오 1초당 최소 기대 글자 수 (공백 제외, 말이 드문 녹음도 넘도록 느슨하게 설정)
+const SHORT_RESULT_MIN_CHARS_PER_SECOND: f64 = 0.5;
+
+/// 히스토리 목록 미리보기의 최대 글자 수
+const PREVIEW_MAX_CHARS: usize = 160;
+
+/// 미리보기 일괄 재생성 시 동시에 읽을 결과 파일 수
+const PREVIEW_WORKERS: usize = 8;
+
+/// 배치 요약에 포함할 항목별 미리보기 줄 수
+const BATCH_SUMMARY_PREVIEW_LINES: usize = 3;
+
+/// 배치 요약 문서의 항목 하나
+struct BatchSummaryEntry {
+    history: TranscriptionHistory,
+    audio_seconds: Option<f64>,         // 마지막 자막 큐 종료 시간 기준
+    word_count: usize,
+    preview: Vec<String>,
+    result_path: Option<PathBuf>,       // 전체 결과 (txt 우선)
+}
+
+/// 검색용으로 파싱한 결과 파일 한 줄 (srt/vtt는 큐 하나)
+#[derive(Clone)]
+struct SearchableLine {
+    text: String,
+    start: Option<f64>,
+}
+
+/// 결과 파일 경로별 (수정 시간, 파싱된 줄들) 캐시
+type SearchCache = std::sync::Arc<
+    std::sync::Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, std::sync::Arc<Vec<SearchableLine>>)>>
+>;
+
 impl HistoryService {
     pub fn new() -> Self {
-        let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let whisper_gui_dir = home_dir.join(".whisper-gui");
+        Self::with_data_dir(AppSettings::default_data_dir())
+    }
+    
+    /// 지정한 데이터 디렉토리를 기준으로 서비스를 생성합니다
+    pub fn with_data_dir(whisper_gui_dir: PathBuf) -> Self {
         let results_dir = whisper_gui_dir.join("results");
         let history_index_file = whisper_gui_dir.join("history.json");
         
@@ -37,9 +113,28 @@ impl HistoryService {
             whisper_gui_dir,
             results_dir,
             history_index_file,
+            search_cache: Default::default(),
+            media_tools: MediaTools::from_settings(&SettingsService::new().load()),
         }
     }
     
+    pub fn media_tools(&self) -> &MediaTools {
+        &self.media_tools
+    }
+    
+    /// ffmpeg/ffprobe 경로 설정이 바뀌면 호출합니다
+    pub fn set_media_tools(&mut self, media_tools: MediaTools) {
+        self.media_tools = media_tools;
+    }
+    
+    /// 데이터 디렉토리를 옮긴 뒤 경로만 새 위치로 바꿉니다 (경로 기준 검색 캐시는 비움)
+    pub fn set_data_dir(&mut self, whisper_gui_dir: PathBuf) {
+        self.results_dir = whisper_gui_dir.join("results");
+        self.history_index_file = whisper_gui_dir.join("history.json");
+        self.whisper_gui_dir = whisper_gui_dir;
+        self.search_cache.lock().unwrap().clear();
+    }
+    
     /// 필요한 디렉토리들을 생성합니다
     pub async fn ensure_directories(&self) -> Result<()> {
         tokio::fs::create_dir_all(&self.results_dir).await?;
@@ -56,12 +151,13 @@ impl HistoryService {
     ) -> Result<TranscriptionHistory> {
         self.ensure_directories().await?;
         
-        let history = TranscriptionHistory::new(
+        let mut history = TranscriptionHistory::new(
             original_file_name,
             original_file_path,
             model_used,
             options_used,
         );
+        history.input_file_size = tokio::fs::metadata(&history.original_file_path).await.ok().map(|m| m.len());
         
         // 히스토리별 디렉토리 생성
         let history_dir = self.get_history_directory(&history.id);
@@ -103,6 +199,9 @@ impl HistoryService {
                     file_path: target_path,
                     file_size: metadata.len(),
                     created_at: chrono::Utc::now().to_rfc3339(),
+                    partial: false,
+                    language: None,
+                    variant: None,
                 };
                 
                 history.results.push(result_file);
@@ -140,6 +239,9 @@ impl HistoryService {
                     file_path,
                     file_size: metadata.len(),
                     created_at: chrono::Utc::now().to_rfc3339(),
+                    partial: false,
+                    language: None,
+                    variant: None,
                 };
                 
                 history.results.push(result_file);
@@ -159,6 +261,77 @@ impl HistoryService {
         Ok(history)
     }
     
+    /// 다시 만든 결과 파일들의 크기와 생성 시간을 갱신합니다 (없던 형식은 새로 등록, 상태는 유지)
+    pub async fn replace_result_files(
+        &self,
+        history_id: &str,
+        result_files: Vec<(PathBuf, String)>, // (파일 경로, 형식)
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        
+        for (file_path, format) in result_files {
+            let metadata = tokio::fs::metadata(&file_path).await?;
+            let result_file = TranscriptionResult {
+                format,
+                file_path,
+                file_size: metadata.len(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                partial: false,
+                language: None,
+                variant: None,
+            };
+            
+            match history.results.iter_mut().find(|r| r.format == result_file.format && r.language.is_none()) {
+                Some(existing) => *existing = result_file,
+                None => history.results.push(result_file),
+            }
+        }
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 번역된 결과 파일을 대상 언어로 표시해 등록합니다 (같은 형식/언어의 이전 번역은 교체)
+    pub async fn register_translated_result(
+        &self,
+        history_id: &str,
+        file_path: PathBuf,
+        format: &str,
+        language: &str,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        let metadata = tokio::fs::metadata(&file_path).await?;
+        
+        history.results.retain(|r| !(r.format == format && r.language.as_deref() == Some(language)));
+        history.results.push(TranscriptionResult {
+            format: format.to_string(),
+            file_path,
+            file_size: metadata.len(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            partial: false,
+            language: Some(language.to_string()),
+            variant: None,
+        });
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 히스토리 상태만 바꿉니다 (대기열에 넣거나 대기열에서 시작할 때 사용)
+    pub async fn update_history_status(&self, history_id: &str, status: TranscriptionStatus) -> Result<()> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.status = status;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(())
+    }
+    
     /// 변환 실패 시 히스토리를 업데이트합니다
     pub async fn mark_history_failed(
         &self,
@@ -174,6 +347,79 @@ impl HistoryService {
         Ok(failed_history)
     }
     
+    /// 대기열에 있던 채로 앱이 종료된 히스토리를 실패로 표시하고 그 수를 반환합니다
+    ///
+    /// 대기열은 메모리에만 있으므로 다시 시작한 뒤에는 이 항목들이 실행되지 않습니다.
+    pub async fn fail_interrupted_queued(&self) -> Result<usize> {
+        let queued: Vec<String> = self.load_history_index().await?
+            .into_iter()
+            .filter(|history| matches!(history.status, TranscriptionStatus::Queued))
+            .map(|history| history.id)
+            .collect();
+        
+        for history_id in &queued {
+            self.mark_history_failed(history_id, "Interrupted while queued (app was closed)".to_string()).await?;
+        }
+        
+        Ok(queued.len())
+    }
+    
+    /// 변환 취소 시 그때까지 생성된 결과 파일들을 부분 결과로 등록하고 상태를 Cancelled로 바꿉니다
+    pub async fn mark_history_cancelled(
+        &self,
+        history_id: &str,
+        partial_files: Vec<(PathBuf, String)>, // (파일 경로, 형식)
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        Self::attach_partial_results(&mut history, partial_files).await;
+        
+        let cancelled_history = history.mark_cancelled();
+        
+        self.save_history_metadata(&cancelled_history).await?;
+        self.update_history_index(&cancelled_history).await?;
+        
+        Ok(cancelled_history)
+    }
+    
+    /// 취소하지 않았는데 프로세스가 중단된 경우 부분 결과를 등록하고 상태를 Failed로 바꿉니다
+    pub async fn mark_history_interrupted(
+        &self,
+        history_id: &str,
+        partial_files: Vec<(PathBuf, String)>, // (파일 경로, 형식)
+        error_message: String,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        Self::attach_partial_results(&mut history, partial_files).await;
+        
+        let failed_history = history.mark_failed(error_message);
+        
+        self.save_history_metadata(&failed_history).await?;
+        self.update_history_index(&failed_history).await?;
+        
+        Ok(failed_history)
+    }
+    
+    /// 비어 있지 않은 파일만 부분 결과로 등록합니다 (같은 형식의 기존 원본 결과는 대체)
+    async fn attach_partial_results(history: &mut TranscriptionHistory, partial_files: Vec<(PathBuf, String)>) {
+        for (file_path, format) in partial_files {
+            let metadata = match tokio::fs::metadata(&file_path).await {
+                Ok(metadata) if metadata.len() > 0 => metadata,
+                _ => continue,
+            };
+            
+            history.results.retain(|r| r.format != format || r.language.is_some());
+            history.results.push(TranscriptionResult {
+                format,
+                file_path,
+                file_size: metadata.len(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                partial: true,
+                language: None,
+                variant: None,
+            });
+        }
+    }
+    
     /// 히스토리 목록을 조회합니다
     pub async fn list_history(&self, query: HistoryQuery) -> Result<HistoryListResponse> {
         let index = self.load_history_index().await?;
@@ -243,6 +489,42 @@ impl HistoryService {
         Ok(history)
     }
     
+    /// 쿼리 조건에 맞는 모든 항목의 태그를 일괄 추가/제거합니다 (실제로 바뀐 항목 수 반환)
+    ///
+    /// 인덱스는 모든 항목을 처리한 뒤 한 번만 저장합니다.
+    pub async fn bulk_tag(
+        &self,
+        query: &HistoryQuery,
+        tags_to_add: &[String],
+        tags_to_remove: &[String],
+    ) -> Result<usize> {
+        let mut index = self.load_history_index().await?;
+        let mut updated = 0;
+        
+        for item in index.iter_mut() {
+            if !self.matches_query(item, query) {
+                continue;
+            }
+            
+            let mut history = self.load_history_metadata(&item.id).await?;
+            let tags = apply_tag_changes(&history.tags, tags_to_add, tags_to_remove);
+            if tags == history.tags {
+                continue;
+            }
+            
+            history.tags = tags;
+            self.save_history_metadata(&history).await?;
+            *item = history;
+            updated += 1;
+        }
+        
+        if updated > 0 {
+            self.save_history_index(&index).await?;
+        }
+        
+        Ok(updated)
+    }
+    
     /// 히스토리 항목의 메모를 업데이트합니다
     pub async fn update_history_notes(
         &self,
@@ -258,104 +540,1274 @@ impl HistoryService {
         Ok(history)
     }
     
-    /// 특정 결과 파일의 경로를 반환합니다
-    pub fn get_result_file_path(&self, history_id: &str, format: &str) -> PathBuf {
-        self.get_history_directory(history_id)
-            .join("files")
-            .join(format!("result.{}", format))
+    /// 히스토리 항목의 사용자 정의 필드를 설정합니다 (기존 값은 덮어씀)
+    pub async fn set_history_custom_field(
+        &self,
+        history_id: &str,
+        key: String,
+        value: String,
+    ) -> Result<TranscriptionHistory> {
+        let key = key.trim().to_string();
+        if key.is_empty() {
+            return Err(anyhow::anyhow!("Custom field key must not be empty"));
+        }
+        
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.custom_fields.insert(key, value);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
     }
     
-    /// 히스토리 디렉토리 경로를 반환합니다 (public)
-    pub fn get_history_directory(&self, history_id: &str) -> PathBuf {
-        self.results_dir.join(history_id)
+    /// 히스토리 항목의 사용자 정의 필드를 삭제합니다
+    pub async fn remove_history_custom_field(
+        &self,
+        history_id: &str,
+        key: &str,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.custom_fields.remove(key);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
     }
     
-    /// 히스토리 메타데이터 파일 경로를 반환합니다
-    fn get_metadata_file_path(&self, history_id: &str) -> PathBuf {
-        self.get_history_directory(history_id).join("metadata.json")
+    /// 히스토리 항목의 사용자 정의 필드 전체를 교체합니다 (키 앞뒤 공백 제거)
+    pub async fn update_history_custom_fields(
+        &self,
+        history_id: &str,
+        custom_fields: std::collections::HashMap<String, String>,
+    ) -> Result<TranscriptionHistory> {
+        let mut normalized = std::collections::HashMap::new();
+        for (key, value) in custom_fields {
+            let key = key.trim().to_string();
+            if key.is_empty() {
+                return Err(anyhow::anyhow!("Custom field key must not be empty"));
+            }
+            normalized.insert(key, value);
+        }
+        
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.custom_fields = normalized;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
     }
     
-    /// 히스토리 메타데이터를 저장합니다
-    async fn save_history_metadata(&self, history: &TranscriptionHistory) -> Result<()> {
-        let metadata_path = self.get_metadata_file_path(&history.id);
-        let json_content = serde_json::to_string_pretty(history)?;
-        tokio::fs::write(metadata_path, json_content).await?;
-        Ok(())
+    /// JSON 결과가 있으면 세그먼트 신뢰도 평균을 계산해 기록합니다
+    pub async fn record_average_confidence(&self, history_id: &str) -> Result<Option<f32>> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        let json = match history.results.iter().find(|r| r.format == "json") {
+            Some(json) => json,
+            None => return Ok(None),
+        };
+        
+        let content = tokio::fs::read_to_string(&json.file_path).await?;
+        history.avg_confidence = average_confidence(&parse_whisper_json_segments(&content)?);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history.avg_confidence)
     }
     
-    /// 히스토리 메타데이터를 로드합니다
-    async fn load_history_metadata(&self, history_id: &str) -> Result<TranscriptionHistory> {
-        let metadata_path = self.get_metadata_file_path(history_id);
-        let json_content = tokio::fs::read_to_string(metadata_path).await?;
-        let history: TranscriptionHistory = serde_json::from_str(&json_content)?;
+    /// 언어 규칙이 적용된 경우 규칙을 기록하고 실제 사용된 옵션으로 갱신합니다
+    pub async fn record_language_rule(
+        &self,
+        history_id: &str,
+        language: &str,
+        options_used: std::collections::HashMap<String, String>,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.applied_language_rule = Some(language.to_string());
+        history.options_used = options_used;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
         Ok(history)
     }
     
-    /// 히스토리 인덱스를 로드합니다
-    async fn load_history_index(&self) -> Result<Vec<TranscriptionHistory>> {
-        if !self.history_index_file.exists() {
-            return Ok(Vec::new());
-        }
+    /// 두 히스토리 항목의 모델/옵션 차이를 비교합니다
+    pub async fn diff_history_options(
+        &self,
+        history_id_a: &str,
+        history_id_b: &str,
+    ) -> Result<HistoryOptionsDiff> {
+        let history_a = self.load_history_metadata(history_id_a).await?;
+        let history_b = self.load_history_metadata(history_id_b).await?;
+
+        Ok(diff_options(&history_a, &history_b))
+    }
+
+    /// adaptive_threads 실행의 스레드 시도 기록을 저장합니다
+    pub async fn record_thread_attempts(
+        &self,
+        history_id: &str,
+        attempts: Vec<ThreadAttempt>,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.thread_attempts = attempts;
         
-        let json_content = tokio::fs::read_to_string(&self.history_index_file).await?;
-        let index: Vec<TranscriptionHistory> = serde_json::from_str(&json_content)?;
-        Ok(index)
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
     }
     
-    /// 히스토리 인덱스를 저장합니다
-    async fn save_history_index(&self, index: &[TranscriptionHistory]) -> Result<()> {
-        let json_content = serde_json::to_string_pretty(index)?;
-        tokio::fs::write(&self.history_index_file, json_content).await?;
-        Ok(())
+    /// 결과 텍스트 앞부분으로 목록 표시용 미리보기를 만들어 저장합니다
+    pub async fn record_preview(&self, history_id: &str) -> Result<Option<String>> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.preview = make_preview(&self.read_result_text(&history).await?);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history.preview)
     }
     
-    /// 히스토리 인덱스를 업데이트합니다
-    async fn update_history_index(&self, history: &TranscriptionHistory) -> Result<()> {
-        let mut index = self.load_history_index().await?;
+    /// 완료된 항목 중 미리보기가 없는 항목(force면 전체)의 미리보기를 다시 만들고, 갱신한 항목 수를 반환합니다
+    pub async fn regenerate_previews(&self, force: bool) -> Result<usize> {
+        use futures_util::StreamExt;
         
-        // 기존 항목 업데이트 또는 새 항목 추가
-        if let Some(existing) = index.iter_mut().find(|item| item.id == history.id) {
-            *existing = history.clone();
-        } else {
-            index.push(history.clone());
+        let targets: Vec<TranscriptionHistory> = self.load_history_index().await?
+            .into_iter()
+            .filter(|item| matches!(item.status, TranscriptionStatus::Completed))
+            .filter(|item| force || item.preview.is_none())
+            .collect();
+        
+        // 결과 파일 읽기만 동시에 하고, 메타데이터/인덱스는 순서대로 갱신
+        // (읽는 동안 다른 작업이 바꾼 인덱스 항목을 덮어쓰지 않도록 항목마다 최신 인덱스에 반영)
+        let previews: Vec<(String, Option<String>)> = futures_util::stream::iter(targets)
+            .map(|history| async move {
+                let preview = self.read_result_text(&history).await.ok().and_then(|text| make_preview(&text));
+                (history.id, preview)
+            })
+            .buffer_unordered(PREVIEW_WORKERS)
+            .collect()
+            .await;
+        
+        let mut updated = 0;
+        for (history_id, preview) in previews {
+            let preview = match preview {
+                Some(preview) => preview,
+                None => continue,
+            };
+            
+            let mut history = match self.load_history_metadata(&history_id).await {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("Failed to load history {} for preview: {}", history_id, e);
+                    continue;
+                }
+            };
+            history.preview = Some(preview);
+            self.save_history_metadata(&history).await?;
+            self.update_history_index(&history).await?;
+            updated += 1;
         }
         
-        self.save_history_index(&index).await?;
-        Ok(())
+        Ok(updated)
     }
     
-    /// 쿼리 조건에 맞는지 확인합니다
-    fn matches_query(&self, item: &TranscriptionHistory, query: &HistoryQuery) -> bool {
-        // 검색어 필터
-        if let Some(search) = &query.search {
-            if !item.original_file_name.to_lowercase().contains(&search.to_lowercase()) {
-                return false;
-            }
-        }
+    /// 프로세스 로그에서 whisper가 실제로 읽은 모델을 기록하고, 요청한 모델과 다르면 경고를 추가합니다
+    pub async fn record_loaded_model(&self, history_id: &str) -> Result<Option<LoadedModelInfo>> {
+        let log = match self.get_history_logs(history_id).await {
+            Ok(log) => log,
+            Err(_) => return Ok(None),
+        };
+        let loaded = match parse_model_load_log(&log) {
+            Some(loaded) => loaded,
+            None => return Ok(None),
+        };
         
-        // 모델 필터
-        if let Some(model) = &query.model_filter {
-            if item.model_used != *model {
-                return false;
-            }
+        let mut history = self.load_history_metadata(history_id).await?;
+        if let Some(message) = describe_model_mismatch(&history.model_used, &loaded) {
+            history = history.with_warning(HistoryWarning {
+                code: "model_mismatch".to_string(),
+                message,
+            });
         }
+        history.loaded_model = Some(loaded.clone());
         
-        // 형식 필터
-        if let Some(format) = &query.format_filter {
-            if !item.get_formats().contains(format) {
-                return false;
-            }
-        }
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
         
-        // 태그 필터
-        if let Some(tag) = &query.tag_filter {
-            if !item.tags.contains(tag) {
-                return false;
-            }
-        }
+        Ok(Some(loaded))
+    }
+    
+    /// 변환 시작 시점의 whisper.cpp 커밋 해시를 기록합니다
+    pub async fn record_whisper_commit(
+        &self,
+        history_id: &str,
+        commit: String,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.whisper_commit = Some(commit);
         
-        // 상태 필터
-        if let Some(status) = &query.status_filter {
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 재실행 때 그대로 복원할 수 있도록 결과 파일 관련 요청(추가 파생 형식, 수정 시간 유지)을 기록합니다
+    pub async fn record_output_preferences(
+        &self,
+        history_id: &str,
+        export_formats: Vec<String>,
+        preserve_source_mtime: bool,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.export_formats = export_formats;
+        history.preserve_source_mtime = preserve_source_mtime;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 변환에 사용한 오디오 트랙 선택을 기록합니다
+    pub async fn record_audio_track(
+        &self,
+        history_id: &str,
+        choice: AudioTrackChoice,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.audio_track = Some(choice);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 변환에 사용한 프로세스 우선순위를 기록합니다
+    pub async fn record_process_priority(
+        &self,
+        history_id: &str,
+        priority: ProcessPriority,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.process_priority = priority;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 세그먼트별 처리 시간 측정 결과를 저장합니다
+    pub async fn record_segment_timings(
+        &self,
+        history_id: &str,
+        timings: Vec<SegmentTiming>,
+    ) -> Result<()> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        history.segment_timings = timings;
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(())
+    }
+    
+    /// 세그먼트별 처리 시간 측정 결과를 반환합니다 (측정되지 않았으면 빈 목록)
+    pub async fn get_segment_timing(&self, history_id: &str) -> Result<Vec<SegmentTiming>> {
+        let history = self.load_history_metadata(history_id).await?;
+        Ok(history.segment_timings)
+    }
+    
+    /// 히스토리 항목에 경고를 추가합니다 (같은 코드의 기존 경고는 교체)
+    pub async fn add_history_warning(
+        &self,
+        history_id: &str,
+        warning: HistoryWarning,
+    ) -> Result<TranscriptionHistory> {
+        let history = self.load_history_metadata(history_id).await?
+            .with_warning(warning);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 결과 파일에서 타임스탬프를 제외한 텍스트를 읽어옵니다 (txt 우선, 없으면 srt/vtt)
+    pub async fn read_result_text(&self, history: &TranscriptionHistory) -> Result<String> {
+        if let Some(txt) = history.results.iter().find(|r| r.format == "txt") {
+            return Ok(tokio::fs::read_to_string(&txt.file_path).await?);
+        }
+        
+        let subtitle = history.results.iter()
+            .find(|r| r.format == "srt" || r.format == "vtt")
+            .ok_or_else(|| anyhow::anyhow!("No text result found for history {}", history.id))?;
+        
+        let content = tokio::fs::read_to_string(&subtitle.file_path).await?;
+        Ok(segments_to_plain_text(&parse_subtitle_segments(&content)))
+    }
+    
+    /// 요청 언어와 결과 텍스트의 문자 체계가 일치하는지 확인하고, 불일치 시 경고를 기록합니다
+    ///
+    /// 언어가 auto이거나 문자 체계를 알 수 없는 언어인 경우 None을 반환합니다.
+    pub async fn check_language_mismatch(&self, history_id: &str) -> Result<Option<LanguageCheck>> {
+        let history = self.load_history_metadata(history_id).await?;
+        let requested_language = requested_language(&history);
+        
+        let expected_scripts = expected_scripts_for_language(&requested_language);
+        if requested_language == "auto" || expected_scripts.is_empty() {
+            return Ok(None);
+        }
+        
+        let text = self.read_result_text(&history).await?;
+        let detected_script = detect_dominant_script(&text);
+        let mismatch = detected_script
+            .map(|script| !expected_scripts.contains(&script))
+            .unwrap_or(false);
+        
+        if mismatch {
+            self.add_history_warning(history_id, HistoryWarning {
+                code: "language_mismatch".to_string(),
+                message: format!(
+                    "Requested language '{}' but output is mostly {:?} script",
+                    requested_language,
+                    detected_script.unwrap_or(TextScript::Latin)
+                ),
+            }).await?;
+        }
+        
+        Ok(Some(LanguageCheck {
+            requested_language,
+            expected_scripts,
+            detected_script,
+            mismatch,
+        }))
+    }
+    
+    /// 결과 파일에 U+FFFD 대체 문자가 있는지 확인하고, 있으면 `possible_encoding_issue` 경고를 기록합니다
+    ///
+    /// 멀티바이트 문자가 토큰 경계에서 잘리면 유효하지 않은 UTF-8이 저장될 수 있어 바이트로 읽어 검사합니다.
+    /// whisper.cpp에는 출력 인코딩 옵션이 없고 대체 문자는 주로 max-len이 문자 중간에서 자를 때 생기므로,
+    /// 문제가 있으면 줄 길이 제한 옵션을 뺀 재실행 설정을 함께 반환합니다.
+    pub async fn check_encoding_issues(&self, history_id: &str) -> Result<EncodingCheck> {
+        let history = self.load_history_metadata(history_id).await?;
+        
+        let mut replacement_chars = 0;
+        let mut affected_formats = Vec::new();
+        for result in &history.results {
+            let bytes = match tokio::fs::read(&result.file_path).await {
+                Ok(bytes) => bytes,
+                Err(_) => continue,
+            };
+            let count = String::from_utf8_lossy(&bytes).matches('\u{FFFD}').count();
+            if count > 0 {
+                replacement_chars += count;
+                affected_formats.push(result.format.clone());
+            }
+        }
+        
+        let possible_issue = replacement_chars > 0;
+        if possible_issue {
+            self.add_history_warning(history_id, HistoryWarning {
+                code: "possible_encoding_issue".to_string(),
+                message: format!(
+                    "Found {} replacement character(s) in {} output",
+                    replacement_chars,
+                    affected_formats.join(", ")
+                ),
+            }).await?;
+        }
+        
+        let rerun_config = if possible_issue {
+            self.get_rerun_config(history_id).await.ok().map(|mut config| {
+                strip_line_length_options(&mut config.options);
+                config
+            })
+        } else {
+            None
+        };
+        
+        Ok(EncodingCheck {
+            replacement_chars,
+            affected_formats,
+            possible_issue,
+            rerun_config,
+        })
+    }
+    
+    /// 저장된 결과 파일이 비어 있거나 의미 없는 내용인지 검사합니다
+    ///
+    /// 오디오 길이는 자막 마지막 큐의 종료 시간을 우선 사용하고, 없으면 원본 파일을 ffprobe로 확인합니다.
+    /// 반복 비율은 "감사합니다"가 계속 반복되는 것 같은 환각 루프를 잡기 위한 값입니다.
+    pub async fn check_result_quality(&self, history_id: &str, format: &str) -> Result<ResultQuality> {
+        let result_path = self.get_result_file_path(history_id, format);
+        if !result_path.exists() {
+            return Err(anyhow::anyhow!("Result file not found: {}", format));
+        }
+        
+        let content = tokio::fs::read_to_string(&result_path).await?;
+        let (lines, subtitle_end): (Vec<String>, Option<f64>) = if format == "srt" || format == "vtt" {
+            let segments = parse_subtitle_segments(&content);
+            let end = segments.last().map(|s| s.end);
+            (segments.into_iter().map(|s| s.text).collect(), end)
+        } else {
+            (content.lines().map(str::to_string).collect(), None)
+        };
+        let lines: Vec<String> = lines.into_iter()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+        
+        let audio_duration_seconds = match subtitle_end {
+            Some(end) => Some(end),
+            None => {
+                let history = self.load_history_metadata(history_id).await?;
+                probe_media_duration(&self.media_tools, &history.original_file_path).await
+            }
+        };
+        
+        let text_chars = lines.iter()
+            .flat_map(|line| line.chars())
+            .filter(|c| !c.is_whitespace())
+            .count();
+        let suspiciously_short = audio_duration_seconds
+            .filter(|duration| *duration >= SHORT_RESULT_MIN_AUDIO_SECONDS)
+            .is_some_and(|duration| (text_chars as f64) < duration * SHORT_RESULT_MIN_CHARS_PER_SECOND);
+        
+        Ok(ResultQuality {
+            history_id: history_id.to_string(),
+            format: format.to_string(),
+            is_empty: content.is_empty(),
+            is_whitespace_only: !content.is_empty() && content.trim().is_empty(),
+            suspiciously_short,
+            repetition_ratio: repetition_ratio(&lines),
+            line_count: lines.len(),
+            audio_duration_seconds,
+        })
+    }
+    
+    /// 완료/실패한 히스토리 항목으로부터 재실행용 설정을 복원합니다
+    pub async fn get_rerun_config(&self, history_id: &str) -> Result<WhisperConfig> {
+        let history = self.load_history_metadata(history_id).await?;
+        
+        // 아직 시작하지 않은 대기 항목을 다시 실행하면 같은 변환이 두 번 돌게 됨
+        if matches!(history.status, TranscriptionStatus::Running | TranscriptionStatus::Queued) {
+            return Err(anyhow::anyhow!("History {} is still running or queued", history_id));
+        }
+        
+        Ok(WhisperConfig {
+            model: history.model_used,
+            input_file: history.original_file_path.to_string_lossy().to_string(),
+            options: history.options_used,
+            adaptive_threads: !history.thread_attempts.is_empty(),
+            export_formats: history.export_formats,
+            preserve_source_mtime: history.preserve_source_mtime,
+            process_priority: history.process_priority,
+            audio_track: history.audio_track.map(|choice| choice.preference),
+        })
+    }
+    
+    /// 타임스탬프가 있는 결과 파일(srt 우선, 없으면 vtt)에서 세그먼트를 읽어옵니다
+    pub async fn load_history_segments(&self, history_id: &str) -> Result<Vec<SubtitleSegment>> {
+        let history = self.load_history_metadata(history_id).await?;
+        
+        let source = ["srt", "vtt"].iter()
+            .find_map(|format| history.results.iter().find(|r| r.format == *format))
+            .ok_or_else(|| anyhow::anyhow!("No timestamped result (srt/vtt) found for history {}", history_id))?;
+        
+        let content = tokio::fs::read_to_string(&source.file_path).await?;
+        Ok(parse_subtitle_segments(&content))
+    }
+    
+    /// 무음 간격 기준으로 챕터를 나눠 YouTube 형식 챕터 목록을 내보냅니다
+    pub async fn export_chapters(
+        &self,
+        history_id: &str,
+        output_path: &str,
+        min_gap_seconds: f64,
+    ) -> Result<String> {
+        let segments = self.load_history_segments(history_id).await?;
+        let chapters = group_into_chapters(&segments, min_gap_seconds);
+        
+        if chapters.is_empty() {
+            return Err(anyhow::anyhow!("No segments found for history {}", history_id));
+        }
+        
+        tokio::fs::write(output_path, chapters_to_text(&chapters)).await?;
+        Ok(format!("Chapters exported to: {}", output_path))
+    }
+    
+    /// 일정 간격마다 [hh:mm:ss] 타임코드를 넣은 교정용 문서를 내보냅니다 (.md면 Markdown, 나머지는 텍스트)
+    pub async fn export_proofreading_doc(
+        &self,
+        history_id: &str,
+        interval_seconds: f64,
+        output_path: &str,
+    ) -> Result<String> {
+        if !interval_seconds.is_finite() || interval_seconds <= 0.0 {
+            return Err(anyhow::anyhow!("Interval must be greater than 0 seconds"));
+        }
+        
+        let history = self.load_history_metadata(history_id).await?;
+        let segments = self.load_history_segments(history_id).await?;
+        if segments.is_empty() {
+            return Err(anyhow::anyhow!("No segments found for history {}", history_id));
+        }
+        
+        let markdown = Path::new(output_path).extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"));
+        let content = segments_to_proofreading_doc(&history.original_file_name, &segments, interval_seconds, markdown);
+        
+        tokio::fs::write(output_path, content).await?;
+        Ok(format!("Proofreading document exported to: {}", output_path))
+    }
+    
+    /// 클릭 가능한 타임스탬프가 미디어 재생 위치를 옮기는 단일 HTML 플레이어를 files/player.html로 만듭니다
+    ///
+    /// media_reference는 <video>/<audio>의 src로 그대로 들어가므로 공유할 위치 기준의 상대 경로나 URL을 넘겨야 합니다.
+    pub async fn export_html_player(&self, history_id: &str, media_reference: &str) -> Result<PathBuf> {
+        let history = self.load_history_metadata(history_id).await?;
+        let segments = self.load_history_segments(history_id).await?;
+        if segments.is_empty() {
+            return Err(anyhow::anyhow!("No segments found for history {}", history_id));
+        }
+        
+        let output_path = self.get_history_directory(history_id).join("files").join(HTML_PLAYER_FILE);
+        tokio::fs::write(&output_path, html_player_document(&history.original_file_name, media_reference, &segments)).await?;
+        Ok(output_path)
+    }
+    
+    /// 결과 자막에서 연속 중복 세그먼트를 합쳐 files/result.dedup.{format}에 저장하고 파생 결과로 등록합니다
+    ///
+    /// 원본 결과 파일은 그대로 두며, 다시 실행하면 이전 정리 결과를 대체합니다.
+    pub async fn deduplicate_segments(
+        &self,
+        history_id: &str,
+        format: &str,
+        similarity_threshold: f64,
+    ) -> Result<DeduplicationReport> {
+        if !(0.0..=1.0).contains(&similarity_threshold) {
+            return Err(anyhow::anyhow!("Similarity threshold must be between 0.0 and 1.0"));
+        }
+        
+        let mut history = self.load_history_metadata(history_id).await?;
+        let result = history.results.iter()
+            .find(|r| r.format == format && r.language.is_none() && r.variant.is_none())
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: {}", format))?;
+        
+        let content = tokio::fs::read_to_string(&result.file_path).await?;
+        let segments = parse_subtitle_segments(&content);
+        let (cleaned, merged_count) = merge_duplicate_segments(&segments, similarity_threshold);
+        
+        let cleaned_content = match format {
+            "srt" => segments_to_srt(&cleaned),
+            "vtt" => segments_to_vtt(&cleaned),
+            _ => return Err(anyhow::anyhow!("Deduplication is only supported for srt/vtt, got: {}", format)),
+        };
+        
+        let output_path = self.get_history_directory(history_id)
+            .join("files")
+            .join(format!("result.dedup.{}", format));
+        tokio::fs::write(&output_path, &cleaned_content).await?;
+        
+        self.register_variant_result(&mut history, format, &output_path, cleaned_content.len() as u64, DEDUP_RESULT_VARIANT).await?;
+        
+        Ok(DeduplicationReport {
+            format: format.to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            original_count: segments.len(),
+            remaining_count: cleaned.len(),
+            merged_count,
+        })
+    }
+    
+    /// 짧은 단어 단위 큐를 합쳐 files/result.merged.{format}에 저장하고 파생 결과로 등록합니다 (srt 우선, 없으면 vtt)
+    ///
+    /// 원본 결과 파일은 그대로 두며, 다시 실행하면 이전 병합 결과를 대체합니다.
+    pub async fn merge_segments(
+        &self,
+        history_id: &str,
+        min_duration_seconds: f64,
+        max_gap_seconds: f64,
+    ) -> Result<SegmentMergeReport> {
+        if !min_duration_seconds.is_finite() || min_duration_seconds <= 0.0 {
+            return Err(anyhow::anyhow!("Minimum duration must be greater than 0"));
+        }
+        if !max_gap_seconds.is_finite() || max_gap_seconds < 0.0 {
+            return Err(anyhow::anyhow!("Maximum gap must not be negative"));
+        }
+        
+        let history = self.load_history_metadata(history_id).await?;
+        let result = ["srt", "vtt"].iter()
+            .find_map(|format| history.results.iter().find(|r| r.format == *format && r.language.is_none() && r.variant.is_none()))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: srt/vtt"))?;
+        
+        self.write_merged_result(history, &result, MERGED_RESULT_VARIANT, |segments| {
+            merge_short_segments(segments, min_duration_seconds, max_gap_seconds, None, false)
+        }).await
+    }
+    
+    /// 최소 길이보다 짧은 큐를 글자 수 제한 안에서 이웃 큐와 합쳐 files/result.short-merged.{format}에 저장합니다
+    ///
+    /// merge_segments와 달리 짧은 큐가 뒤에 오면 앞 큐에 붙이며, 결과 파일도 따로 저장해 서로 덮어쓰지 않습니다.
+    pub async fn merge_short_segments(
+        &self,
+        history_id: &str,
+        format: &str,
+        min_duration_ms: u64,
+        max_merged_chars: usize,
+    ) -> Result<SegmentMergeReport> {
+        if min_duration_ms == 0 {
+            return Err(anyhow::anyhow!("Minimum duration must be greater than 0"));
+        }
+        if max_merged_chars == 0 {
+            return Err(anyhow::anyhow!("Maximum merged characters must be greater than 0"));
+        }
+        if format != "srt" && format != "vtt" {
+            return Err(anyhow::anyhow!("Merging is only supported for srt/vtt, got: {}", format));
+        }
+        
+        let history = self.load_history_metadata(history_id).await?;
+        let result = history.results.iter()
+            .find(|r| r.format == format && r.language.is_none() && r.variant.is_none())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: {}", format))?;
+        
+        let min_duration_seconds = min_duration_ms as f64 / 1000.0;
+        self.write_merged_result(history, &result, SHORT_MERGED_RESULT_VARIANT, |segments| {
+            merge_short_segments(segments, min_duration_seconds, SHORT_SEGMENT_MAX_GAP_SECONDS, Some(max_merged_chars), true)
+        }).await
+    }
+    
+    /// srt/vtt 결과의 세그먼트를 합쳐 원본 옆에 result.{variant}.{format}으로 저장하고 파생 결과로 등록합니다
+    async fn write_merged_result(
+        &self,
+        mut history: TranscriptionHistory,
+        result: &TranscriptionResult,
+        variant: &str,
+        merge: impl FnOnce(&[SubtitleSegment]) -> Vec<SubtitleSegment>,
+    ) -> Result<SegmentMergeReport> {
+        let content = tokio::fs::read_to_string(&result.file_path).await?;
+        let segments = parse_subtitle_segments(&content);
+        if segments.is_empty() {
+            return Err(anyhow::anyhow!("No segments found in {} result", result.format));
+        }
+        
+        let merged = merge(&segments);
+        let merged_content = if result.format == "srt" { segments_to_srt(&merged) } else { segments_to_vtt(&merged) };
+        
+        let output_path = self.get_history_directory(&history.id)
+            .join("files")
+            .join(format!("result.{}.{}", variant, result.format));
+        tokio::fs::write(&output_path, &merged_content).await?;
+        
+        self.register_variant_result(&mut history, &result.format, &output_path, merged_content.len() as u64, variant).await?;
+        
+        Ok(SegmentMergeReport {
+            format: result.format.clone(),
+            output_path: output_path.to_string_lossy().to_string(),
+            original_count: segments.len(),
+            merged_count: merged.len(),
+        })
+    }
+    
+    /// 원본에서 파생한 결과 파일을 같은 형식/variant의 이전 결과 대신 등록합니다
+    async fn register_variant_result(
+        &self,
+        history: &mut TranscriptionHistory,
+        format: &str,
+        output_path: &Path,
+        file_size: u64,
+        variant: &str,
+    ) -> Result<()> {
+        // 원본을 찾는 곳은 첫 번째 결과를 쓰므로 파생 결과는 항상 뒤에 둠
+        history.results.retain(|r| !(r.format == format && r.variant.as_deref() == Some(variant)));
+        history.results.push(TranscriptionResult {
+            format: format.to_string(),
+            file_path: output_path.to_path_buf(),
+            file_size,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            partial: false,
+            language: None,
+            variant: Some(variant.to_string()),
+        });
+        
+        self.save_history_metadata(history).await?;
+        self.update_history_index(history).await
+    }
+    
+    /// 자막 큐가 오디오 전체를 얼마나 덮는지 분석합니다
+    pub async fn analyze_subtitle_coverage(&self, history_id: &str) -> Result<SubtitleCoverage> {
+        let history = self.load_history_metadata(history_id).await?;
+        let segments = self.load_history_segments(history_id).await?;
+        
+        // 원본 길이를 알 수 없으면 마지막 큐 종료 시간을 길이로 사용
+        let (duration, estimated) = match probe_media_duration(&self.media_tools, &history.original_file_path).await {
+            Some(duration) => (duration, false),
+            None => (segments.iter().map(|s| s.end).fold(0.0, f64::max), true),
+        };
+        
+        Ok(analyze_coverage(&segments, duration, estimated))
+    }
+    
+    /// 자막 세그먼트별 말하기 속도(분당 단어 수)를 계산합니다
+    pub async fn get_speaking_rate(&self, history_id: &str) -> Result<SpeakingRate> {
+        let segments = self.load_history_segments(history_id).await?;
+        if segments.is_empty() {
+            return Err(anyhow::anyhow!("No segments found for history {}", history_id));
+        }
+        
+        Ok(compute_speaking_rate(&segments))
+    }
+    
+    /// JSON 결과의 세그먼트 신뢰도가 임계값 이상인 세그먼트만 내보냅니다 (큐 번호는 다시 매김)
+    pub async fn export_high_confidence(
+        &self,
+        history_id: &str,
+        format: &str,
+        min_confidence: f64,
+        output_path: &str,
+    ) -> Result<ConfidenceExportReport> {
+        let history = self.load_history_metadata(history_id).await?;
+        let json_result = history.results.iter()
+            .find(|r| r.format == "json")
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: json (enable JSON output to use confidence filtering)"))?;
+        
+        let content = tokio::fs::read_to_string(&json_result.file_path).await?;
+        let scored = parse_whisper_json_segments(&content)?;
+        
+        if scored.iter().all(|s| s.confidence.is_none()) {
+            return Err(anyhow::anyhow!("JSON result has no token probabilities (use full JSON output)"));
+        }
+        
+        let total = scored.len();
+        let kept: Vec<SubtitleSegment> = scored.into_iter()
+            .filter(|s| s.confidence.is_some_and(|c| c >= min_confidence))
+            .map(|s| SubtitleSegment { start: s.start, end: s.end, text: s.text })
+            .collect();
+        
+        let output_content = match format {
+            "srt" => segments_to_srt(&kept),
+            "vtt" => segments_to_vtt(&kept),
+            "txt" => segments_to_plain_text(&kept),
+            _ => return Err(anyhow::anyhow!("Unsupported export format: {}", format)),
+        };
+        tokio::fs::write(output_path, output_content).await?;
+        
+        Ok(ConfidenceExportReport {
+            output_path: output_path.to_string(),
+            kept_count: kept.len(),
+            dropped_count: total - kept.len(),
+        })
+    }
+    
+    /// 자막 결과를 길이/큐 개수/크기 기준으로 여러 파일로 나눕니다
+    pub async fn split_result(
+        &self,
+        history_id: &str,
+        format: &str,
+        options: &SplitOptions,
+    ) -> Result<Vec<PathBuf>> {
+        let history = self.load_history_metadata(history_id).await?;
+        let result = history.results.iter()
+            .find(|r| r.format == format)
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: {}", format))?;
+        
+        let content = tokio::fs::read_to_string(&result.file_path).await?;
+        let segments = parse_subtitle_segments(&content);
+        if segments.is_empty() {
+            return Err(anyhow::anyhow!("No segments found in {} result", format));
+        }
+        
+        let parts = match format {
+            "srt" => split_segments(&segments, &options.strategy, 0, |i, s| format_srt_cue(i, s).len()),
+            "vtt" => split_segments(&segments, &options.strategy, VTT_HEADER.len(), |_, s| format_vtt_cue(s).len()),
+            _ => return Err(anyhow::anyhow!("Splitting is only supported for srt/vtt, got: {}", format)),
+        };
+        
+        let output_dir = PathBuf::from(&options.output_dir);
+        tokio::fs::create_dir_all(&output_dir).await?;
+        
+        let stem = std::path::Path::new(&history.original_file_name)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "result".to_string());
+        
+        let mut produced = Vec::new();
+        for (i, part) in parts.iter().enumerate() {
+            let part = if options.rebase_timings { rebase_segments(part) } else { part.clone() };
+            let part_content = if format == "srt" { segments_to_srt(&part) } else { segments_to_vtt(&part) };
+            
+            let part_path = output_dir.join(format!("{}.part{:02}.{}", stem, i + 1, format));
+            tokio::fs::write(&part_path, part_content).await?;
+            produced.push(part_path);
+        }
+        
+        Ok(produced)
+    }
+    
+    /// 이미 처리된 오디오 위치를 반환합니다 (기록이 없으면 기존 자막의 마지막 큐 종료 시간)
+    pub async fn get_processed_until(&self, history_id: &str) -> Result<f64> {
+        let history = self.load_history_metadata(history_id).await?;
+        if let Some(processed) = history.processed_until_seconds {
+            return Ok(processed);
+        }
+        
+        let segments = self.load_history_segments(history_id).await.unwrap_or_default();
+        Ok(segments.iter().map(|s| s.end).fold(0.0, f64::max))
+    }
+    
+    /// 새로 변환된 세그먼트를 기존 txt/srt/vtt 결과 뒤에 이어붙입니다
+    pub async fn append_transcription_segments(
+        &self,
+        history_id: &str,
+        new_segments: &[SubtitleSegment],
+        processed_until: f64,
+    ) -> Result<TranscriptionHistory> {
+        let mut history = self.load_history_metadata(history_id).await?;
+        
+        for result in history.results.iter_mut() {
+            let updated = match result.format.as_str() {
+                "txt" => {
+                    let mut content = tokio::fs::read_to_string(&result.file_path).await?;
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        content.push('\n');
+                    }
+                    content.push_str(&segments_to_plain_text(new_segments));
+                    content.push('\n');
+                    content
+                }
+                "srt" | "vtt" => {
+                    // 큐 번호를 이어서 매기기 위해 전체를 다시 작성
+                    let content = tokio::fs::read_to_string(&result.file_path).await?;
+                    let mut segments = parse_subtitle_segments(&content);
+                    segments.extend_from_slice(new_segments);
+                    if result.format == "srt" { segments_to_srt(&segments) } else { segments_to_vtt(&segments) }
+                }
+                other => {
+                    eprintln!("Skipping append for unsupported format: {}", other);
+                    continue;
+                }
+            };
+            
+            tokio::fs::write(&result.file_path, &updated).await?;
+            result.file_size = updated.len() as u64;
+        }
+        
+        history.processed_until_seconds = Some(processed_until);
+        
+        self.save_history_metadata(&history).await?;
+        self.update_history_index(&history).await?;
+        
+        Ok(history)
+    }
+    
+    /// 단어 단위 타이밍을 읽어옵니다 (JSON 전체 출력 우선, 없으면 --max-len 1로 만든 srt)
+    pub async fn load_word_timings(&self, history_id: &str) -> Result<Vec<WordTimedLine>> {
+        let history = self.load_history_metadata(history_id).await?;
+        
+        if let Some(json_result) = history.results.iter().find(|r| r.format == "json") {
+            let content = tokio::fs::read_to_string(&json_result.file_path).await?;
+            if let Some(lines) = parse_whisper_json_words(&content)? {
+                return Ok(lines);
+            }
+        }
+        
+        let word_level_srt = history.options_used.get("max-len").map(|v| v.trim() == "1").unwrap_or(false);
+        if word_level_srt {
+            if let Some(srt_result) = history.results.iter().find(|r| r.format == "srt") {
+                let content = tokio::fs::read_to_string(&srt_result.file_path).await?;
+                return Ok(group_words_into_lines(&parse_subtitle_segments(&content)));
+            }
+        }
+        
+        Err(anyhow::anyhow!(
+            "Word-level timing is not available for this entry (only segment-level timing); \
+             re-run with full JSON output (--output-json-full) or --max-len 1"
+        ))
+    }
+    
+    /// 단어별 `\k` 태그가 붙은 노래방 스타일 ASS 자막을 내보냅니다
+    pub async fn export_karaoke_ass(&self, history_id: &str, output_path: &str) -> Result<String> {
+        let lines = self.load_word_timings(history_id).await?;
+        if lines.is_empty() {
+            return Err(anyhow::anyhow!("No word timings found for history {}", history_id));
+        }
+        
+        let mut content = default_ass_header();
+        for line in &lines {
+            content.push_str(&karaoke_dialogue(line));
+            content.push('\n');
+        }
+        
+        tokio::fs::write(output_path, content).await?;
+        Ok(format!("Karaoke ASS exported to: {}", output_path))
+    }
+    
+    /// 쿼리 조건에 맞는 히스토리를 보고서 파일로 내보냅니다 (.csv면 CSV, 그 외에는 JSON)
+    ///
+    /// 목록 화면과 같은 필터를 쓰지만 페이징(limit/offset)은 무시하고 일치하는 항목을 모두 담습니다.
+    pub async fn export_history_report(&self, query: &HistoryQuery, output_path: &str) -> Result<usize> {
+        let mut items: Vec<TranscriptionHistory> = self.load_history_index().await?
+            .into_iter()
+            .filter(|item| self.matches_query(item, query))
+            .collect();
+        items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        
+        let is_csv = Path::new(output_path).extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let content = if is_csv {
+            history_report_csv(&items)
+        } else {
+            serde_json::to_string_pretty(&items)?
+        };
+        
+        tokio::fs::write(output_path, content).await?;
+        Ok(items.len())
+    }
+    
+    /// 같은 원본 파일(경로 + 크기)로 만든 히스토리 항목들을 묶어 반환합니다 (2개 이상인 그룹만)
+    ///
+    /// 크기가 기록되지 않은 예전 항목은 원본 파일의 현재 크기를 사용하고, 파일이 없으면 경로만으로 비교합니다.
+    pub async fn find_duplicate_histories(&self) -> Result<Vec<DuplicateHistoryGroup>> {
+        let mut index = self.load_history_index().await?;
+        index.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        
+        let mut groups: Vec<DuplicateHistoryGroup> = Vec::new();
+        for item in index {
+            let size = match item.input_file_size {
+                Some(size) => Some(size),
+                None => tokio::fs::metadata(&item.original_file_path).await.ok().map(|m| m.len()),
+            };
+            
+            match groups.iter_mut().find(|g| g.original_file_path == item.original_file_path && g.input_file_size == size) {
+                Some(group) => group.history_ids.push(item.id),
+                None => groups.push(DuplicateHistoryGroup {
+                    original_file_path: item.original_file_path,
+                    input_file_size: size,
+                    history_ids: vec![item.id],
+                }),
+            }
+        }
+        
+        groups.retain(|g| g.history_ids.len() > 1);
+        Ok(groups)
+    }
+    
+    /// 여러 히스토리 항목의 길이, 모델, 단어 수, 첫 몇 줄과 합계를 담은 요약 문서를 만듭니다
+    ///
+    /// 결과가 없는 항목(실패 등)도 목록에는 포함되고 통계만 비어 있습니다.
+    pub async fn generate_batch_summary(&self, history_ids: &[String], format: SummaryFormat) -> Result<String> {
+        let mut entries = Vec::new();
+        for history_id in history_ids {
+            let history = self.load_history_metadata(history_id).await?;
+            let text = self.read_result_text(&history).await.unwrap_or_default();
+            let audio_seconds = self.load_history_segments(history_id).await.ok()
+                .and_then(|segments| segments.last().map(|s| s.end));
+            let result_path = ["txt", "srt", "vtt"].iter()
+                .find_map(|format| history.results.iter().find(|r| r.format == *format))
+                .map(|r| r.file_path.clone());
+            
+            entries.push(BatchSummaryEntry {
+                audio_seconds,
+                word_count: text.split_whitespace().count(),
+                preview: text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .take(BATCH_SUMMARY_PREVIEW_LINES)
+                    .map(str::to_string)
+                    .collect(),
+                result_path,
+                history,
+            });
+        }
+        
+        Ok(match format {
+            SummaryFormat::Markdown => batch_summary_markdown(&entries),
+            SummaryFormat::Html => batch_summary_html(&entries),
+        })
+    }
+    
+    /// 변환 중 기록된 whisper 프로세스 출력(stdout/stderr)을 반환합니다
+    pub async fn get_history_logs(&self, history_id: &str) -> Result<String> {
+        let log_path = self.get_history_directory(history_id).join(HISTORY_LOG_FILE);
+        if !log_path.exists() {
+            return Err(anyhow::anyhow!("No logs recorded for history: {}", history_id));
+        }
+        
+        Ok(tokio::fs::read_to_string(&log_path).await?)
+    }
+    
+    /// 결과 파일의 마지막 N줄을 역순(마지막 줄 먼저)으로 반환합니다
+    pub async fn tail_result(
+        &self,
+        history_id: &str,
+        format: &str,
+        line_count: usize,
+    ) -> Result<TailResult> {
+        let result_path = self.get_result_file_path(history_id, format);
+        if !result_path.exists() {
+            return Err(anyhow::anyhow!("Result file not found: {}", format));
+        }
+        
+        let (mut lines, has_more) = read_last_lines(&result_path, line_count).await?;
+        lines.reverse();
+        
+        Ok(TailResult { lines, has_more })
+    }
+    
+    /// 존재하는 결과 파일들의 형식별 절대 경로를 반환합니다
+    pub async fn get_result_file_paths(
+        &self,
+        history_id: &str,
+    ) -> Result<std::collections::HashMap<String, PathBuf>> {
+        let history = self.load_history_metadata(history_id).await?;
+        
+        let paths = history.results.into_iter()
+            .filter(|result| result.file_path.exists())
+            .map(|result| {
+                let absolute_path = result.file_path.canonicalize()
+                    .unwrap_or(result.file_path);
+                (result.format, absolute_path)
+            })
+            .collect();
+        
+        Ok(paths)
+    }
+    
+    /// 데이터 디렉토리 이동 후 메타데이터와 인덱스에 저장된 절대 경로를 새 위치로 바꿉니다
+    pub async fn rewrite_stored_paths(&self, old_root: &Path, new_root: &Path) -> Result<()> {
+        let mut index = self.load_history_index().await?;
+        
+        for item in index.iter_mut() {
+            rewrite_history_paths(item, old_root, new_root);
+            
+            // 메타데이터 파일이 있는 경우 함께 갱신
+            if self.get_metadata_file_path(&item.id).exists() {
+                let mut history = self.load_history_metadata(&item.id).await?;
+                rewrite_history_paths(&mut history, old_root, new_root);
+                self.save_history_metadata(&history).await?;
+            }
+        }
+        
+        self.save_history_index(&index).await?;
+        Ok(())
+    }
+    
+    /// 완료된 모든 히스토리의 결과 파일에서 검색어를 찾아 주변 문맥과 함께 반환합니다
+    pub async fn search_all_transcripts(
+        &self,
+        term: &str,
+        options: &TranscriptSearchOptions,
+    ) -> Result<TranscriptSearchResponse> {
+        use futures_util::StreamExt;
+        
+        let matcher = build_search_matcher(term, options)?;
+        let cap = options.max_results.unwrap_or(DEFAULT_SEARCH_RESULT_CAP);
+        
+        let entries: Vec<TranscriptionHistory> = self.load_history_index().await?
+            .into_iter()
+            .filter(|item| matches!(item.status, TranscriptionStatus::Completed))
+            .collect();
+        
+        // 제한된 수의 파일만 동시에 읽되, 결과는 히스토리 순서대로 받음
+        let mut scans = futures_util::stream::iter(entries)
+            .map(|history| async move {
+                let source = ["srt", "vtt", "txt"].iter()
+                    .find_map(|format| history.results.iter().find(|r| r.format == *format))
+                    .cloned();
+                let lines = match &source {
+                    Some(result) => self.load_searchable_lines(result).await.ok(),
+                    None => None,
+                };
+                (history, source, lines)
+            })
+            .buffered(SEARCH_WORKERS);
+        
+        let mut hits = Vec::new();
+        let mut truncated = false;
+        
+        'scan: while let Some((history, source, lines)) = scans.next().await {
+            let (source, lines) = match (source, lines) {
+                (Some(source), Some(lines)) => (source, lines),
+                _ => continue,
+            };
+            
+            for (index, line) in lines.iter().enumerate() {
+                if !matcher.is_match(&line.text) {
+                    continue;
+                }
+                if hits.len() >= cap {
+                    truncated = true;
+                    break 'scan;
+                }
+                
+                let context_start = index.saturating_sub(options.context_lines);
+                let context_end = (index + 1 + options.context_lines).min(lines.len());
+                
+                hits.push(TranscriptSearchHit {
+                    history_id: history.id.clone(),
+                    original_file_name: history.original_file_name.clone(),
+                    format: source.format.clone(),
+                    line_index: index,
+                    line: line.text.clone(),
+                    context_before: lines[context_start..index].iter().map(|l| l.text.clone()).collect(),
+                    context_after: lines[index + 1..context_end].iter().map(|l| l.text.clone()).collect(),
+                    timestamp: line.start,
+                });
+            }
+        }
+        
+        Ok(TranscriptSearchResponse { hits, truncated })
+    }
+    
+    /// 결과 파일을 검색용 줄 목록으로 읽습니다 (수정 시간이 같으면 캐시 사용)
+    async fn load_searchable_lines(&self, result: &TranscriptionResult) -> Result<std::sync::Arc<Vec<SearchableLine>>> {
+        let modified = tokio::fs::metadata(&result.file_path).await?.modified()?;
+        
+        if let Some((cached_mtime, lines)) = self.search_cache.lock().unwrap().get(&result.file_path) {
+            if *cached_mtime == modified {
+                return Ok(lines.clone());
+            }
+        }
+        
+        let content = tokio::fs::read_to_string(&result.file_path).await?;
+        let lines: Vec<SearchableLine> = if result.format == "txt" {
+            content.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| SearchableLine { text: line.trim().to_string(), start: None })
+                .collect()
+        } else {
+            parse_subtitle_segments(&content).into_iter()
+                .map(|segment| SearchableLine { text: segment.text.replace('\n', " "), start: Some(segment.start) })
+                .collect()
+        };
+        
+        let lines = std::sync::Arc::new(lines);
+        self.search_cache.lock().unwrap().insert(result.file_path.clone(), (modified, lines.clone()));
+        Ok(lines)
+    }
+    
+    /// 특정 결과 파일의 경로를 반환합니다
+    pub fn get_result_file_path(&self, history_id: &str, format: &str) -> PathBuf {
+        self.get_history_directory(history_id)
+            .join("files")
+            .join(format!("result.{}", format))
+    }
+    
+    /// 히스토리 디렉토리 경로를 반환합니다 (public)
+    pub fn get_history_directory(&self, history_id: &str) -> PathBuf {
+        self.results_dir.join(history_id)
+    }
+    
+    /// 히스토리 메타데이터 파일 경로를 반환합니다
+    fn get_metadata_file_path(&self, history_id: &str) -> PathBuf {
+        self.get_history_directory(history_id).join("metadata.json")
+    }
+    
+    /// 히스토리 메타데이터를 저장합니다
+    async fn save_history_metadata(&self, history: &TranscriptionHistory) -> Result<()> {
+        let metadata_path = self.get_metadata_file_path(&history.id);
+        let json_content = serde_json::to_string_pretty(history)?;
+        tokio::fs::write(metadata_path, json_content).await?;
+        Ok(())
+    }
+    
+    /// 히스토리 메타데이터를 로드합니다
+    async fn load_history_metadata(&self, history_id: &str) -> Result<TranscriptionHistory> {
+        let metadata_path = self.get_metadata_file_path(history_id);
+        let json_content = tokio::fs::read_to_string(metadata_path).await?;
+        let history: TranscriptionHistory = serde_json::from_str(&json_content)?;
+        Ok(history)
+    }
+    
+    /// 히스토리 인덱스를 로드합니다
+    async fn load_history_index(&self) -> Result<Vec<TranscriptionHistory>> {
+        if !self.history_index_file.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let json_content = tokio::fs::read_to_string(&self.history_index_file).await?;
+        let index: Vec<TranscriptionHistory> = serde_json::from_str(&json_content)?;
+        Ok(index)
+    }
+    
+    /// 히스토리 인덱스를 저장합니다
+    async fn save_history_index(&self, index: &[TranscriptionHistory]) -> Result<()> {
+        let json_content = serde_json::to_string_pretty(index)?;
+        tokio::fs::write(&self.history_index_file, json_content).await?;
+        Ok(())
+    }
+    
+    /// 히스토리 인덱스를 업데이트합니다
+    async fn update_history_index(&self, history: &TranscriptionHistory) -> Result<()> {
+        let mut index = self.load_history_index().await?;
+        
+        // 기존 항목 업데이트 또는 새 항목 추가
+        if let Some(existing) = index.iter_mut().find(|item| item.id == history.id) {
+            *existing = history.clone();
+        } else {
+            index.push(history.clone());
+        }
+        
+        self.save_history_index(&index).await?;
+        Ok(())
+    }
+    
+    /// 쿼리 조건에 맞는지 확인합니다
+    fn matches_query(&self, item: &TranscriptionHistory, query: &HistoryQuery) -> bool {
+        // 검색어 필터
+        if let Some(search) = &query.search {
+            if !item.original_file_name.to_lowercase().contains(&search.to_lowercase()) {
+                return false;
+            }
+        }
+        
+        // 모델 필터
+        if let Some(model) = &query.model_filter {
+            if item.model_used != *model {
+                return false;
+            }
+        }
+        
+        // 형식 필터
+        if let Some(format) = &query.format_filter {
+            if !item.get_formats().contains(format) {
+                return false;
+            }
+        }
+        
+        // 태그 필터
+        if let Some(tag) = &query.tag_filter {
+            if !item.tags.contains(tag) {
+                return false;
+            }
+        }
+        
+        // 사용자 정의 필드 필터
+        if let Some((key, value)) = &query.custom_field_filter {
+            if item.custom_fields.get(key) != Some(value) {
+                return false;
+            }
+        }
+        
+        // 검토 필요 필터 (신뢰도를 모르는 항목은 판단할 수 없으므로 제외)
+        if let Some(threshold) = query.needs_review_filter {
+            if !item.avg_confidence.is_some_and(|confidence| confidence < threshold) {
+                return false;
+            }
+        }
+        
+        // 상태 필터 (Cancelled도 별도 상태로 구분)
+        if let Some(status) = &query.status_filter {
             if std::mem::discriminant(&item.status) != std::mem::discriminant(status) {
                 return false;
             }
@@ -376,4 +1828,746 @@ impl HistoryService {
         
         true
     }
-}
\ No newline at end of file
+}
+/// 기존 태그 순서를 유지하며 제거 → 추가를 적용합니다 (중복과 빈 태그는 제외)
+fn apply_tag_changes(tags: &[String], tags_to_add: &[String], tags_to_remove: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags.iter().chain(tags_to_add.iter()) {
+        let tag = tag.trim();
+        if tag.is_empty() || tags_to_remove.iter().any(|removed| removed.trim() == tag) {
+            continue;
+        }
+        if !result.iter().any(|existing| existing == tag) {
+            result.push(tag.to_string());
+        }
+    }
+    result
+}
+
+/// 두 히스토리 항목의 옵션 차이를 계산합니다 (a → b 기준)
+pub fn diff_options(a: &TranscriptionHistory, b: &TranscriptionHistory) -> HistoryOptionsDiff {
+    let mut added = std::collections::HashMap::new();
+    let mut removed = std::collections::HashMap::new();
+    let mut changed = std::collections::HashMap::new();
+    
+    for (key, value_b) in &b.options_used {
+        match a.options_used.get(key) {
+            None => {
+                added.insert(key.clone(), value_b.clone());
+            }
+            Some(value_a) if value_a != value_b => {
+                changed.insert(key.clone(), ValueChange {
+                    from: value_a.clone(),
+                    to: value_b.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+    
+    for (key, value_a) in &a.options_used {
+        if !b.options_used.contains_key(key) {
+            removed.insert(key.clone(), value_a.clone());
+        }
+    }
+    
+    let value_change = |from: String, to: String| {
+        if from != to { Some(ValueChange { from, to }) } else { None }
+    };
+    
+    HistoryOptionsDiff {
+        model_changed: value_change(a.model_used.clone(), b.model_used.clone()),
+        added,
+        removed,
+        changed,
+        language_changed: value_change(requested_language(a), requested_language(b)),
+        task_changed: value_change(requested_task(a), requested_task(b)),
+    }
+}
+
+/// 히스토리 보고서 CSV를 만듭니다 (항목당 한 줄)
+fn history_report_csv(items: &[TranscriptionHistory]) -> String {
+    let mut csv = String::from("id,file_name,model,status,created_at,completed_at,duration_seconds,formats,total_size,tags\n");
+    for item in items {
+        let row = [
+            item.id.clone(),
+            item.original_file_name.clone(),
+            item.model_used.clone(),
+            format!("{:?}", item.status),
+            item.created_at.clone(),
+            item.completed_at.clone().unwrap_or_default(),
+            item.duration_seconds.map(|d| format!("{:.1}", d)).unwrap_or_default(),
+            item.get_formats().join(";"),
+            item.total_file_size().to_string(),
+            item.tags.join(";"),
+        ];
+        let escaped: Vec<String> = row.iter().map(|field| escape_csv_field(field)).collect();
+        csv.push_str(&escaped.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 배치 요약의 합계 (파일 수, 오디오 길이, 단어 수, 처리 시간)
+fn batch_summary_totals(entries: &[BatchSummaryEntry]) -> (usize, f64, usize, f64) {
+    (
+        entries.len(),
+        entries.iter().filter_map(|e| e.audio_seconds).sum(),
+        entries.iter().map(|e| e.word_count).sum(),
+        entries.iter().filter_map(|e| e.history.duration_seconds).sum(),
+    )
+}
+
+fn batch_summary_markdown(entries: &[BatchSummaryEntry]) -> String {
+    let (files, audio_seconds, words, processing_seconds) = batch_summary_totals(entries);
+    let mut doc = String::from("# Batch Summary\n\n");
+    doc.push_str(&format!(
+        "- Files: {}\n- Total audio: {}\n- Total words: {}\n- Total processing time: {}\n",
+        files,
+        format_chapter_timestamp(audio_seconds),
+        words,
+        format_chapter_timestamp(processing_seconds),
+    ));
+    
+    for entry in entries {
+        let history = &entry.history;
+        doc.push_str(&format!("\n## {}\n\n", history.original_file_name));
+        doc.push_str(&format!("- ID: `{}`\n", history.id));
+        doc.push_str(&format!("- Status: {:?}\n", history.status));
+        doc.push_str(&format!("- Model: {}\n", history.model_used));
+        doc.push_str(&format!("- Duration: {}\n", entry.audio_seconds.map(format_chapter_timestamp).unwrap_or_else(|| "-".to_string())));
+        doc.push_str(&format!("- Words: {}\n", entry.word_count));
+        if let Some(path) = &entry.result_path {
+            doc.push_str(&format!("- Result: [{}](<{}>)\n", path.display(), path.display()));
+        }
+        if !entry.preview.is_empty() {
+            doc.push('\n');
+            for line in &entry.preview {
+                doc.push_str(&format!("> {}\n", line));
+            }
+        }
+    }
+    doc
+}
+
+fn batch_summary_html(entries: &[BatchSummaryEntry]) -> String {
+    let (files, audio_seconds, words, processing_seconds) = batch_summary_totals(entries);
+    let mut doc = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Batch Summary</title>\n</head>\n<body>\n<h1>Batch Summary</h1>\n");
+    doc.push_str(&format!(
+        "<ul>\n<li>Files: {}</li>\n<li>Total audio: {}</li>\n<li>Total words: {}</li>\n<li>Total processing time: {}</li>\n</ul>\n",
+        files,
+        format_chapter_timestamp(audio_seconds),
+        words,
+        format_chapter_timestamp(processing_seconds),
+    ));
+    
+    for entry in entries {
+        let history = &entry.history;
+        doc.push_str(&format!("<section id=\"{}\">\n<h2>{}</h2>\n<ul>\n", escape_html(&history.id), escape_html(&history.original_file_name)));
+        doc.push_str(&format!("<li>ID: <code>{}</code></li>\n", escape_html(&history.id)));
+        doc.push_str(&format!("<li>Status: {:?}</li>\n", history.status));
+        doc.push_str(&format!("<li>Model: {}</li>\n", escape_html(&history.model_used)));
+        doc.push_str(&format!("<li>Duration: {}</li>\n", entry.audio_seconds.map(format_chapter_timestamp).unwrap_or_else(|| "-".to_string())));
+        doc.push_str(&format!("<li>Words: {}</li>\n", entry.word_count));
+        if let Some(path) = &entry.result_path {
+            let path = escape_html(&path.to_string_lossy());
+            doc.push_str(&format!("<li>Result: <a href=\"file://{}\">{}</a></li>\n", path, path));
+        }
+        doc.push_str("</ul>\n");
+        if !entry.preview.is_empty() {
+            doc.push_str(&format!("<blockquote>{}</blockquote>\n", entry.preview.iter().map(|l| escape_html(l)).collect::<Vec<_>>().join("<br>")));
+        }
+        doc.push_str("</section>\n");
+    }
+    doc.push_str("</body>\n</html>\n");
+    doc
+}
+
+/// 세그먼트를 클릭하면 해당 위치로 이동하는 단일 HTML 자막 플레이어를 만듭니다 (외부 리소스 없음)
+fn html_player_document(title: &str, media_reference: &str, segments: &[SubtitleSegment]) -> String {
+    let media_tag = if is_video_file(media_reference) { "video" } else { "audio" };
+    let mut doc = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{style}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<{tag} id=\"player\" src=\"{src}\" controls preload=\"metadata\"></{tag}>\n<div id=\"transcript\">\n",
+        title = escape_html(title),
+        style = HTML_PLAYER_STYLE,
+        tag = media_tag,
+        src = escape_html(media_reference),
+    );
+    
+    for segment in segments {
+        doc.push_str(&format!(
+            "<p class=\"cue\" data-start=\"{:.3}\" data-end=\"{:.3}\"><a href=\"#\" class=\"time\">{}</a> {}</p>\n",
+            segment.start,
+            segment.end,
+            format_chapter_timestamp(segment.start),
+            escape_html(&segment.text).replace('\n', "<br>"),
+        ));
+    }
+    
+    doc.push_str("</div>\n<script>\n");
+    doc.push_str(HTML_PLAYER_SCRIPT);
+    doc.push_str("</script>\n</body>\n</html>\n");
+    doc
+}
+
+const HTML_PLAYER_STYLE: &str = "body { font-family: sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; }
+video, audio { width: 100%; position: sticky; top: 0; background: #fff; }
+.cue { margin: 0.4rem 0; padding: 0.2rem 0.4rem; border-radius: 4px; cursor: pointer; }
+.cue.active { background: #fff3b0; }
+.time { color: #2563eb; font-family: monospace; text-decoration: none; margin-right: 0.5rem; }
+";
+
+/// 큐 클릭 시 미디어를 이동하고, 재생 위치에 맞는 큐를 강조합니다
+const HTML_PLAYER_SCRIPT: &str = "const player = document.getElementById('player');
+const cues = Array.from(document.querySelectorAll('.cue'));
+cues.forEach((cue) => {
+  cue.addEventListener('click', (event) => {
+    event.preventDefault();
+    player.currentTime = parseFloat(cue.dataset.start);
+    player.play();
+  });
+});
+player.addEventListener('timeupdate', () => {
+  const time = player.currentTime;
+  cues.forEach((cue) => {
+    const active = time >= parseFloat(cue.dataset.start) && time < parseFloat(cue.dataset.end);
+    cue.classList.toggle('active', active);
+  });
+});
+";
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// 쉼표, 따옴표, 줄바꿈이 있는 필드를 따옴표로 감쌉니다
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// `old_root` 아래를 가리키는 경로만 `new_root` 기준으로 바꿉니다 (원본 파일 경로 등 외부 경로는 유지)
+fn rewrite_history_paths(history: &mut TranscriptionHistory, old_root: &Path, new_root: &Path) {
+    let rebase = |path: &mut PathBuf| {
+        if let Ok(relative) = path.strip_prefix(old_root) {
+            *path = new_root.join(relative);
+        }
+    };
+    
+    rebase(&mut history.original_file_path);
+    for result in history.results.iter_mut() {
+        rebase(&mut result.file_path);
+    }
+}
+
+/// 검색 옵션에 맞는 정규식을 만듭니다 (일반 검색어는 이스케이프)
+fn build_search_matcher(term: &str, options: &TranscriptSearchOptions) -> Result<regex::Regex> {
+    if term.is_empty() {
+        return Err(anyhow::anyhow!("Search term must not be empty"));
+    }
+    
+    let pattern = if options.regex { term.to_string() } else { regex::escape(term) };
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Invalid search pattern: {}", e))
+}
+
+/// 결과 텍스트의 공백을 한 칸으로 정리하고 PREVIEW_MAX_CHARS자로 자른 미리보기 (텍스트가 없으면 None)
+fn make_preview(text: &str) -> Option<String> {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    
+    if collapsed.chars().count() <= PREVIEW_MAX_CHARS {
+        return Some(collapsed);
+    }
+    
+    let truncated: String = collapsed.chars().take(PREVIEW_MAX_CHARS).collect();
+    Some(format!("{}…", truncated.trim_end()))
+}
+
+/// 앞에서 이미 나온 줄과 같은 줄의 비율 (대소문자와 앞뒤 문장부호는 무시)
+fn repetition_ratio(lines: &[String]) -> f64 {
+    if lines.is_empty() {
+        return 0.0;
+    }
+    
+    let mut seen = std::collections::HashSet::new();
+    let repeated = lines.iter()
+        .map(|line| line.trim_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace()).to_lowercase())
+        .filter(|normalized| !seen.insert(normalized.clone()))
+        .count();
+    
+    repeated as f64 / lines.len() as f64
+}
+
+/// 멀티바이트 문자 중간에서 줄을 자를 수 있는 옵션(max-len과 함께 켜지는 split-on-word)을 뺍니다
+fn strip_line_length_options(options: &mut std::collections::HashMap<String, String>) {
+    options.remove("max-len");
+    options.remove("split-on-word");
+}
+
+/// 요청된 언어를 반환합니다 (미지정 시 auto)
+fn requested_language(history: &TranscriptionHistory) -> String {
+    history.options_used.get("language")
+        .filter(|language| !language.is_empty())
+        .cloned()
+        .unwrap_or_else(|| "auto".to_string())
+}
+
+/// 요청된 작업 종류를 반환합니다 (translate 플래그 여부)
+fn requested_task(history: &TranscriptionHistory) -> String {
+    if history.options_used.contains_key("translate") {
+        "translate".to_string()
+    } else {
+        "transcribe".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn test_service() -> (HistoryService, PathBuf) {
+        let data_dir = std::env::temp_dir().join(format!("whisper-gui-test-{}", uuid::Uuid::new_v4()));
+        (HistoryService::with_data_dir(data_dir.clone()), data_dir)
+    }
+    
+    /// files/result.{format}에 내용을 쓰고 원본 결과로 등록한 히스토리를 만듭니다
+    async fn history_with_result(service: &HistoryService, format: &str, content: &str) -> TranscriptionHistory {
+        let history = service.create_history_entry(
+            "talk.wav".to_string(),
+            PathBuf::from("talk.wav"),
+            "base".to_string(),
+            Default::default(),
+        ).await.unwrap();
+        let result_path = service.get_history_directory(&history.id).join("files").join(format!("result.{}", format));
+        tokio::fs::write(&result_path, content).await.unwrap();
+        service.register_existing_results(&history.id, vec![(result_path, format.to_string())]).await.unwrap()
+    }
+    
+    #[tokio::test]
+    async fn deduplicate_segments_writes_sibling_and_keeps_original() {
+        let (service, data_dir) = test_service();
+        let original = "1\n00:00:00,000 --> 00:00:01,000\nhello there\n\n\
+                        2\n00:00:01,000 --> 00:00:02,000\nhello there\n\n\
+                        3\n00:00:02,000 --> 00:00:03,000\ngoodbye\n";
+        let history = history_with_result(&service, "srt", original).await;
+        
+        let report = service.deduplicate_segments(&history.id, "srt", 0.9).await.unwrap();
+        assert_eq!((report.original_count, report.remaining_count, report.merged_count), (3, 2, 1));
+        assert!(report.output_path.ends_with("result.dedup.srt"));
+        
+        let original_path = &history.results[0].file_path;
+        assert_eq!(tokio::fs::read_to_string(original_path).await.unwrap(), original);
+        assert_eq!(parse_subtitle_segments(&tokio::fs::read_to_string(&report.output_path).await.unwrap()).len(), 2);
+        
+        // 다시 실행해도 파생 결과는 하나만 남고 원본이 먼저 옴
+        service.deduplicate_segments(&history.id, "srt", 0.9).await.unwrap();
+        let history = service.get_history(&history.id).await.unwrap();
+        assert_eq!(history.results.len(), 2);
+        assert!(history.results[0].variant.is_none());
+        assert_eq!(history.results[1].variant.as_deref(), Some("dedup"));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn merged_outputs_are_registered_as_variant_results() {
+        let (service, data_dir) = test_service();
+        let original = "1\n00:00:00,000 --> 00:00:00,300\nshort\n\n\
+                        2\n00:00:00,300 --> 00:00:00,600\ncues\n\n\
+                        3\n00:00:00,600 --> 00:00:02,000\nhere\n";
+        let history = history_with_result(&service, "srt", original).await;
+        
+        let report = service.merge_segments(&history.id, 1.0, 0.5).await.unwrap();
+        assert!(report.output_path.ends_with("result.merged.srt"));
+        let report = service.merge_short_segments(&history.id, "srt", 1000, 42).await.unwrap();
+        assert!(report.output_path.ends_with("result.short-merged.srt"));
+        
+        // 다시 실행해도 variant별 결과는 하나만 남고 원본이 먼저 옴
+        service.merge_segments(&history.id, 1.0, 0.5).await.unwrap();
+        let history = service.get_history(&history.id).await.unwrap();
+        let variants: Vec<Option<&str>> = history.results.iter().map(|r| r.variant.as_deref()).collect();
+        assert_eq!(variants, vec![None, Some("short-merged"), Some("merged")]);
+        let merged = &history.results[2];
+        assert_eq!(merged.format, "srt");
+        assert_eq!(merged.file_size, tokio::fs::metadata(&merged.file_path).await.unwrap().len());
+        assert_eq!(tokio::fs::read_to_string(&history.results[0].file_path).await.unwrap(), original);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    fn history_with_options(model: &str, options: &[(&str, &str)]) -> TranscriptionHistory {
+        let options = options.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        TranscriptionHistory::new("talk.wav".to_string(), PathBuf::from("talk.wav"), model.to_string(), options)
+    }
+    
+    #[test]
+    fn diff_options_reports_added_removed_and_changed() {
+        let a = history_with_options("base", &[("language", "en"), ("threads", "4"), ("beam_size", "5")]);
+        let b = history_with_options("small", &[("language", "ko"), ("threads", "8"), ("translate", "true")]);
+        
+        let diff = diff_options(&a, &b);
+        let model = diff.model_changed.unwrap();
+        assert_eq!((model.from.as_str(), model.to.as_str()), ("base", "small"));
+        assert_eq!(diff.added.get("translate").map(String::as_str), Some("true"));
+        assert_eq!(diff.removed.get("beam_size").map(String::as_str), Some("5"));
+        assert_eq!(diff.changed["threads"].to, "8");
+        let language = diff.language_changed.unwrap();
+        assert_eq!((language.from.as_str(), language.to.as_str()), ("en", "ko"));
+        let task = diff.task_changed.unwrap();
+        assert_eq!((task.from.as_str(), task.to.as_str()), ("transcribe", "translate"));
+    }
+    
+    #[test]
+    fn diff_options_treats_empty_language_as_auto() {
+        let a = history_with_options("base", &[("language", "")]);
+        let b = history_with_options("base", &[]);
+        
+        let diff = diff_options(&a, &b);
+        assert!(diff.model_changed.is_none());
+        assert!(diff.language_changed.is_none());
+        assert!(diff.task_changed.is_none());
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.added.is_empty() && diff.changed.is_empty());
+    }
+    
+    fn empty_query() -> HistoryQuery {
+        HistoryQuery {
+            limit: None,
+            offset: None,
+            search: None,
+            model_filter: None,
+            format_filter: None,
+            tag_filter: None,
+            status_filter: None,
+            date_from: None,
+            date_to: None,
+            custom_field_filter: None,
+            needs_review_filter: None,
+        }
+    }
+    
+    fn status_query(status: TranscriptionStatus) -> HistoryQuery {
+        HistoryQuery { status_filter: Some(status), ..empty_query() }
+    }
+    
+    #[tokio::test]
+    async fn status_filter_counts_cancelled_separately_from_failed() {
+        let (service, data_dir) = test_service();
+        let cancelled = history_with_result(&service, "srt", "").await;
+        let failed = history_with_result(&service, "srt", "").await;
+        service.mark_history_cancelled(&cancelled.id, Vec::new()).await.unwrap();
+        service.mark_history_failed(&failed.id, "boom".to_string()).await.unwrap();
+        
+        let response = service.list_history(status_query(TranscriptionStatus::Cancelled)).await.unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.items[0].id, cancelled.id);
+        
+        let response = service.list_history(status_query(TranscriptionStatus::Failed)).await.unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.items[0].id, failed.id);
+        
+        assert_eq!(service.list_history(status_query(TranscriptionStatus::Completed)).await.unwrap().total_count, 0);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn cancelled_status_survives_serialization() {
+        let (service, data_dir) = test_service();
+        let history = history_with_result(&service, "srt", "").await;
+        service.mark_history_cancelled(&history.id, Vec::new()).await.unwrap();
+        
+        // 디스크의 메타데이터를 다시 읽어도 취소 상태가 유지되고 실패 메시지는 없음
+        let reloaded = service.get_history(&history.id).await.unwrap();
+        assert!(matches!(reloaded.status, TranscriptionStatus::Cancelled));
+        assert!(reloaded.error_message.is_none());
+        assert!(reloaded.completed_at.is_some());
+        
+        let json = serde_json::to_string(&TranscriptionStatus::Cancelled).unwrap();
+        assert_eq!(json, "\"Cancelled\"");
+        assert!(matches!(serde_json::from_str(&json).unwrap(), TranscriptionStatus::Cancelled));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn custom_fields_are_trimmed_filtered_and_removed() {
+        let (service, data_dir) = test_service();
+        let tagged = history_with_result(&service, "srt", "").await;
+        let other = history_with_result(&service, "srt", "").await;
+        
+        assert!(service.set_history_custom_field(&tagged.id, "   ".to_string(), "x".to_string()).await.is_err());
+        let updated = service.set_history_custom_field(&tagged.id, " client ".to_string(), "acme".to_string()).await.unwrap();
+        assert_eq!(updated.custom_fields.get("client").map(String::as_str), Some("acme"));
+        service.set_history_custom_field(&other.id, "client".to_string(), "globex".to_string()).await.unwrap();
+        
+        let query = HistoryQuery {
+            custom_field_filter: Some(("client".to_string(), "acme".to_string())),
+            ..empty_query()
+        };
+        let response = service.list_history(query.clone()).await.unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.items[0].id, tagged.id);
+        
+        let removed = service.remove_history_custom_field(&tagged.id, "client").await.unwrap();
+        assert!(removed.custom_fields.is_empty());
+        assert_eq!(service.list_history(query).await.unwrap().total_count, 0);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn rerun_config_reuses_options_of_finished_entries_only() {
+        let (service, data_dir) = test_service();
+        let options = [("language".to_string(), "ko".to_string())].into_iter().collect();
+        let history = service.create_history_entry(
+            "talk.wav".to_string(),
+            PathBuf::from("talk.wav"),
+            "small".to_string(),
+            options,
+        ).await.unwrap();
+        assert!(service.get_rerun_config(&history.id).await.is_err());
+        service.update_history_status(&history.id, TranscriptionStatus::Queued).await.unwrap();
+        assert!(service.get_rerun_config(&history.id).await.is_err());
+        
+        service.record_output_preferences(&history.id, strings(&["vtt", "fcpxml"]), true).await.unwrap();
+        service.mark_history_failed(&history.id, "boom".to_string()).await.unwrap();
+        let config = service.get_rerun_config(&history.id).await.unwrap();
+        assert_eq!((config.model.as_str(), config.input_file.as_str()), ("small", "talk.wav"));
+        assert_eq!(config.options.get("language").map(String::as_str), Some("ko"));
+        assert!(!config.adaptive_threads);
+        assert_eq!(config.export_formats, strings(&["vtt", "fcpxml"]));
+        assert!(config.preserve_source_mtime);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn search_matcher_escapes_plain_terms() {
+        let plain = TranscriptSearchOptions::default();
+        assert!(build_search_matcher("", &plain).is_err());
+        assert!(build_search_matcher("c++ (v2)", &plain).unwrap().is_match("learning C++ (v2) today"));
+        
+        let regex = TranscriptSearchOptions { regex: true, case_sensitive: true, ..Default::default() };
+        let matcher = build_search_matcher(r"^Hello\b", &regex).unwrap();
+        assert!(matcher.is_match("Hello world"));
+        assert!(!matcher.is_match("hello world"));
+        assert!(build_search_matcher("(unclosed", &regex).is_err());
+    }
+    
+    #[tokio::test]
+    async fn search_all_transcripts_returns_context_and_timestamp() {
+        let (service, data_dir) = test_service();
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nalpha\n\n\
+                   2\n00:00:03,000 --> 00:00:04,000\nbeta needle\n\n\
+                   3\n00:00:05,000 --> 00:00:06,000\ngamma\n";
+        let history = history_with_result(&service, "srt", srt).await;
+        
+        let options = TranscriptSearchOptions { context_lines: 1, ..Default::default() };
+        let response = service.search_all_transcripts("NEEDLE", &options).await.unwrap();
+        assert!(!response.truncated);
+        assert_eq!(response.hits.len(), 1);
+        let hit = &response.hits[0];
+        assert_eq!((hit.history_id.as_str(), hit.line_index, hit.timestamp), (history.id.as_str(), 1, Some(3.0)));
+        assert_eq!((hit.context_before.clone(), hit.context_after.clone()), (vec!["alpha".to_string()], vec!["gamma".to_string()]));
+        
+        let capped = TranscriptSearchOptions { regex: true, max_results: Some(1), ..Default::default() };
+        assert!(service.search_all_transcripts("a", &capped).await.unwrap().truncated);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    fn summary_entry(file_name: &str, audio_seconds: Option<f64>, word_count: usize) -> BatchSummaryEntry {
+        let mut history = history_with_options("base", &[]);
+        history.original_file_name = file_name.to_string();
+        history.duration_seconds = Some(30.0);
+        BatchSummaryEntry {
+            history,
+            audio_seconds,
+            word_count,
+            preview: vec!["first <line>".to_string()],
+            result_path: None,
+        }
+    }
+    
+    #[test]
+    fn batch_summary_totals_skip_unknown_durations() {
+        let entries = vec![summary_entry("a.wav", Some(90.0), 10), summary_entry("b.wav", None, 5)];
+        assert_eq!(batch_summary_totals(&entries), (2, 90.0, 15, 60.0));
+        
+        let markdown = batch_summary_markdown(&entries);
+        assert!(markdown.contains("- Files: 2\n- Total audio: 1:30\n- Total words: 15\n- Total processing time: 1:00\n"));
+        assert!(markdown.contains("\n## b.wav\n"));
+        assert!(markdown.contains("- Duration: -\n"));
+        assert!(markdown.contains("> first <line>\n"));
+    }
+    
+    #[test]
+    fn batch_summary_html_escapes_user_text() {
+        let html = batch_summary_html(&[summary_entry("Q&A \"live\".wav", Some(5.0), 1)]);
+        assert!(html.contains("<h2>Q&amp;A &quot;live&quot;.wav</h2>"));
+        assert!(html.contains("<blockquote>first &lt;line&gt;</blockquote>"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+    
+    #[test]
+    fn repetition_ratio_ignores_case_and_edge_punctuation() {
+        let lines: Vec<String> = ["Thank you.", "thank you", "THANK YOU!", "Goodbye"].iter().map(|l| l.to_string()).collect();
+        assert_eq!(repetition_ratio(&lines), 0.5);
+        assert_eq!(repetition_ratio(&[]), 0.0);
+    }
+    
+    #[tokio::test]
+    async fn result_quality_flags_short_subtitles() {
+        let (service, data_dir) = test_service();
+        let srt = "1\n00:00:00,000 --> 00:01:00,000\nhi\n\n2\n00:01:00,000 --> 00:02:00,000\nhi\n";
+        let history = history_with_result(&service, "srt", srt).await;
+        
+        let quality = service.check_result_quality(&history.id, "srt").await.unwrap();
+        assert!(quality.suspiciously_short);
+        assert!(!quality.is_empty && !quality.is_whitespace_only);
+        assert_eq!((quality.line_count, quality.repetition_ratio, quality.audio_duration_seconds), (2, 0.5, Some(120.0)));
+        assert!(service.check_result_quality(&history.id, "vtt").await.is_err());
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn needs_review_filter_uses_recorded_confidence() {
+        let (service, data_dir) = test_service();
+        let json = r#"{"transcription": [{"offsets": {"from": 0, "to": 1000}, "text": "hi", "tokens": [{"text": " hi", "p": 0.4}]}]}"#;
+        let uncertain = history_with_result(&service, "json", json).await;
+        let unknown = history_with_result(&service, "srt", "").await;
+        
+        let confidence = service.record_average_confidence(&uncertain.id).await.unwrap().unwrap();
+        assert!((confidence - 0.4).abs() < 1e-6);
+        assert_eq!(service.record_average_confidence(&unknown.id).await.unwrap(), None);
+        
+        // 신뢰도를 모르는 항목은 검토 대상에 포함하지 않음
+        let query = |threshold| HistoryQuery { needs_review_filter: Some(threshold), ..empty_query() };
+        let response = service.list_history(query(0.5)).await.unwrap();
+        assert_eq!(response.total_count, 1);
+        assert_eq!(response.items[0].id, uncertain.id);
+        assert_eq!(service.list_history(query(0.3)).await.unwrap().total_count, 0);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+    
+    #[test]
+    fn tag_changes_keep_order_and_drop_duplicates() {
+        let tags = strings(&["work", "draft", "ko"]);
+        assert_eq!(
+            apply_tag_changes(&tags, &strings(&[" final ", "work", ""]), &strings(&["draft "])),
+            strings(&["work", "ko", "final"])
+        );
+        // 추가와 제거에 같은 태그가 있으면 제거가 우선
+        assert_eq!(apply_tag_changes(&[], &strings(&["x"]), &strings(&["x"])), Vec::<String>::new());
+    }
+    
+    #[tokio::test]
+    async fn bulk_tag_counts_only_changed_entries() {
+        let (service, data_dir) = test_service();
+        let failed = history_with_result(&service, "srt", "").await;
+        history_with_result(&service, "srt", "").await;
+        service.mark_history_failed(&failed.id, "boom".to_string()).await.unwrap();
+        
+        let query = status_query(TranscriptionStatus::Failed);
+        assert_eq!(service.bulk_tag(&query, &strings(&["retry"]), &[]).await.unwrap(), 1);
+        assert_eq!(service.get_history(&failed.id).await.unwrap().tags, strings(&["retry"]));
+        // 이미 태그가 있는 항목은 다시 저장하지 않음
+        assert_eq!(service.bulk_tag(&query, &strings(&["retry"]), &[]).await.unwrap(), 0);
+        
+        let tagged = HistoryQuery { tag_filter: Some("retry".to_string()), ..empty_query() };
+        assert_eq!(service.list_history(tagged).await.unwrap().total_count, 1);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn html_player_picks_media_tag_and_escapes_cues() {
+        let segments = vec![SubtitleSegment { start: 65.5, end: 67.0, text: "<b>hi</b>\nthere".to_string() }];
+        
+        let doc = html_player_document("Talk & Q&A", "clip \"1\".mp4", &segments);
+        assert!(doc.contains("<title>Talk &amp; Q&amp;A</title>"));
+        assert!(doc.contains("<video id=\"player\" src=\"clip &quot;1&quot;.mp4\" controls"));
+        assert!(doc.contains("<p class=\"cue\" data-start=\"65.500\" data-end=\"67.000\"><a href=\"#\" class=\"time\">1:05</a> &lt;b&gt;hi&lt;/b&gt;<br>there</p>"));
+        
+        assert!(html_player_document("Talk", "talk.mp3", &segments).contains("<audio id=\"player\""));
+    }
+    
+    #[tokio::test]
+    async fn html_player_requires_timestamped_segments() {
+        let (service, data_dir) = test_service();
+        let history = history_with_result(&service, "txt", "no timing").await;
+        assert!(service.export_html_player(&history.id, "talk.wav").await.is_err());
+        
+        let history = history_with_result(&service, "srt", "1\n00:00:00,000 --> 00:00:01,000\nhi\n").await;
+        let path = service.export_html_player(&history.id, "talk.wav").await.unwrap();
+        assert!(path.ends_with(HTML_PLAYER_FILE));
+        assert!(tokio::fs::read_to_string(&path).await.unwrap().contains("class=\"cue\""));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn preview_collapses_whitespace_and_truncates() {
+        assert_eq!(make_preview("  hello \n\n  world\t"), Some("hello world".to_string()));
+        assert_eq!(make_preview(" \n "), None);
+        
+        let long = format!("{} tail", "가".repeat(PREVIEW_MAX_CHARS - 1));
+        let preview = make_preview(&long).unwrap();
+        // 잘린 위치의 공백은 지우고 말줄임표를 붙임
+        assert_eq!(preview, format!("{}…", "가".repeat(PREVIEW_MAX_CHARS - 1)));
+    }
+    
+    #[tokio::test]
+    async fn regenerate_previews_fills_missing_previews_from_subtitles() {
+        let (service, data_dir) = test_service();
+        let history = history_with_result(&service, "srt", "1\n00:00:00,000 --> 00:00:01,000\nhello there\n").await;
+        
+        assert_eq!(service.regenerate_previews(false).await.unwrap(), 1);
+        assert_eq!(service.get_history(&history.id).await.unwrap().preview.as_deref(), Some("hello there"));
+        let response = service.list_history(empty_query()).await.unwrap();
+        assert_eq!(response.items[0].preview.as_deref(), Some("hello there"));
+        // 이미 미리보기가 있으면 force 없이는 다시 만들지 않음
+        assert_eq!(service.regenerate_previews(false).await.unwrap(), 0);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn encoding_check_offers_rerun_without_line_length_limit() {
+        let (service, data_dir) = test_service();
+        let history = history_with_result(&service, "txt", "안녕\u{FFFD}하세요").await;
+        service.update_history_status(&history.id, TranscriptionStatus::Completed).await.unwrap();
+        
+        let check = service.check_encoding_issues(&history.id).await.unwrap();
+        assert!(check.possible_issue);
+        assert_eq!((check.replacement_chars, check.affected_formats), (1, strings(&["txt"])));
+        assert!(check.rerun_config.is_some());
+        let warnings = service.get_history(&history.id).await.unwrap().warnings;
+        assert!(warnings.iter().any(|warning| warning.code == "possible_encoding_issue"));
+        
+        let mut options = [("max-len", "20"), ("split-on-word", ""), ("language", "ko")].iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        strip_line_length_options(&mut options);
+        assert_eq!(options.keys().collect::<Vec<_>>(), vec!["language"]);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+}
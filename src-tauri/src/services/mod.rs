@@ -1,6 +1,8 @@
 mod whisper_service;
 mod whisper_installer;
 mod history_service;
+mod settings_service;
 
 pub use whisper_service::*;
-pub use history_service::*;
\ No newline at end of file
+pub use history_service::*;
+pub use settings_service::*;
\ No newline at end of file
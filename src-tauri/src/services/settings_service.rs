@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use crate::models::{AppSettings, BuildBackend};
+use crate::services::history_service::HistoryService;
+use crate::utils::{copy_dir_recursive, is_executable};
+use crate::utils::export_formats::whisper_output_formats;
+
+/// 앱 설정 관리 서비스
+///
+/// 설정 파일은 데이터 디렉토리가 옮겨져도 찾을 수 있도록
+/// 데이터 디렉토리 밖(OS 설정 디렉토리)에 저장됩니다.
+#[derive(Clone)]
+pub struct SettingsService {
+    pub settings_file: PathBuf,
+}
+
+impl SettingsService {
+    pub fn new() -> Self {
+        let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self {
+            settings_file: config_dir.join("whisper-gui").join("settings.json"),
+        }
+    }
+    
+    /// 설정을 로드합니다 (파일이 없거나 손상된 경우 기본값)
+    pub fn load(&self) -> AppSettings {
+        std::fs::read_to_string(&self.settings_file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+    
+    /// 설정을 저장합니다
+    pub async fn save(&self, settings: &AppSettings) -> Result<()> {
+        if let Some(parent) = self.settings_file.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let json_content = serde_json::to_string_pretty(settings)?;
+        tokio::fs::write(&self.settings_file, json_content).await?;
+        Ok(())
+    }
+    
+    /// 자동 감지 언어별 옵션 규칙을 교체합니다 (언어 코드는 소문자로 정규화)
+    pub async fn set_language_rules(
+        &self,
+        rules: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+    ) -> Result<AppSettings> {
+        let mut settings = self.load();
+        settings.language_rules = rules.into_iter()
+            .map(|(language, options)| (language.trim().to_lowercase(), options))
+            .filter(|(language, _)| !language.is_empty())
+            .collect();
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 일괄 내보내기 파일 이름 템플릿을 설정합니다
+    pub async fn set_export_filename_template(&self, template: &str) -> Result<AppSettings> {
+        let template = template.trim();
+        if template.is_empty() {
+            return Err(anyhow::anyhow!("Filename template must not be empty"));
+        }
+        
+        let mut settings = self.load();
+        settings.export_filename_template = template.to_string();
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 출력 형식을 지정하지 않은 변환에 사용할 기본 형식을 설정합니다 (whisper.cpp가 직접 만드는 형식만 허용)
+    pub async fn set_default_output_formats(&self, formats: Vec<String>) -> Result<AppSettings> {
+        let mut normalized: Vec<String> = Vec::new();
+        for format in formats {
+            let format = format.trim().to_lowercase();
+            if !whisper_output_formats().any(|(_, id)| id == format) {
+                return Err(anyhow::anyhow!("Unsupported default output format: {}", format));
+            }
+            if !normalized.contains(&format) {
+                normalized.push(format);
+            }
+        }
+        
+        let mut settings = self.load();
+        settings.default_output_formats = normalized;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 변환 완료 후 실행할 셸 명령을 설정합니다 (None이나 빈 문자열이면 해제)
+    ///
+    /// 설정된 명령은 사용자 권한으로 그대로 실행되므로 신뢰할 수 있는 명령만 등록해야 합니다.
+    /// `{history_id}`, `{result_path}`, `{file_name}`은 따옴표로 감싼 환경 변수 참조로 바뀌므로 직접 따옴표로 감싸지 않습니다.
+    pub async fn set_post_hook_command(&self, command: Option<String>) -> Result<AppSettings> {
+        let mut settings = self.load();
+        settings.post_hook_command = command
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 결과 번역에 사용할 셸 명령을 설정합니다 (None이나 빈 문자열이면 해제)
+    ///
+    /// 명령은 stdin으로 번역할 라인들을 받아 같은 수의 번역 라인을 stdout으로 출력해야 합니다.
+    /// {source_language}, {target_language} 자리표시자를 사용할 수 있습니다.
+    pub async fn set_translator_command(&self, command: Option<String>) -> Result<AppSettings> {
+        let mut settings = self.load();
+        settings.translator_command = command
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty());
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 대기열에서 동시에 실행할 변환 수를 설정합니다
+    pub async fn set_max_concurrent(&self, max_concurrent: usize) -> Result<AppSettings> {
+        if max_concurrent == 0 {
+            return Err(anyhow::anyhow!("Max concurrent transcriptions must be at least 1"));
+        }
+        
+        let mut settings = self.load();
+        settings.max_concurrent = max_concurrent;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 다음 빌드부터 사용할 가속 백엔드를 저장합니다
+    pub async fn set_build_backend(&self, backend: Option<BuildBackend>) -> Result<AppSettings> {
+        let mut settings = self.load();
+        settings.build_backend = backend;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    pub async fn set_verify_after_download(&self, enabled: bool) -> Result<AppSettings> {
+        let mut settings = self.load();
+        settings.verify_after_download = enabled;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// ffmpeg/ffprobe 경로를 설정합니다 (None이면 PATH에서 찾도록 해제)
+    pub async fn set_media_tool_paths(
+        &self,
+        ffmpeg_path: Option<PathBuf>,
+        ffprobe_path: Option<PathBuf>,
+    ) -> Result<AppSettings> {
+        for path in ffmpeg_path.iter().chain(ffprobe_path.iter()) {
+            if !is_executable(path) {
+                return Err(anyhow::anyhow!("Not an executable file: {}", path.display()));
+            }
+        }
+        
+        let mut settings = self.load();
+        settings.ffmpeg_path = ffmpeg_path;
+        settings.ffprobe_path = ffprobe_path;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 직접 빌드한 whisper-cli 경로를 설정합니다 (None이면 기본 탐색으로 되돌림)
+    pub async fn set_custom_binary_path(&self, path: Option<PathBuf>) -> Result<AppSettings> {
+        if let Some(path) = &path {
+            if !is_executable(path) {
+                return Err(anyhow::anyhow!("Not an executable file: {}", path.display()));
+            }
+        }
+        
+        let mut settings = self.load();
+        settings.custom_binary_path = path;
+        self.save(&settings).await?;
+        Ok(settings)
+    }
+    
+    /// 데이터 디렉토리 전체를 새 위치로 옮깁니다
+    ///
+    /// 새 위치로 복사 → 저장된 절대 경로 재작성 → 설정 저장 순서로 진행하고,
+    /// 중간에 실패하면 새 위치의 복사본만 정리하므로 기존 위치는 그대로 남습니다.
+    /// 기존 위치는 모든 단계가 성공한 뒤 호출자가 `remove_old_data_directory`로 삭제합니다.
+    pub async fn migrate_data_directory(&self, new_path: &Path) -> Result<AppSettings> {
+        let mut settings = self.load();
+        let old_path = settings.data_dir.clone();
+        
+        validate_migration_target(&old_path, new_path)?;
+        
+        match self.copy_and_rewrite(&old_path, new_path).await {
+            Ok(()) => {
+                settings.data_dir = new_path.to_path_buf();
+                if let Err(e) = self.save(&settings).await {
+                    let _ = tokio::fs::remove_dir_all(new_path).await;
+                    return Err(e);
+                }
+                Ok(settings)
+            }
+            Err(e) => {
+                // 새 위치의 부분 복사본 정리 (기존 위치는 건드리지 않음)
+                let _ = tokio::fs::remove_dir_all(new_path).await;
+                Err(e)
+            }
+        }
+    }
+    
+    async fn copy_and_rewrite(&self, old_path: &Path, new_path: &Path) -> Result<()> {
+        if old_path.exists() {
+            let (from, to) = (old_path.to_path_buf(), new_path.to_path_buf());
+            tokio::task::spawn_blocking(move || copy_dir_recursive(&from, &to)).await??;
+        } else {
+            tokio::fs::create_dir_all(new_path).await?;
+        }
+        
+        HistoryService::with_data_dir(new_path.to_path_buf())
+            .rewrite_stored_paths(old_path, new_path)
+            .await
+    }
+}
+
+/// 마이그레이션 완료 후 기존 데이터 디렉토리를 삭제합니다
+pub async fn remove_old_data_directory(old_path: &Path) -> Result<()> {
+    if old_path.exists() {
+        tokio::fs::remove_dir_all(old_path).await?;
+    }
+    Ok(())
+}
+
+fn validate_migration_target(old_path: &Path, new_path: &Path) -> Result<()> {
+    if !new_path.is_absolute() {
+        return Err(anyhow::anyhow!("Target path must be absolute: {:?}", new_path));
+    }
+    
+    if new_path == old_path {
+        return Err(anyhow::anyhow!("Target path is the current data directory"));
+    }
+    
+    // 자기 자신 내부/상위로 복사하면 무한 재귀 또는 덮어쓰기가 발생
+    if new_path.starts_with(old_path) || old_path.starts_with(new_path) {
+        return Err(anyhow::anyhow!("Target path must not be inside (or contain) the current data directory"));
+    }
+    
+    if new_path.exists() {
+        let is_empty = std::fs::read_dir(new_path)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(false);
+        if !is_empty {
+            return Err(anyhow::anyhow!("Target directory already exists and is not empty: {:?}", new_path));
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn test_settings() -> (SettingsService, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("whisper-gui-test-{}", uuid::Uuid::new_v4()));
+        (SettingsService { settings_file: dir.join("settings.json") }, dir)
+    }
+    
+    #[tokio::test]
+    async fn default_output_formats_are_normalized_and_validated() {
+        let (service, dir) = test_settings();
+        
+        let settings = service.set_default_output_formats(vec![" SRT ".to_string(), "txt".to_string(), "srt".to_string()]).await.unwrap();
+        assert_eq!(settings.default_output_formats, vec!["srt", "txt"]);
+        assert_eq!(service.load().default_output_formats, vec!["srt", "txt"]);
+        
+        // whisper.cpp가 직접 만들지 않는 형식은 거부하고 기존 설정을 유지
+        assert!(service.set_default_output_formats(vec!["docx".to_string()]).await.is_err());
+        assert_eq!(service.load().default_output_formats, vec!["srt", "txt"]);
+        
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn max_concurrent_must_be_positive() {
+        let (service, dir) = test_settings();
+        assert_eq!(service.load().max_concurrent, AppSettings::default_max_concurrent());
+        assert!(service.set_max_concurrent(0).await.is_err());
+        assert_eq!(service.set_max_concurrent(3).await.unwrap().max_concurrent, 3);
+        assert_eq!(service.load().max_concurrent, 3);
+        
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+}
@@ -1,12 +1,53 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::process::Command as TokioCommand;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use std::process::Stdio;
 use tauri::Manager;
+use crate::models::{BuildBackend, DownloadCapabilities, DownloadTool, DownloadToolInfo, GpuBackendDetection};
+use crate::services::SettingsService;
+use crate::utils::model_provenance::find_model_provenance;
+
+pub const WHISPER_CPP_REPO_URL: &str = "https://github.com/ggerganov/whisper.cpp.git";
+
+/// 설치 시 체크아웃할 whisper.cpp ref (None이면 기본 브랜치)
+///
+/// 업스트림 변경으로 모든 사용자의 빌드가 한꺼번에 깨지지 않도록 검증된 태그로 고정합니다.
+pub const WHISPER_CPP_PINNED_REF: Option<&str> = Some("v1.7.4");
+
+/// 실제로 설치된 whisper.cpp 버전(git describe 결과)을 기록하는 파일 (저장소 루트 기준)
+const WHISPER_VERSION_FILE: &str = ".whisper-version";
+
+/// 버전을 고정하지 않은 업데이트가 분리된 HEAD에서 돌아갈 기본 브랜치
+const WHISPER_CPP_DEFAULT_BRANCH: &str = "master";
+
+/// whisper.cpp 빌드 시 최대 병렬 작업 수 (코어가 많아도 메모리 사용량이 과하지 않도록 제한)
+const MAX_BUILD_JOBS: usize = 16;
+
+/// 빌드에 사용할 병렬 작업 수 (CPU 코어 수, MAX_BUILD_JOBS로 제한)
+fn build_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_BUILD_JOBS)
+}
+
+/// 모델 다운로드 진행률 이벤트 최소 간격 (밀리초)
+const DOWNLOAD_PROGRESS_INTERVAL_MS: u64 = 250;
+
+/// 모델 저장소 파일 목록 API (LFS 파일마다 sha256이 포함됨)
+const MODEL_TREE_API_URL: &str = "https://huggingface.co/api/models/ggerganov/whisper.cpp/tree/main";
+
+/// 받아 온 모델 체크섬 표를 저장하는 파일 (models 디렉토리 안)
+const MODEL_CHECKSUMS_FILE: &str = "checksums.json";
 
 pub struct WhisperInstaller {
     whisper_repo_path: PathBuf,
     models_path: PathBuf,
+    download_capabilities: std::sync::Mutex<Option<DownloadCapabilities>>, // 처음 확인한 다운로드 도구 정보 캐시
+    model_checksums: std::sync::Mutex<Option<HashMap<String, String>>>, // 모델 이름 → sha256 캐시
+    verified_models: std::sync::Mutex<HashMap<PathBuf, (u64, std::time::SystemTime)>>, // 체크섬이 맞았던 파일의 (크기, 수정 시간)
+    active_downloads: std::sync::atomic::AtomicUsize, // 진행 중인 모델 다운로드/복구 수 (데이터 디렉토리 이동 전 확인용)
 }
 
 impl WhisperInstaller {
@@ -14,25 +55,170 @@ impl WhisperInstaller {
         Self {
             whisper_repo_path,
             models_path,
+            download_capabilities: std::sync::Mutex::new(None),
+            model_checksums: std::sync::Mutex::new(None),
+            verified_models: std::sync::Mutex::new(HashMap::new()),
+            active_downloads: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+    
+    /// 모델 파일을 쓰는 작업이 끝날 때까지 진행 중으로 셉니다 (반환된 가드가 drop되면 끝난 것으로 처리)
+    pub fn track_download(&self) -> DownloadGuard<'_> {
+        self.active_downloads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        DownloadGuard(&self.active_downloads)
+    }
+    
+    pub fn has_active_downloads(&self) -> bool {
+        self.active_downloads.load(std::sync::atomic::Ordering::SeqCst) > 0
+    }
+    
+
+    /// 공식 모델 이름별 기대 SHA256 표
+    ///
+    /// 모델이 추가될 때마다 값을 옮겨 적지 않도록 HuggingFace 저장소의 LFS 메타데이터에서 받아
+    /// models 디렉토리에 저장해 두고 재사용합니다. 받지 못하면(오프라인 등) 빈 표를 반환합니다.
+    pub async fn model_checksums(&self) -> HashMap<String, String> {
+        if let Some(cached) = self.model_checksums.lock().unwrap().clone() {
+            return cached;
+        }
+        
+        let cache_path = self.models_path.join(MODEL_CHECKSUMS_FILE);
+        let checksums = match tokio::fs::read_to_string(&cache_path).await.ok()
+            .and_then(|content| serde_json::from_str::<HashMap<String, String>>(&content).ok())
+        {
+            Some(checksums) => checksums,
+            None => match fetch_model_checksums().await {
+                Ok(checksums) => {
+                    if let Ok(content) = serde_json::to_string_pretty(&checksums) {
+                        tokio::fs::create_dir_all(&self.models_path).await.ok();
+                        tokio::fs::write(&cache_path, content).await.ok();
+                    }
+                    checksums
+                }
+                Err(e) => {
+                    eprintln!("Failed to fetch model checksums: {}", e);
+                    return HashMap::new();
+                }
+            },
+        };
+        
+        *self.model_checksums.lock().unwrap() = Some(checksums.clone());
+        checksums
+    }
+
+    /// 모델의 기대 SHA256을 찾습니다
+    ///
+    /// 설치된 whisper.cpp의 download-ggml-model.sh에 해시가 적혀 있으면 그 값을 우선 사용하고,
+    /// 없으면 HuggingFace에서 받은 표(model_checksums)를 사용합니다.
+    pub async fn expected_model_checksum(&self, model_name: &str) -> Option<String> {
+        let script_path = self.whisper_repo_path.join("models").join("download-ggml-model.sh");
+        if let Ok(script) = tokio::fs::read_to_string(&script_path).await {
+            if let Some(checksum) = parse_script_checksums(&script).remove(model_name) {
+                return Some(checksum);
+            }
+        }
+        
+        self.model_checksums().await.remove(model_name)
+    }
+
+    /// 모델 파일의 SHA256을 기대값과 비교합니다 (기대값을 모르는 모델이면 None)
+    ///
+    /// 한 번 일치한 파일은 크기와 수정 시간이 그대로면 다시 해시하지 않습니다.
+    pub async fn verify_model_checksum(&self, model_name: &str, path: &std::path::Path) -> anyhow::Result<Option<bool>> {
+        let expected = match self.expected_model_checksum(model_name).await {
+            Some(expected) => expected,
+            None => return Ok(None),
+        };
+        
+        let metadata = tokio::fs::metadata(path).await?;
+        let fingerprint = (metadata.len(), metadata.modified()?);
+        if self.verified_models.lock().unwrap().get(path) == Some(&fingerprint) {
+            return Ok(Some(true));
+        }
+        
+        let actual = sha256_file(path).await?;
+        let matches = actual.eq_ignore_ascii_case(&expected);
+        if matches {
+            self.verified_models.lock().unwrap().insert(path.to_path_buf(), fingerprint);
+        } else {
+            eprintln!("Checksum mismatch for {}: expected {}, got {}", model_name, expected, actual);
         }
+        
+        Ok(Some(matches))
     }
 
-    pub async fn setup_whisper(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
+    /// 받은 모델의 체크섬이 틀리면 파일을 지우고 에러를 반환합니다
+    async fn ensure_model_checksum(&self, model_name: &str, path: &std::path::Path) -> anyhow::Result<()> {
+        if self.verify_model_checksum(model_name, path).await? == Some(false) {
+            tokio::fs::remove_file(path).await.ok();
+            return Err(anyhow::anyhow!("Checksum mismatch for model {}, the download is corrupted", model_name));
+        }
+        Ok(())
+    }
+
+    /// 사용 가능한 다운로드 도구(wget/curl)를 확인합니다 (처음 한 번만 확인하고 이후에는 캐시 사용)
+    ///
+    /// 진행률 파싱이 wget 출력 형식에 맞춰져 있으므로 둘 다 있으면 wget을 우선합니다.
+    pub async fn get_download_capabilities(&self) -> DownloadCapabilities {
+        if let Some(cached) = self.download_capabilities.lock().unwrap().clone() {
+            return cached;
+        }
+        
+        let tools = vec![
+            probe_download_tool(DownloadTool::Wget).await,
+            probe_download_tool(DownloadTool::Curl).await,
+        ];
+        let preferred = tools.iter().find(|t| t.available).map(|t| t.tool);
+        let capabilities = DownloadCapabilities { tools, preferred };
+        
+        *self.download_capabilities.lock().unwrap() = Some(capabilities.clone());
+        capabilities
+    }
+
+    async fn preferred_download_tool(&self) -> anyhow::Result<DownloadTool> {
+        self.get_download_capabilities().await
+            .preferred
+            .ok_or_else(|| anyhow::anyhow!("Neither wget nor curl is installed"))
+    }
+
+    /// whisper.cpp를 설치하거나 업데이트합니다
+    ///
+    /// `version`(태그 또는 커밋 SHA)을 지정하면 해당 ref를 체크아웃해서 빌드하고,
+    /// 없으면 WHISPER_CPP_PINNED_REF, 그것도 없으면 기본 브랜치 최신 상태를 사용합니다.
+    /// 고정 태그 체크아웃이 실패하면 경고를 남기고 기본 브랜치로 진행하지만,
+    /// 사용자가 직접 지정한 버전은 실패 시 에러를 반환합니다.
+    pub async fn setup_whisper(&self, app_handle: Option<tauri::AppHandle>, version: Option<String>) -> anyhow::Result<String> {
+        let allow_fallback = version.is_none();
+        let version = version.or_else(|| WHISPER_CPP_PINNED_REF.map(|r| r.to_string()));
+        if let Some(ref version) = version {
+            // git 옵션으로 해석되지 않도록 검사
+            if version.starts_with('-') || version.chars().any(char::is_whitespace) {
+                return Err(anyhow::anyhow!("Invalid whisper.cpp version: {}", version));
+            }
+        }
+        
         let parent_dir = self.whisper_repo_path.parent().unwrap();
         std::fs::create_dir_all(parent_dir)?;
         std::fs::create_dir_all(&self.models_path)?;
 
-        if self.whisper_repo_path.exists() {
+        let result = if self.whisper_repo_path.exists() {
             self.emit_log(app_handle.as_ref(), "Existing installation found, updating...").await;
-            self.update_whisper(app_handle).await
+            self.update_whisper(app_handle.clone(), version.as_deref(), allow_fallback).await
         } else {
             self.emit_log(app_handle.as_ref(), "Starting fresh installation...").await;
-            self.clone_and_build_whisper(app_handle).await
+            self.clone_and_build_whisper(app_handle.clone(), version.as_deref(), allow_fallback).await
+        }?;
+        
+        match self.record_installed_version().await {
+            Ok(installed) => self.emit_log(app_handle.as_ref(), &format!("설치된 whisper.cpp 버전: {}", installed)).await,
+            Err(e) => eprintln!("Failed to record whisper.cpp version: {}", e),
         }
+        
+        Ok(result)
     }
 
-    async fn clone_and_build_whisper(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
-        let repo_url = "https://github.com/ggerganov/whisper.cpp.git";
+    async fn clone_and_build_whisper(&self, app_handle: Option<tauri::AppHandle>, version: Option<&str>, allow_fallback: bool) -> anyhow::Result<String> {
+        let repo_url = WHISPER_CPP_REPO_URL;
         
         if self.whisper_repo_path.exists() {
             self.emit_log(app_handle.as_ref(), "기존 디렉토리 제거 중...").await;
@@ -70,27 +256,179 @@ impl WhisperInstaller {
         }
 
         self.emit_log(app_handle.as_ref(), "Repository 클론 완료!").await;
-        self.build_with_make(app_handle).await
+        if let Some(version) = version {
+            // 클론 직후에는 기본 브랜치가 체크아웃되어 있으므로 실패 시 그대로 진행
+            if let Err(e) = self.checkout_version(version, app_handle.as_ref()).await {
+                if !allow_fallback {
+                    return Err(e);
+                }
+                self.emit_log(app_handle.as_ref(), &format!("⚠️ {}, 기본 브랜치로 빌드합니다", e)).await;
+            }
+        }
+        self.build_whisper(app_handle).await
     }
 
-    async fn update_whisper(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
-        self.emit_log(app_handle.as_ref(), "Git pull로 업데이트 중...").await;
+    async fn update_whisper(&self, app_handle: Option<tauri::AppHandle>, version: Option<&str>, allow_fallback: bool) -> anyhow::Result<String> {
+        let checked_out = match version {
+            Some(version) => match self.fetch_and_checkout(version, app_handle.as_ref()).await {
+                Ok(()) => true,
+                Err(e) if allow_fallback => {
+                    self.emit_log(app_handle.as_ref(), &format!("⚠️ {}, 기본 브랜치로 업데이트합니다", e)).await;
+                    false
+                }
+                Err(e) => return Err(e),
+            },
+            None => false,
+        };
         
+        if !checked_out {
+            // 이전에 버전을 고정했다면 HEAD가 분리되어 있어 pull이 실패하므로 기본 브랜치로 돌아감
+            if self.run_git(&["symbolic-ref", "-q", "HEAD"]).await.is_err() {
+                self.emit_log(app_handle.as_ref(), &format!("{} 브랜치로 전환 중...", WHISPER_CPP_DEFAULT_BRANCH)).await;
+                self.run_git(&["checkout", WHISPER_CPP_DEFAULT_BRANCH]).await
+                    .map_err(|e| anyhow::anyhow!("Git checkout failed: {}", e))?;
+            }
+            
+            self.emit_log(app_handle.as_ref(), "Git pull로 업데이트 중...").await;
+            self.run_git(&["pull"]).await
+                .map_err(|e| anyhow::anyhow!("Git pull failed: {}", e))?;
+        }
+
+        self.emit_log(app_handle.as_ref(), "업데이트 완료, 다시 빌드 중...").await;
+        self.build_whisper(app_handle).await
+    }
+    
+    /// 원격 태그를 가져온 뒤 지정한 ref를 체크아웃합니다
+    async fn fetch_and_checkout(&self, version: &str, app_handle: Option<&tauri::AppHandle>) -> anyhow::Result<()> {
+        self.emit_log(app_handle, "Git fetch로 태그 가져오는 중...").await;
+        self.run_git(&["fetch", "--tags", "origin"]).await
+            .map_err(|e| anyhow::anyhow!("Git fetch failed: {}", e))?;
+        self.checkout_version(version, app_handle).await
+    }
+    
+    /// 지정한 태그/커밋을 체크아웃합니다
+    async fn checkout_version(&self, version: &str, app_handle: Option<&tauri::AppHandle>) -> anyhow::Result<()> {
+        self.emit_log(app_handle, &format!("whisper.cpp {} 체크아웃 중...", version)).await;
+        self.run_git(&["checkout", "--quiet", version]).await
+            .map_err(|e| anyhow::anyhow!("Git checkout of {} failed: {}", version, e))?;
+        Ok(())
+    }
+    
+    /// 저장소 디렉토리에서 git을 실행하고 stdout을 반환합니다 (실패하면 stderr를 에러로 반환)
+    async fn run_git(&self, args: &[&str]) -> anyhow::Result<String> {
         let output = TokioCommand::new("git")
-            .args(["pull"])
+            .args(args)
             .current_dir(&self.whisper_repo_path)
             .output()
             .await?;
-
+        
         if !output.status.success() {
-            return Err(anyhow::anyhow!("Git pull failed: {}", String::from_utf8_lossy(&output.stderr)));
+            return Err(anyhow::anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+    
+    /// 체크아웃된 버전을 git describe로 확인해 WHISPER_VERSION_FILE에 기록합니다
+    async fn record_installed_version(&self) -> anyhow::Result<String> {
+        let version = self.run_git(&["describe", "--tags", "--always"]).await?;
+        tokio::fs::write(self.whisper_repo_path.join(WHISPER_VERSION_FILE), format!("{}\n", version)).await?;
+        Ok(version)
+    }
+    
+    /// 현재 체크아웃된 whisper.cpp 버전을 git describe --tags로 확인합니다
+    ///
+    /// git 체크아웃이 아니거나 태그가 없으면 마지막 설치 시 기록된 버전을 반환합니다.
+    pub async fn describe_installed_version(&self) -> Option<String> {
+        if self.whisper_repo_path.join(".git").exists() {
+            if let Ok(version) = self.run_git(&["describe", "--tags"]).await {
+                if !version.is_empty() {
+                    return Some(version);
+                }
+            }
         }
+        self.get_installed_version().await
+    }
+    
+    /// 마지막 설치 시 기록된 whisper.cpp 버전을 반환합니다 (기록이 없으면 None)
+    pub async fn get_installed_version(&self) -> Option<String> {
+        let content = tokio::fs::read_to_string(self.whisper_repo_path.join(WHISPER_VERSION_FILE)).await.ok()?;
+        let version = content.trim().to_string();
+        if version.is_empty() { None } else { Some(version) }
+    }
 
-        self.emit_log(app_handle.as_ref(), "업데이트 완료, 다시 빌드 중...").await;
-        self.build_with_make(app_handle).await
+    /// 저장소에 맞는 빌드 방식을 고릅니다 (CMakeLists.txt가 있으면 CMake 우선, 없으면 Makefile)
+    ///
+    /// Windows에는 make가 없으므로 항상 CMake를 사용합니다.
+    /// 가속 백엔드는 설정에 저장된 값을 사용하므로 업데이트 후 다시 빌드해도 유지됩니다.
+    async fn build_whisper(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
+        let backend = SettingsService::new().load().build_backend;
+        if let Some(backend) = backend {
+            self.emit_log(app_handle.as_ref(), &format!("Build backend: {:?}", backend)).await;
+        }
+        
+        if cfg!(windows) || self.whisper_repo_path.join("CMakeLists.txt").exists() {
+            self.build_with_cmake(app_handle, backend).await
+        } else if self.whisper_repo_path.join("Makefile").exists() {
+            self.build_with_make(app_handle, backend).await
+        } else {
+            Err(anyhow::anyhow!("Neither CMakeLists.txt nor Makefile found in whisper.cpp directory"))
+        }
+    }
+
+    async fn build_with_cmake(&self, app_handle: Option<tauri::AppHandle>, backend: Option<BuildBackend>) -> anyhow::Result<String> {
+        self.emit_log(app_handle.as_ref(), "Starting compilation with CMake...").await;
+        self.emit_log(app_handle.as_ref(), "⏳ This process may take several minutes...").await;
+        
+        let mut configure = TokioCommand::new("cmake");
+        configure
+            .args(["-B", "build", "-DCMAKE_BUILD_TYPE=Release"])
+            .current_dir(&self.whisper_repo_path);
+        #[cfg(target_os = "macos")]
+        configure.args(["-DCMAKE_OSX_DEPLOYMENT_TARGET=10.15", "-DCMAKE_CXX_STANDARD=17"]);
+        if let Some(backend) = backend {
+            configure.args(cmake_backend_flags(backend));
+        }
+        
+        let output = self.run_build_command(configure, app_handle.clone()).await?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("CMake configure failed.\nStderr: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+        
+        let jobs = build_jobs();
+        self.emit_log(app_handle.as_ref(), &format!("Building with {} parallel jobs", jobs)).await;
+        
+        let cmake_build = |jobs: usize| {
+            let mut command = TokioCommand::new("cmake");
+            command
+                .args(["--build".to_string(), "build".to_string(), "--config".to_string(), "Release".to_string(), "-j".to_string(), jobs.to_string()])
+                .current_dir(&self.whisper_repo_path);
+            command
+        };
+        
+        let mut output = self.run_build_command(cmake_build(jobs), app_handle.clone()).await?;
+        
+        // 병렬 빌드는 여러 작업의 출력이 섞여 에러를 읽기 어려우므로 한 번 더 단일 작업으로 빌드
+        if !output.status.success() && jobs > 1 {
+            self.emit_log(app_handle.as_ref(), "Parallel build failed, retrying with a single job for clearer error output...").await;
+            output = self.run_build_command(cmake_build(1), app_handle.clone()).await?;
+        }
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(anyhow::anyhow!("CMake build failed.\nStderr: {}\nStdout: {}", stderr, stdout));
+        }
+        
+        let binary_path = self.locate_built_binary()?;
+        
+        self.emit_log(app_handle.as_ref(), "✅ CMake build completed!").await;
+        self.emit_log(app_handle.as_ref(), &format!("Binary location: {}", binary_path.display())).await;
+        
+        Ok("Whisper.cpp successfully built with CMake".to_string())
     }
 
-    async fn build_with_make(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
+    async fn build_with_make(&self, app_handle: Option<tauri::AppHandle>, backend: Option<BuildBackend>) -> anyhow::Result<String> {
         // Makefile 존재 확인
         let makefile_path = self.whisper_repo_path.join("Makefile");
         if !makefile_path.exists() {
@@ -98,21 +436,100 @@ impl WhisperInstaller {
         }
 
         self.emit_log(app_handle.as_ref(), "Starting compilation with Make...").await;
+        #[cfg(target_os = "macos")]
         self.emit_log(app_handle.as_ref(), "📦 Using latest C++ standard for macOS compatibility").await;
         self.emit_log(app_handle.as_ref(), "⏳ This process may take several minutes...").await;
-            
-        let mut cmd = TokioCommand::new("make")
-        .args(["build"])
-        .current_dir(&self.whisper_repo_path)
-        .env("MACOSX_DEPLOYMENT_TARGET", "10.15")
-        .env("CMAKE_ARGS", "-DCMAKE_OSX_DEPLOYMENT_TARGET=10.15 -DCMAKE_CXX_STANDARD=17")
-        .env("CXXFLAGS", "-std=c++17 -mmacosx-version-min=10.15")
-        .env("CFLAGS", "-mmacosx-version-min=10.15")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()?;
-
-        // Make 진행상황을 실시간으로 표시
+        
+        let jobs = build_jobs();
+        self.emit_log(app_handle.as_ref(), &format!("Building with {} parallel jobs", jobs)).await;
+        
+        // Makefile이 내부에서 cmake --build를 호출하므로 CMAKE_BUILD_PARALLEL_LEVEL도 함께 지정
+        // (백엔드는 cmake를 거치는 Makefile은 CMAKE_ARGS로, 예전 Makefile은 환경 변수로 전달)
+        let mut cmake_args: Vec<&str> = Vec::new();
+        // 배포 대상 버전 플래그는 Apple clang만 이해하므로 macOS에서만 지정
+        #[cfg(target_os = "macos")]
+        cmake_args.extend(["-DCMAKE_OSX_DEPLOYMENT_TARGET=10.15", "-DCMAKE_CXX_STANDARD=17"]);
+        if let Some(backend) = backend {
+            cmake_args.extend(cmake_backend_flags(backend));
+        }
+        
+        let make_build = |jobs: usize| {
+            let mut command = TokioCommand::new("make");
+            command
+                .args(["build".to_string(), format!("-j{}", jobs)])
+                .current_dir(&self.whisper_repo_path)
+                .env("CMAKE_BUILD_PARALLEL_LEVEL", jobs.to_string());
+            if !cmake_args.is_empty() {
+                command.env("CMAKE_ARGS", cmake_args.join(" "));
+            }
+            if let Some(backend) = backend {
+                command.envs(make_backend_env(backend));
+            }
+            #[cfg(target_os = "macos")]
+            command
+                .env("MACOSX_DEPLOYMENT_TARGET", "10.15")
+                .env("CXXFLAGS", "-std=c++17 -mmacosx-version-min=10.15")
+                .env("CFLAGS", "-mmacosx-version-min=10.15");
+            command
+        };
+        
+        let mut output = self.run_build_command(make_build(jobs), app_handle.clone()).await?;
+        
+        // 병렬 빌드는 여러 작업의 출력이 섞여 에러를 읽기 어려우므로 한 번 더 단일 작업으로 빌드
+        if !output.status.success() && jobs > 1 {
+            self.emit_log(app_handle.as_ref(), "Parallel build failed, retrying with a single job for clearer error output...").await;
+            output = self.run_build_command(make_build(1), app_handle.clone()).await?;
+        }
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(anyhow::anyhow!("Make failed.\nStderr: {}\nStdout: {}", stderr, stdout));
+        }
+
+        let binary_path = self.locate_built_binary()?;
+
+        self.emit_log(app_handle.as_ref(), "✅ Make build completed!").await;
+        self.emit_log(app_handle.as_ref(), &format!("Binary location: {}", binary_path.display())).await;
+        
+        Ok("Whisper.cpp successfully built with Make".to_string())
+    }
+
+    /// 빌드된 바이너리 확인 (CMake 빌드는 build 디렉토리 사용, 최신 버전은 whisper-cli)
+    ///
+    /// Windows의 Visual Studio 제너레이터는 구성별 하위 디렉토리(bin/Release)에 .exe를 만듭니다.
+    fn locate_built_binary(&self) -> anyhow::Result<PathBuf> {
+        let build_dir = self.whisper_repo_path.join("build");
+        #[cfg(windows)]
+        let candidates = [
+            build_dir.join("bin").join("Release").join("whisper-cli.exe"),
+            build_dir.join("bin").join("whisper-cli.exe"),
+            build_dir.join("bin").join("Release").join("main.exe"),
+        ];
+        #[cfg(not(windows))]
+        let candidates = [
+            build_dir.join("bin").join("whisper-cli"),
+            build_dir.join("bin").join("main"),
+            build_dir.join("main"),
+        ];
+        
+        candidates.iter()
+            .find(|path| path.exists())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!(
+                "Whisper binary not found after build. Checked: {}",
+                candidates.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")
+            ))
+    }
+
+    /// 빌드 명령을 실행하고 진행 상황을 setup-log로 보냅니다
+    async fn run_build_command(&self, mut command: TokioCommand, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<std::process::Output> {
+        let mut cmd = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // 빌드 진행상황을 실시간으로 표시
         if let Some(stdout) = cmd.stdout.take() {
             let app_handle_clone = app_handle.clone();
             tokio::spawn(async move {
@@ -141,43 +558,23 @@ impl WhisperInstaller {
             });
         }
 
-        let output = cmd.wait_with_output().await?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            return Err(anyhow::anyhow!("Make failed.\nStderr: {}\nStdout: {}", stderr, stdout));
-        }
-
-        // 빌드된 바이너리 확인 (CMake 빌드는 build 디렉토리 사용)
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
-        
-        let binary_path = if main_binary.exists() {
-            main_binary
-        } else if fallback_binary.exists() {
-            fallback_binary
-        } else {
-            return Err(anyhow::anyhow!("Whisper binary not found after build. Checked: {} and {}", 
-                main_binary.display(), fallback_binary.display()));
-        };
-
-        self.emit_log(app_handle.as_ref(), "✅ Make build completed!").await;
-        self.emit_log(app_handle.as_ref(), &format!("Binary location: {}", binary_path.display())).await;
-        
-        Ok("Whisper.cpp successfully built with Make".to_string())
+        Ok(cmd.wait_with_output().await?)
     }
 
     pub async fn download_model(&self, model_name: &str) -> anyhow::Result<()> {
+        let _download = self.track_download();
         let script_path = self.whisper_repo_path.join("models").join("download-ggml-model.sh");
         
         if script_path.exists() {
             eprintln!("Using whisper.cpp download script for model: {}", model_name);
-            return self.download_with_script(&script_path, model_name).await;
+            self.download_with_script(&script_path, model_name).await?;
         } else {
             eprintln!("Script not found, using direct download for model: {}", model_name);
-            return self.download_with_direct_url(model_name).await;
+            self.download_with_direct_url(model_name).await?;
         }
+        
+        let output_file = self.models_path.join(format!("ggml-{}.bin", model_name));
+        self.ensure_model_checksum(model_name, &output_file).await
     }
 
     async fn download_with_script(&self, script_path: &std::path::Path, model_name: &str) -> anyhow::Result<()> {
@@ -224,14 +621,8 @@ impl WhisperInstaller {
         
         eprintln!("Downloading {} from {}", model_name, model_url);
         
-        let output = TokioCommand::new("wget")
-            .args([
-                "--no-config",
-                "--quiet", 
-                "--show-progress",
-                "-O", &output_file.to_string_lossy(),
-                &model_url
-            ])
+        let tool = self.preferred_download_tool().await?;
+        let output = simple_download_command(tool, &model_url, &output_file)
             .current_dir(&self.models_path)
             .output()
             .await?;
@@ -244,6 +635,29 @@ impl WhisperInstaller {
         Ok(())
     }
 
+    /// 모델 다운로드 크기를 확인합니다 (서버 응답 크기 우선, 연결할 수 없으면 예상 크기)
+    pub async fn get_model_download_size(&self, model_name: &str) -> anyhow::Result<crate::models::ModelDownloadSize> {
+        let model_url = get_model_url_dynamic(model_name)?;
+        let size = match self.preferred_download_tool().await {
+            Ok(tool) => get_remote_file_size(tool, &model_url).await,
+            Err(e) => Err(e),
+        };
+        let (bytes, estimated) = match size {
+            Ok(size) => (size, false),
+            Err(e) => {
+                eprintln!("Falling back to expected size for {}: {}", model_name, e);
+                (get_expected_model_size(model_name), true)
+            }
+        };
+        
+        Ok(crate::models::ModelDownloadSize {
+            model_name: model_name.to_string(),
+            bytes,
+            display: crate::utils::format_file_size(bytes),
+            estimated,
+        })
+    }
+
     pub async fn download_model_with_progress(
         &self, 
         model_name: &str, 
@@ -251,6 +665,7 @@ impl WhisperInstaller {
     ) -> anyhow::Result<()> {
         use crate::models::{DownloadProgress, DownloadStatus};
         
+        let _download = self.track_download();
         let output_file = self.models_path.join(format!("ggml-{}.bin", model_name));
         
         // 모델 디렉토리 생성
@@ -270,207 +685,265 @@ impl WhisperInstaller {
             return Ok(());
         }
 
-        // 스크립트 기반 다운로드 시도
-        let script_path = self.whisper_repo_path.join("models").join("download-ggml-model.sh");
-        
-        if script_path.exists() {
-            return self.download_model_with_script_progress(model_name, app_handle).await;
-        } else {
-            return self.download_model_with_direct_progress(model_name, app_handle).await;
-        }
+        self.download_model_with_stream(model_name, &output_file, app_handle).await
     }
 
-    async fn download_model_with_script_progress(
+    /// reqwest로 모델을 스트리밍 다운로드하며 받은 바이트 수로 진행률/속도/남은 시간을 계산합니다
+    ///
+    /// 받는 동안에는 .part 파일에 쓰고, 끝까지 받은 뒤에만 최종 경로로 옮겨 중간에 실패한 파일이 모델로 보이지 않게 합니다.
+    /// 남아 있는 .part 파일은 Range 요청으로 이어받습니다 (서버가 206 대신 200을 주면 처음부터 다시 받음).
+    async fn download_model_with_stream(
         &self,
         model_name: &str,
+        output_file: &std::path::Path,
         app_handle: tauri::AppHandle
     ) -> anyhow::Result<()> {
         use crate::models::{DownloadProgress, DownloadStatus};
+        use tokio::io::AsyncWriteExt;
         
-        let script_path = self.whisper_repo_path.join("models").join("download-ggml-model.sh");
+        let model_url = get_model_url_dynamic(model_name)?;
+        let partial_file = output_file.with_extension("bin.part");
         
-        // 스크립트 실행 권한 확인 및 설정
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let metadata = std::fs::metadata(&script_path)?;
-            let mut permissions = metadata.permissions();
-            permissions.set_mode(0o755);
-            std::fs::set_permissions(&script_path, permissions)?;
-        }
+        // 이전에 끊긴 다운로드가 남아 있으면 그 뒤부터 이어받음
+        let existing_bytes = tokio::fs::metadata(&partial_file).await.map(|m| m.len()).unwrap_or(0);
         
         // 다운로드 시작 알림
         app_handle.emit_all("download-progress", &DownloadProgress {
             model_name: model_name.to_string(),
             progress: 0.0,
-            downloaded_bytes: 0,
+            downloaded_bytes: existing_bytes,
             total_bytes: None,
             download_speed: None,
             eta: None,
             status: DownloadStatus::Starting,
         }).ok();
         
-        eprintln!("Starting script-based download for model: {}", model_name);
-        
-        let mut cmd = TokioCommand::new("bash")
-            .args([&script_path.to_string_lossy(), model_name, &self.models_path.to_string_lossy()])
-            .current_dir(&self.whisper_repo_path.join("models"))
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let model_name_clone = model_name.to_string();
-        let app_handle_stderr = app_handle.clone();
-        
-        // stderr에서 wget 진행률 파싱
-        if let Some(stderr) = cmd.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    eprintln!("SCRIPT STDERR: '{}'", line);
-                    
-                    if let Some(progress) = parse_wget_progress(&line, &model_name_clone) {
-                        eprintln!("PARSED PROGRESS: {:?}", progress);
-                        app_handle_stderr.emit_all("download-progress", &progress).ok();
+        let result = async {
+            let client = reqwest::Client::new();
+            let mut response = if existing_bytes > 0 {
+                eprintln!("Resuming download of {} from {} bytes", model_name, existing_bytes);
+                client.get(&model_url)
+                    .header(reqwest::header::RANGE, format!("bytes={}-", existing_bytes))
+                    .send()
+                    .await?
+            } else {
+                client.get(&model_url).send().await?
+            };
+            
+            // 남은 파일이 이미 전체 크기 이상이면 범위를 만족할 수 없으므로 처음부터 다시 받음
+            if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+                response = client.get(&model_url).send().await?;
+            }
+            let mut response = response.error_for_status()?;
+            
+            // 206이면 이어서 쓰고, 200이면 서버가 Range를 무시한 것이므로 처음부터 씀
+            let resumed_bytes = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT { existing_bytes } else { 0 };
+            let total_bytes = response.content_length().map(|length| length + resumed_bytes);
+            let mut file = if resumed_bytes > 0 {
+                tokio::fs::OpenOptions::new().append(true).open(&partial_file).await?
+            } else {
+                tokio::fs::File::create(&partial_file).await?
+            };
+            
+            let started = std::time::Instant::now();
+            let mut last_emit = started;
+            let mut downloaded_bytes: u64 = resumed_bytes;
+            
+            while let Some(chunk) = response.chunk().await? {
+                file.write_all(&chunk).await?;
+                downloaded_bytes += chunk.len() as u64;
+                
+                if last_emit.elapsed() < std::time::Duration::from_millis(DOWNLOAD_PROGRESS_INTERVAL_MS) {
+                    continue;
+                }
+                last_emit = std::time::Instant::now();
+                
+                // 속도는 이번에 받은 양으로만 계산
+                let elapsed = started.elapsed().as_secs_f64();
+                let session_bytes = downloaded_bytes - resumed_bytes;
+                let bytes_per_second = if elapsed > 0.0 { session_bytes as f64 / elapsed } else { 0.0 };
+                let eta = match total_bytes {
+                    Some(total) if bytes_per_second > 0.0 => {
+                        Some(format_eta(total.saturating_sub(downloaded_bytes) as f64 / bytes_per_second))
                     }
+                    _ => None,
+                };
+                
+                app_handle.emit_all("download-progress", &DownloadProgress {
+                    model_name: model_name.to_string(),
+                    progress: total_bytes
+                        .filter(|total| *total > 0)
+                        .map(|total| (downloaded_bytes as f64 / total as f64) as f32)
+                        .unwrap_or(0.0),
+                    downloaded_bytes,
+                    total_bytes,
+                    download_speed: Some(format!("{}/s", crate::utils::format_file_size(bytes_per_second as u64))),
+                    eta,
+                    status: DownloadStatus::Downloading,
+                }).ok();
+            }
+            
+            file.flush().await?;
+            
+            if let Some(total) = total_bytes {
+                if downloaded_bytes != total {
+                    return Err(anyhow::anyhow!(
+                        "Download incomplete: received {} of {} bytes", downloaded_bytes, total
+                    ));
                 }
-            });
-        }
-
-        let output = cmd.wait_with_output().await?;
-
-        if output.status.success() {
-            // 다운로드 완료
+            }
+            
             app_handle.emit_all("download-progress", &DownloadProgress {
                 model_name: model_name.to_string(),
                 progress: 1.0,
-                downloaded_bytes: 0,
-                total_bytes: None,
-                download_speed: None,
-                eta: None,
-                status: DownloadStatus::Completed,
-            }).ok();
-            Ok(())
-        } else {
-            // 다운로드 실패
-            app_handle.emit_all("download-progress", &DownloadProgress {
-                model_name: model_name.to_string(),
-                progress: 0.0,
-                downloaded_bytes: 0,
-                total_bytes: None,
+                downloaded_bytes,
+                total_bytes,
                 download_speed: None,
                 eta: None,
-                status: DownloadStatus::Failed,
+                status: DownloadStatus::Verifying,
             }).ok();
+            self.ensure_model_checksum(model_name, &partial_file).await?;
             
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Err(anyhow::anyhow!("Script download failed: {}\n{}", stderr, stdout))
+            tokio::fs::rename(&partial_file, output_file).await?;
+            Ok::<(u64, Option<u64>), anyhow::Error>((downloaded_bytes, total_bytes))
+        }.await;
+        
+        match result {
+            Ok((downloaded_bytes, total_bytes)) => {
+                // 다운로드 완료
+                app_handle.emit_all("download-progress", &DownloadProgress {
+                    model_name: model_name.to_string(),
+                    progress: 1.0,
+                    downloaded_bytes,
+                    total_bytes,
+                    download_speed: None,
+                    eta: None,
+                    status: DownloadStatus::Completed,
+                }).ok();
+                Ok(())
+            }
+            Err(e) => {
+                // 다운로드 실패 (.part 파일은 다음 시도에서 이어받도록 남겨 둠, 체크섬 불일치 시에는 이미 삭제됨)
+                app_handle.emit_all("download-progress", &DownloadProgress {
+                    model_name: model_name.to_string(),
+                    progress: 0.0,
+                    downloaded_bytes: 0,
+                    total_bytes: None,
+                    download_speed: None,
+                    eta: None,
+                    status: DownloadStatus::Failed,
+                }).ok();
+                Err(anyhow::anyhow!("Download failed: {}", e))
+            }
         }
     }
 
-    async fn download_model_with_direct_progress(
-        &self,
-        model_name: &str,
-        app_handle: tauri::AppHandle
-    ) -> anyhow::Result<()> {
-        use crate::models::{DownloadProgress, DownloadStatus};
+    /// 설치된 whisper.cpp 저장소의 HEAD 커밋 해시를 반환합니다 (git 체크아웃이 아니면 None)
+    pub async fn get_installed_commit(&self) -> Option<String> {
+        if !self.whisper_repo_path.join(".git").exists() {
+            return None;
+        }
         
-        // 폴백: 직접 URL로 다운로드 (기존 진행률 파싱 방식)
-        let model_url = get_model_url_dynamic(model_name)?;
-        let output_file = self.models_path.join(format!("ggml-{}.bin", model_name));
-
-        // 다운로드 시작 알림
-        app_handle.emit_all("download-progress", &DownloadProgress {
-            model_name: model_name.to_string(),
-            progress: 0.0,
-            downloaded_bytes: 0,
-            total_bytes: None,
-            download_speed: None,
-            eta: None,
-            status: DownloadStatus::Starting,
-        }).ok();
-
-        // wget 명령어로 다운로드 (실시간 진행률 파싱)
-        let mut cmd = TokioCommand::new("wget")
-            .args([
-                "--progress=dot:giga",   // 더 자주 업데이트되는 dot 형식 사용
-                "--show-progress",       // 진행률 표시
-                "-O", &output_file.to_string_lossy(),
-                &model_url
-            ])
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let model_name_clone = model_name.to_string();
-        let app_handle_stderr = app_handle.clone();
+        let output = TokioCommand::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(&self.whisper_repo_path)
+            .output()
+            .await
+            .ok()?;
         
-        // wget 진행률 파싱이 활성화되었는지 추적하기 위한 공유 상태
-        let wget_active = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-        let wget_active_clone = wget_active.clone();
-
-        // stderr에서 wget 진행률 파싱
-        if let Some(stderr) = cmd.stderr.take() {
-            tokio::spawn(async move {
-                let mut reader = BufReader::new(stderr).lines();
-                while let Ok(Some(line)) = reader.next_line().await {
-                    // 모든 wget 출력 디버깅
-                    eprintln!("WGET STDERR: '{}'", line);
-                    
-                    if let Some(progress) = parse_wget_progress(&line, &model_name_clone) {
-                        eprintln!("PARSED PROGRESS: {:?}", progress);
-                        
-                        // wget 파싱이 활성화됨을 표시
-                        wget_active_clone.store(true, std::sync::atomic::Ordering::Relaxed);
-                        
-                        app_handle_stderr.emit_all("download-progress", &progress).ok();
-                    }
-                }
-            });
+        if !output.status.success() {
+            return None;
         }
-
-        // wget 파싱이 잘 작동하므로 파일 크기 모니터링 임시 비활성화
-        // (필요시 나중에 활성화 가능)
-        let size_monitor = tokio::spawn(async move {
-            // 빈 태스크 - wget 파싱만 사용
-            eprintln!("FILE SIZE MONITORING DISABLED - USING WGET PARSING ONLY");
-            loop {
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        
+        let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if commit.is_empty() { None } else { Some(commit) }
+    }
+    
+    /// models/download-ggml-model.sh만 다시 받아옵니다 (git 체크아웃 우선, 실패하면 원본 저장소에서 직접 다운로드)
+    pub async fn repair_download_script(&self) -> anyhow::Result<()> {
+        let relative_path = "models/download-ggml-model.sh";
+        let script_path = self.whisper_repo_path.join(relative_path);
+        
+        let restored = if self.whisper_repo_path.join(".git").exists() {
+            let output = TokioCommand::new("git")
+                .args(["checkout", "HEAD", "--", relative_path])
+                .current_dir(&self.whisper_repo_path)
+                .output()
+                .await?;
+            if !output.status.success() {
+                eprintln!("Git checkout of download script failed: {}", String::from_utf8_lossy(&output.stderr));
             }
-        });
-
-        let output = cmd.wait_with_output().await?;
+            output.status.success()
+        } else {
+            false
+        };
+        
+        if !restored {
+            let script_url = format!(
+                "https://raw.githubusercontent.com/ggerganov/whisper.cpp/{}/{}",
+                WHISPER_CPP_PINNED_REF.unwrap_or("master"),
+                relative_path
+            );
+            std::fs::create_dir_all(self.whisper_repo_path.join("models"))?;
+            
+            eprintln!("Downloading download script from {}", script_url);
+            let tool = self.preferred_download_tool().await?;
+            let output = simple_download_command(tool, &script_url, &script_path)
+                .output()
+                .await?;
+            
+            if !output.status.success() {
+                tokio::fs::remove_file(&script_path).await.ok();
+                return Err(anyhow::anyhow!("Download script fetch failed: {}", String::from_utf8_lossy(&output.stderr)));
+            }
+        }
         
-        // 파일 크기 모니터링 중단
-        size_monitor.abort();
+        Ok(())
+    }
 
-        if output.status.success() {
-            // 다운로드 완료
-            app_handle.emit_all("download-progress", &DownloadProgress {
-                model_name: model_name.to_string(),
-                progress: 1.0,
-                downloaded_bytes: 0,
-                total_bytes: None,
-                download_speed: None,
-                eta: None,
-                status: DownloadStatus::Completed,
-            }).ok();
-            Ok(())
+    /// 설치된 도구와 플랫폼으로 빌드 가능한 가속 백엔드를 확인하고 기본값을 제안합니다
+    ///
+    /// CUDA는 nvidia-smi로 GPU를 찾았더라도 nvcc가 없으면 빌드할 수 없으므로 제안하지 않습니다.
+    pub async fn detect_gpu_backends(&self) -> GpuBackendDetection {
+        let nvidia_gpus = match TokioCommand::new("nvidia-smi")
+            .args(["--query-gpu=name", "--format=csv,noheader"])
+            .output()
+            .await
+        {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let cuda_toolkit = command_succeeds("nvcc", &["--version"]).await;
+        let openblas = command_succeeds("pkg-config", &["--exists", "openblas"]).await;
+        
+        let mut available = vec![BuildBackend::Cpu];
+        if cfg!(target_os = "macos") {
+            available.push(BuildBackend::Metal);
+        }
+        if !nvidia_gpus.is_empty() && cuda_toolkit {
+            available.push(BuildBackend::Cuda);
+        }
+        if openblas {
+            available.push(BuildBackend::OpenBlas);
+        }
+        
+        let suggested = if cfg!(target_os = "macos") {
+            BuildBackend::Metal
+        } else if available.contains(&BuildBackend::Cuda) {
+            BuildBackend::Cuda
         } else {
-            // 다운로드 실패
-            app_handle.emit_all("download-progress", &DownloadProgress {
-                model_name: model_name.to_string(),
-                progress: 0.0,
-                downloaded_bytes: 0,
-                total_bytes: None,
-                download_speed: None,
-                eta: None,
-                status: DownloadStatus::Failed,
-            }).ok();
-            
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(anyhow::anyhow!("Download failed: {}", stderr))
+            BuildBackend::Cpu
+        };
+        
+        GpuBackendDetection {
+            available,
+            suggested,
+            nvidia_gpus,
+            cuda_toolkit,
         }
     }
 
@@ -481,31 +954,30 @@ impl WhisperInstaller {
     }
 }
 
+/// `WhisperInstaller::track_download`가 반환하는 가드 (drop되면 진행 중인 다운로드 수를 줄임)
+pub struct DownloadGuard<'a>(&'a std::sync::atomic::AtomicUsize);
+
+impl Drop for DownloadGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// 공식 모델의 다운로드 URL (양자화/turbo/tdrz 변형 포함, 출처 표에 있는 모델만)
 fn get_model_url(model_name: &str) -> anyhow::Result<String> {
-    let base_url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
-    
-    let url = match model_name {
-        "tiny" => format!("{}/ggml-tiny.bin", base_url),
-        "tiny.en" => format!("{}/ggml-tiny.en.bin", base_url),
-        "base" => format!("{}/ggml-base.bin", base_url),
-        "base.en" => format!("{}/ggml-base.en.bin", base_url),
-        "small" => format!("{}/ggml-small.bin", base_url),
-        "small.en" => format!("{}/ggml-small.en.bin", base_url),
-        "medium" => format!("{}/ggml-medium.bin", base_url),
-        "medium.en" => format!("{}/ggml-medium.en.bin", base_url),
-        "large-v1" => format!("{}/ggml-large-v1.bin", base_url),
-        "large-v2" => format!("{}/ggml-large-v2.bin", base_url),
-        "large-v3" => format!("{}/ggml-large-v3.bin", base_url),
-        _ => return Err(anyhow::anyhow!("Unknown model: {}", model_name)),
-    };
-    
-    Ok(url)
+    find_model_provenance(model_name)
+        .map(|provenance| provenance.download_url)
+        .ok_or_else(|| anyhow::anyhow!("Unknown model: {}", model_name))
 }
 
 fn get_model_url_dynamic(model_name: &str) -> anyhow::Result<String> {
+    if let Ok(url) = get_model_url(model_name) {
+        return Ok(url);
+    }
+    
     let base_url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
     
-    // 모든 모델 변형을 지원하는 동적 URL 생성
+    // 출처 표에 없는 모델(스크립트에 새로 추가된 변형 등)도 같은 이름 규칙으로 시도
     // 모델명이 정확히 일치하므로 ggml-{model}.bin 형식으로 구성
     let url = format!("{}/ggml-{}.bin", base_url, model_name);
     
@@ -513,24 +985,65 @@ fn get_model_url_dynamic(model_name: &str) -> anyhow::Result<String> {
     Ok(url)
 }
 
-async fn get_remote_file_size(url: &str) -> anyhow::Result<u64> {
-    // wget을 사용하여 파일 크기 확인
-    let output = TokioCommand::new("wget")
-        .args([
-            "--spider",           // 파일을 다운로드하지 않고 헤더만 확인
-            "--server-response",  // 서버 응답 헤더 표시
-            url
-        ])
-        .output()
-        .await?;
+/// 백엔드별 CMake 옵션 (Cpu는 macOS에서 기본으로 켜지는 Metal을 명시적으로 끔)
+fn cmake_backend_flags(backend: BuildBackend) -> Vec<&'static str> {
+    match backend {
+        BuildBackend::Cpu => vec!["-DGGML_METAL=OFF", "-DGGML_CUDA=OFF", "-DGGML_BLAS=OFF"],
+        BuildBackend::Metal => vec!["-DGGML_METAL=ON"],
+        BuildBackend::Cuda => vec!["-DGGML_CUDA=ON"],
+        BuildBackend::OpenBlas => vec!["-DGGML_BLAS=ON", "-DGGML_BLAS_VENDOR=OpenBLAS"],
+    }
+}
+
+/// cmake를 거치지 않는 예전 Makefile용 백엔드 환경 변수
+fn make_backend_env(backend: BuildBackend) -> Vec<(&'static str, &'static str)> {
+    match backend {
+        BuildBackend::Cpu => vec![("GGML_NO_METAL", "1")],
+        BuildBackend::Metal => vec![("GGML_METAL", "1")],
+        BuildBackend::Cuda => vec![("GGML_CUDA", "1")],
+        BuildBackend::OpenBlas => vec![("GGML_OPENBLAS", "1"), ("GGML_BLAS", "1")],
+    }
+}
+
+/// 명령을 실행해 성공하는지만 확인합니다 (실행 파일이 없으면 false)
+async fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    TokioCommand::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+async fn get_remote_file_size(tool: DownloadTool, url: &str) -> anyhow::Result<u64> {
+    let output = match tool {
+        DownloadTool::Wget => TokioCommand::new("wget")
+            .args([
+                "--spider",           // 파일을 다운로드하지 않고 헤더만 확인
+                "--server-response",  // 서버 응답 헤더 표시
+                "--timeout=10",       // 오프라인일 때 오래 기다리지 않도록 제한
+                "--tries=1",
+                url
+            ])
+            .output()
+            .await?,
+        DownloadTool::Curl => TokioCommand::new("curl")
+            .args(["--silent", "--head", "--location", "--max-time", "10", url])
+            .output()
+            .await?,
+    };
 
-    // wget은 헤더 정보를 stderr에 출력
-    let stderr_output = String::from_utf8_lossy(&output.stderr);
+    // wget은 헤더 정보를 stderr에, curl은 stdout에 출력
+    let header_output = match tool {
+        DownloadTool::Wget => String::from_utf8_lossy(&output.stderr),
+        DownloadTool::Curl => String::from_utf8_lossy(&output.stdout),
+    };
     
     let mut last_content_length = None;
     
-    // Content-Length 헤더 찾기
-    for line in stderr_output.lines() {
+    // Content-Length 헤더 찾기 (리다이렉트가 있으면 마지막 응답 기준)
+    for line in header_output.lines() {
         if line.to_lowercase().contains("content-length:") {
             if let Some(size_str) = line.split(':').nth(1) {
                 if let Ok(size) = size_str.trim().parse::<u64>() {
@@ -547,6 +1060,80 @@ async fn get_remote_file_size(url: &str) -> anyhow::Result<u64> {
     Err(anyhow::anyhow!("Could not determine file size"))
 }
 
+fn tool_program(tool: DownloadTool) -> &'static str {
+    match tool {
+        DownloadTool::Wget => "wget",
+        DownloadTool::Curl => "curl",
+    }
+}
+
+/// `<도구> --version`으로 설치 여부와 버전을, 도움말에 나오는 옵션으로 이어받기/프록시 지원 여부를 확인합니다
+///
+/// 옵션 지원 여부만 보며, 실제 서버가 Range 요청을 받아 주는지나 프록시 설정이 맞는지는 확인하지 않습니다.
+async fn probe_download_tool(tool: DownloadTool) -> DownloadToolInfo {
+    let version_line = match TokioCommand::new(tool_program(tool)).arg("--version").output().await {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(str::to_string),
+        _ => None,
+    };
+    
+    // "GNU Wget 1.21.4 built on linux-gnu." / "curl 8.4.0 (x86_64-pc-linux-gnu) ..."
+    let version = version_line.as_deref().and_then(|line| {
+        line.split_whitespace()
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            .map(str::to_string)
+    });
+    let available = version_line.is_some();
+    
+    let help = if available {
+        match TokioCommand::new(tool_program(tool)).args(help_args(tool)).output().await {
+            Ok(output) => format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+            Err(_) => String::new(),
+        }
+    } else {
+        String::new()
+    };
+    let (supports_resume, supports_proxy) = parse_help_capabilities(tool, &help);
+    
+    DownloadToolInfo {
+        tool,
+        available,
+        version,
+        supports_resume,
+        supports_proxy,
+    }
+}
+
+/// 전체 옵션 목록을 출력하는 도움말 인자 (curl 7.73+는 `--help`만으로는 일부 분류만 보여 줌)
+fn help_args(tool: DownloadTool) -> &'static [&'static str] {
+    match tool {
+        DownloadTool::Wget => &["--help"],
+        DownloadTool::Curl => &["--help", "all"],
+    }
+}
+
+/// 도움말에서 이어받기(wget `--continue` / curl `--continue-at`)와 프록시 옵션(wget `--no-proxy` / curl `--proxy`)을 찾습니다
+fn parse_help_capabilities(tool: DownloadTool, help: &str) -> (bool, bool) {
+    let (resume_option, proxy_option) = match tool {
+        DownloadTool::Wget => ("--continue", "--no-proxy"),
+        DownloadTool::Curl => ("--continue-at", "--proxy "),
+    };
+    (help.contains(resume_option), help.contains(proxy_option))
+}
+
+/// 진행률 파싱 없이 파일 하나를 받는 명령
+fn simple_download_command(tool: DownloadTool, url: &str, output_file: &std::path::Path) -> TokioCommand {
+    let mut command = TokioCommand::new(tool_program(tool));
+    match tool {
+        DownloadTool::Wget => command.args(["--no-config", "--quiet", "-O"]),
+        DownloadTool::Curl => command.args(["--fail", "--silent", "--show-error", "--location", "-o"]),
+    };
+    command.arg(output_file).arg(url);
+    command
+}
+
 fn get_expected_model_size(model_name: &str) -> u64 {
     // 예상 모델 크기 (바이트 단위)
     match model_name {
@@ -559,138 +1146,160 @@ fn get_expected_model_size(model_name: &str) -> u64 {
     }
 }
 
-fn parse_wget_progress(line: &str, model_name: &str) -> Option<crate::models::DownloadProgress> {
-    use crate::models::{DownloadProgress, DownloadStatus};
-    
-    // wget 진행률 출력 파싱
-    // 다양한 형식 지원:
-    // 1. Bar 형식: "test_download        95%[==================> ]  46.72K   491 B/s    약 5s"
-    // 2. Dot 형식: "     0K .......... .......... .......... .......... ..........  0%  491K 5s"
-    // 3. Show-progress 형식: "46,720K  .......... .......... .......... .......... ..........  95%  491K 5s"
-    
-    // 모든 wget 출력을 더 자세히 디버깅
-    eprintln!("WGET LINE ANALYSIS: '{}'", line);
-    
-    // 패턴 1: 퍼센티지 찾기 (95%, 100% 등)
-    if let Some(percent_pos) = line.find('%') {
-        // 퍼센티지 앞의 숫자 찾기
-        let before_percent = &line[..percent_pos];
-        
-        // 여러 패턴으로 퍼센티지 추출 시도
-        let percentage = if let Some(last_space) = before_percent.rfind(' ') {
-            // 공백으로 구분된 경우
-            before_percent[last_space + 1..].parse::<f32>().ok()
-        } else if let Some(last_bracket) = before_percent.rfind(']') {
-            // 대괄호 다음에 오는 경우
-            before_percent[last_bracket + 1..].trim().parse::<f32>().ok()
-        } else {
-            // 직접 파싱 시도
-            before_percent.trim().parse::<f32>().ok()
-        };
-        
-        if let Some(percentage) = percentage {
-            let progress = percentage / 100.0;
-            
-            eprintln!("FOUND PERCENTAGE: {}% -> progress: {}", percentage, progress);
-            
-            // 다운로드 속도 파싱 (K/s, M/s, B/s 등)
-            let download_speed = extract_speed_from_line(line);
-            
-            // ETA 파싱 (초 단위)
-            let eta = extract_eta_from_line(line);
-            
-            // 다운로드된 크기 파싱
-            let downloaded_bytes = parse_size_from_line(line);
-            
-            return Some(DownloadProgress {
-                model_name: model_name.to_string(),
-                progress,
-                downloaded_bytes,
-                total_bytes: None,
-                download_speed,
-                eta,
-                status: if progress >= 1.0 { 
-                    DownloadStatus::Completed 
-                } else { 
-                    DownloadStatus::Downloading 
-                },
-            });
-        }
-    }
+/// 저장소 파일 목록에서 ggml-<name>.bin 파일들의 LFS sha256을 모읍니다
+async fn fetch_model_checksums() -> anyhow::Result<HashMap<String, String>> {
+    let entries: Vec<serde_json::Value> = reqwest::get(MODEL_TREE_API_URL).await?
+        .error_for_status()?
+        .json()
+        .await?;
     
-    // 패턴 2: "received/total" 형식 파싱 (일부 wget 버전에서 사용)
-    if line.contains("received") || line.contains("saved") {
-        eprintln!("FOUND RECEIVED/SAVED PATTERN: {}", line);
-        // 이 경우에도 파싱 로직 추가 가능
-    }
+    let checksums = entries.iter()
+        .filter_map(|entry| {
+            let path = entry.get("path")?.as_str()?;
+            let model_name = path.strip_prefix("ggml-")?.strip_suffix(".bin")?;
+            let oid = entry.get("lfs")?.get("oid")?.as_str()?;
+            Some((model_name.to_string(), oid.trim_start_matches("sha256:").to_lowercase()))
+        })
+        .collect();
     
-    None
+    Ok(checksums)
 }
 
-fn extract_speed_from_line(line: &str) -> Option<String> {
-    // 속도 패턴 찾기: "491K", "1.2M", "500B" 등 뒤에 "/s" 또는 단독으로
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    
-    for (i, part) in parts.iter().enumerate() {
-        // "K/s", "M/s", "B/s" 형태
-        if part.ends_with("K/s") || part.ends_with("M/s") || part.ends_with("B/s") {
-            return Some(part.to_string());
-        }
-        // "K", "M" 뒤에 "/s"가 올 수 있음
-        if (part.ends_with('K') || part.ends_with('M')) && i + 1 < parts.len() {
-            if parts[i + 1] == "/s" || parts[i + 1].starts_with("/") {
-                return Some(format!("{}/s", part));
-            }
-        }
-        // 단독 "K", "M" 형태 (wget dot 형식에서 자주 보임)
-        if part.ends_with('K') || part.ends_with('M') {
-            // 숫자로 시작하는지 확인
-            if part.chars().next().map_or(false, |c| c.is_ascii_digit()) {
-                return Some(format!("{}/s", part));
+/// 다운로드 스크립트에서 "모델 이름 ... 64자리 16진수" 형태로 적힌 SHA256을 모읍니다
+fn parse_script_checksums(script: &str) -> HashMap<String, String> {
+    let pattern = regex::Regex::new(r#"["']?([a-z0-9][a-z0-9.\-]*)["']?\s*[=:,\s]\s*["']?([0-9a-fA-F]{64})\b"#).unwrap();
+    pattern.captures_iter(script)
+        .map(|captures| (captures[1].to_string(), captures[2].to_lowercase()))
+        .collect()
+}
+
+/// 파일의 SHA256을 16진수 문자열로 계산합니다 (큰 모델 파일도 나눠 읽음)
+pub async fn sha256_file(path: &std::path::Path) -> anyhow::Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<String> {
+        use sha2::{Digest, Sha256};
+        use std::io::Read;
+        
+        let mut file = std::fs::File::open(&path)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 1024 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
             }
+            hasher.update(&buffer[..read]);
         }
-    }
-    
-    None
+        
+        Ok(format!("{:x}", hasher.finalize()))
+    }).await?
 }
 
-fn extract_eta_from_line(line: &str) -> Option<String> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
+/// 남은 시간을 "1h2m", "3m5s", "5s" 형식으로 표시합니다
+fn format_eta(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (hours, minutes, secs) = (total / 3600, (total % 3600) / 60, total % 60);
     
-    // 마지막 부분에서 시간 형식 찾기 ("5s", "1m", "10m", "1h2m" 등)
-    for part in parts.iter().rev() {
-        if part.ends_with('s') || part.ends_with('m') || part.ends_with('h') {
-            if part.chars().next().map_or(false, |c| c.is_ascii_digit()) {
-                return Some(part.to_string());
-            }
-        }
+    if hours > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
     }
-    
-    None
 }
 
-fn parse_size_from_line(line: &str) -> u64 {
-    // 크기 표시를 찾기 (예: "46.72K", "1.2M", "1234")
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    
-    for part in parts.iter() {
-        if part.ends_with('K') || part.ends_with('M') || part.ends_with('G') {
-            if let Ok(num) = part[..part.len()-1].parse::<f64>() {
-                let multiplier = match part.chars().last() {
-                    Some('K') => 1024,
-                    Some('M') => 1024 * 1024,
-                    Some('G') => 1024 * 1024 * 1024,
-                    _ => 1,
-                };
-                return (num * multiplier as f64) as u64;
-            }
-        } else if let Ok(num) = part.parse::<u64>() {
-            // 일반 숫자인 경우
-            if num > 1000 { // 바이트 크기로 추정되는 큰 숫자
-                return num;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn parse_help_capabilities_finds_wget_options() {
+        let help = "  -c,  --continue                 resume getting a partially-downloaded file\n       --no-proxy                 explicitly turn off proxy\n";
+        assert_eq!(parse_help_capabilities(DownloadTool::Wget, help), (true, true));
+        assert_eq!(parse_help_capabilities(DownloadTool::Wget, "  -q,  --quiet"), (false, false));
+    }
+    
+    #[test]
+    fn parse_help_capabilities_ignores_curl_proxy_prefixed_options() {
+        let help = " -C, --continue-at <offset>  Resumed transfer offset\n     --proxy-anyauth         Pick any proxy authentication method\n";
+        assert_eq!(parse_help_capabilities(DownloadTool::Curl, help), (true, false));
+        
+        let help = format!("{} -x, --proxy [protocol://]host[:port] Use this proxy\n", help);
+        assert_eq!(parse_help_capabilities(DownloadTool::Curl, &help), (true, true));
+    }
+    
+    #[test]
+    fn get_model_url_covers_quantized_and_turbo_models() {
+        let base = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+        for model in ["base.en-q5_1", "large-v3-turbo", "large-v3-turbo-q5_0", "small.en-tdrz", "medium-q8_0"] {
+            assert_eq!(get_model_url(model).unwrap(), format!("{}/ggml-{}.bin", base, model));
         }
+        assert!(get_model_url("my-finetune").is_err());
+    }
+    
+    #[test]
+    fn get_model_url_dynamic_falls_back_to_naming_rule() {
+        assert_eq!(get_model_url_dynamic("large-v3").unwrap(), get_model_url("large-v3").unwrap());
+        assert_eq!(
+            get_model_url_dynamic("large-v4-preview").unwrap(),
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v4-preview.bin"
+        );
     }
     
-    0
-}
\ No newline at end of file
+    #[test]
+    fn cmake_backend_flags_select_one_backend() {
+        assert_eq!(cmake_backend_flags(BuildBackend::Cpu), vec!["-DGGML_METAL=OFF", "-DGGML_CUDA=OFF", "-DGGML_BLAS=OFF"]);
+        assert_eq!(cmake_backend_flags(BuildBackend::Metal), vec!["-DGGML_METAL=ON"]);
+        assert_eq!(cmake_backend_flags(BuildBackend::Cuda), vec!["-DGGML_CUDA=ON"]);
+        assert_eq!(cmake_backend_flags(BuildBackend::OpenBlas), vec!["-DGGML_BLAS=ON", "-DGGML_BLAS_VENDOR=OpenBLAS"]);
+    }
+    
+    #[test]
+    fn make_backend_env_matches_cmake_backend() {
+        assert_eq!(make_backend_env(BuildBackend::Cpu), vec![("GGML_NO_METAL", "1")]);
+        assert_eq!(make_backend_env(BuildBackend::Metal), vec![("GGML_METAL", "1")]);
+        assert_eq!(make_backend_env(BuildBackend::Cuda), vec![("GGML_CUDA", "1")]);
+        assert_eq!(make_backend_env(BuildBackend::OpenBlas), vec![("GGML_OPENBLAS", "1"), ("GGML_BLAS", "1")]);
+    }
+    
+    #[test]
+    fn format_eta_uses_largest_units() {
+        assert_eq!(format_eta(3725.0), "1h2m");
+        assert_eq!(format_eta(185.4), "3m5s");
+        assert_eq!(format_eta(4.6), "5s");
+        assert_eq!(format_eta(-3.0), "0s");
+    }
+    
+    #[test]
+    fn script_checksums_accept_common_layouts() {
+        let hash = "a".repeat(64);
+        let upper = "B".repeat(64);
+        let script = format!(
+            "models=\"tiny\"\ntiny  {hash}\n\"base.en\": \"{upper}\",\nlarge-v3-turbo={hash}\nsmall {short}\n",
+            hash = hash,
+            upper = upper,
+            short = "c".repeat(40),
+        );
+        
+        let checksums = parse_script_checksums(&script);
+        assert_eq!(checksums.get("tiny"), Some(&hash));
+        assert_eq!(checksums.get("base.en"), Some(&"b".repeat(64)));
+        assert_eq!(checksums.get("large-v3-turbo"), Some(&hash));
+        // 64자리가 아닌 해시(SHA1 등)는 무시
+        assert!(!checksums.contains_key("small"));
+    }
+    
+    #[test]
+    fn download_guard_tracks_active_downloads() {
+        let installer = WhisperInstaller::new(PathBuf::from("whisper.cpp"), PathBuf::from("models"));
+        assert!(!installer.has_active_downloads());
+        
+        let outer = installer.track_download();
+        let inner = installer.track_download();
+        drop(inner);
+        assert!(installer.has_active_downloads());
+        drop(outer);
+        assert!(!installer.has_active_downloads());
+    }
+}
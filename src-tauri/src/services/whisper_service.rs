@@ -1,22 +1,42 @@
 use std::path::PathBuf;
 use tauri::Manager;
+use sysinfo::SystemExt;
 use crate::models::*;
-use crate::services::whisper_installer::WhisperInstaller;
-use crate::services::history_service::HistoryService;
+use crate::services::whisper_installer::{sha256_file, WhisperInstaller, WHISPER_CPP_REPO_URL, WHISPER_CPP_PINNED_REF};
+use crate::services::history_service::{HistoryService, HISTORY_LOG_FILE};
+use crate::services::settings_service::SettingsService;
+use crate::utils::{audio_track_args, is_executable, is_media_file, probe_audio_stream_count, probe_media_duration, render_filename_template, MediaTools};
+use crate::utils::ass::speaker_segments_to_ass;
+use crate::utils::export_formats::{export_format_rank, whisper_output_formats, EXPORT_FORMATS};
+use crate::utils::subtitle::{
+    convert_words_to_srt, extract_translatable_lines, format_chapter_timestamp, format_vtt_timestamp, label_speakers, parse_subtitle_segments,
+    parse_subtitle_timestamp, reflow_segments, reinject_translated_lines, segments_to_plain_text, segments_to_srt, segments_to_vtt,
+};
+use crate::utils::whisper_json::parse_whisper_json_words;
 
 pub struct WhisperService {
     pub whisper_repo_path: PathBuf,
     pub whisper_binary_path: PathBuf,
     pub models_path: PathBuf,
-    installer: WhisperInstaller,
+    installer: std::sync::Arc<WhisperInstaller>, // 모델 복구 시 서비스 잠금 없이 다운로드하도록 공유
     history_service: HistoryService,
+    discovered_binary: std::sync::Mutex<Option<PathBuf>>, // 재귀 탐색으로 찾은 바이너리 경로 캐시
+    custom_binary_probe: std::sync::Mutex<Option<CustomBinaryProbe>>, // 사용자 지정 바이너리의 --help 확인 결과 캐시
+    active_transcriptions: ActiveTranscriptions,           // 실행 중인 변환의 진행 상황 (history_id 기준)
+    transcription_queue: std::sync::Arc<TranscriptionQueue>, // enqueue_transcription으로 추가된 대기 중인 변환
+    scheduled_jobs_file: PathBuf,                          // 예약된 변환 목록 (재시작 후에도 유지)
+    resource_sampler: ResourceSampler,                     // CPU 사용률은 이전 측정과의 차이로 계산되므로 계속 재사용
+    active_appends: ActiveAppends,                         // transcribe_append가 진행 중인 history_id (중복 실행 방지)
+    media_tools: MediaTools,                               // 설정의 ffmpeg/ffprobe 경로 (set_media_tools로 갱신)
 }
 
 impl WhisperService {
     pub fn new() -> Self {
-        let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let whisper_dir = home_dir.join(".whisper-gui");
-        
+        Self::with_data_dir(AppSettings::default_data_dir())
+    }
+    
+    /// 지정한 데이터 디렉토리를 기준으로 서비스를 생성합니다
+    pub fn with_data_dir(whisper_dir: PathBuf) -> Self {
         let whisper_repo_path = whisper_dir.join("whisper.cpp");
         let whisper_binary_path = whisper_repo_path.join("build").join("bin").join("main");
         let models_path = whisper_dir.join("models");
@@ -25,21 +45,95 @@ impl WhisperService {
             whisper_repo_path: whisper_repo_path.clone(),
             whisper_binary_path,
             models_path: models_path.clone(),
-            installer: WhisperInstaller::new(whisper_repo_path, models_path),
-            history_service: HistoryService::new(),
+            installer: std::sync::Arc::new(WhisperInstaller::new(whisper_repo_path, models_path)),
+            history_service: HistoryService::with_data_dir(whisper_dir.clone()),
+            discovered_binary: std::sync::Mutex::new(None),
+            custom_binary_probe: std::sync::Mutex::new(None),
+            active_transcriptions: Default::default(),
+            transcription_queue: Default::default(),
+            scheduled_jobs_file: whisper_dir.join(SCHEDULED_JOBS_FILE),
+            resource_sampler: std::sync::Arc::new(std::sync::Mutex::new(sysinfo::System::new())),
+            active_appends: Default::default(),
+            media_tools: MediaTools::from_settings(&SettingsService::new().load()),
         }
     }
 
+    /// ffmpeg/ffprobe 경로 설정이 바뀌면 호출합니다 (내부 히스토리 서비스에도 반영)
+    pub fn set_media_tools(&mut self, media_tools: MediaTools) {
+        self.history_service.set_media_tools(media_tools.clone());
+        self.media_tools = media_tools;
+    }
+    
+    /// 실행 중이거나 대기열에 있는 변환이 있는지 확인합니다 (데이터 디렉토리 이동 전 확인용)
+    pub fn has_pending_transcriptions(&self) -> bool {
+        !self.active_transcriptions.lock().unwrap().is_empty()
+            || !self.transcription_queue.jobs.lock().unwrap().is_empty()
+            || !self.transcription_queue.starting.lock().unwrap().is_empty()
+            || !self.active_appends.lock().unwrap().is_empty()
+    }
+    
+    /// 모델 다운로드나 복구가 진행 중인지 확인합니다 (데이터 디렉토리 이동 전 확인용)
+    ///
+    /// 복구는 서비스 잠금 없이 다운로드하므로 잠금만으로는 막을 수 없습니다.
+    pub fn has_active_model_downloads(&self) -> bool {
+        self.installer.has_active_downloads()
+    }
+    
+    /// 데이터 디렉토리를 옮긴 뒤 경로를 새 위치로 바꿉니다
+    ///
+    /// 서비스를 새로 만들지 않으므로 대기열 처리 태스크와 캐시는 그대로 유지됩니다.
+    pub fn set_data_dir(&mut self, whisper_dir: PathBuf) {
+        self.whisper_repo_path = whisper_dir.join("whisper.cpp");
+        self.whisper_binary_path = self.whisper_repo_path.join("build").join("bin").join("main");
+        self.models_path = whisper_dir.join("models");
+        // 진행 중인 복구 다운로드가 기존 설치기를 쓰고 있을 수 있으므로 바꾸지 않고 새로 만듦
+        self.installer = std::sync::Arc::new(WhisperInstaller::new(self.whisper_repo_path.clone(), self.models_path.clone()));
+        self.history_service.set_data_dir(whisper_dir.clone());
+        self.scheduled_jobs_file = whisper_dir.join(SCHEDULED_JOBS_FILE);
+        *self.discovered_binary.lock().unwrap() = None;
+    }
+
     pub async fn check_whisper_installation(&self) -> anyhow::Result<bool> {
         // 빌드된 바이너리 위치 확인 (여러 가능한 위치 체크)
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
+        Ok(self.resolve_whisper_binary().is_some())
+    }
+
+    /// 설치 여부와 함께 실제로 사용할 바이너리, 사용자 지정 바이너리 사용 여부를 반환합니다
+    pub async fn get_installation_status(&self) -> anyhow::Result<InstallationStatus> {
+        let custom = self.check_custom_binary();
+        let binary_path = self.resolve_whisper_binary();
         
-        Ok(main_binary.exists() || fallback_binary.exists())
+        Ok(InstallationStatus {
+            installed: binary_path.is_some(),
+            binary_path: binary_path.map(|path| path.to_string_lossy().to_string()),
+            custom_binary_path: SettingsService::new().load().custom_binary_path
+                .map(|path| path.to_string_lossy().to_string()),
+            using_custom_binary: matches!(custom, Some(Ok(_))),
+            custom_binary_error: custom.and_then(|result| result.err()),
+            installed_version: self.installer.get_installed_version().await,
+        })
     }
 
-    pub async fn setup_whisper(&self, app_handle: Option<tauri::AppHandle>) -> anyhow::Result<String> {
-        self.installer.setup_whisper(app_handle).await
+    /// `backend`를 지정하면 설정에 저장해 이후 업데이트/재빌드에도 같은 백엔드를 사용합니다
+    pub async fn setup_whisper(
+        &self,
+        app_handle: Option<tauri::AppHandle>,
+        version: Option<String>,
+        backend: Option<BuildBackend>,
+    ) -> anyhow::Result<String> {
+        if let Some(backend) = backend {
+            SettingsService::new().set_build_backend(Some(backend)).await?;
+        }
+        self.installer.setup_whisper(app_handle, version).await
+    }
+    
+    pub async fn detect_gpu_backends(&self) -> GpuBackendDetection {
+        self.installer.detect_gpu_backends().await
+    }
+    
+    /// 설치된 whisper.cpp 버전 (git describe --tags)
+    pub async fn get_installed_whisper_version(&self) -> Option<String> {
+        self.installer.describe_installed_version().await
     }
 
     pub async fn list_available_models(&self) -> anyhow::Result<Vec<String>> {
@@ -86,6 +180,41 @@ impl WhisperService {
         Ok(fallback_models)
     }
 
+    /// 모델 목록의 출처인 다운로드 스크립트가 존재하고, 읽을 수 있고, 모델 목록이 파싱되는지 확인합니다
+    pub async fn check_download_script(&self) -> DownloadScriptStatus {
+        let script_path = self.whisper_repo_path.join("models").join("download-ggml-model.sh");
+        let mut status = DownloadScriptStatus {
+            path: script_path.clone(),
+            exists: script_path.is_file(),
+            readable: false,
+            model_count: 0,
+            error: None,
+        };
+        
+        if !status.exists {
+            status.error = Some("Download script not found".to_string());
+            return status;
+        }
+        
+        if let Err(e) = tokio::fs::read_to_string(&script_path).await {
+            status.error = Some(format!("Failed to read download script: {}", e));
+            return status;
+        }
+        status.readable = true;
+        
+        match self.parse_models_from_script(&script_path).await {
+            Ok(models) => status.model_count = models.len(),
+            Err(e) => status.error = Some(e.to_string()),
+        }
+        status
+    }
+    
+    /// 다운로드 스크립트만 다시 받아온 뒤 상태를 다시 확인합니다
+    pub async fn repair_download_script(&self) -> anyhow::Result<DownloadScriptStatus> {
+        self.installer.repair_download_script().await?;
+        Ok(self.check_download_script().await)
+    }
+
     async fn parse_models_from_script(&self, script_path: &std::path::Path) -> anyhow::Result<Vec<String>> {
         let content = tokio::fs::read_to_string(script_path).await?;
         let mut models = Vec::new();
@@ -192,116 +321,273 @@ impl WhisperService {
         self.installer.download_model(model_name).await
     }
 
-    pub async fn validate_model(&self, model_name: &str) -> anyhow::Result<bool> {
+    /// 받은 모델 파일의 SHA256을 계산해 기대값과 비교한 결과를 반환합니다
+    pub async fn verify_model_checksum(&self, model_name: &str) -> anyhow::Result<ModelChecksum> {
         let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
-        
         if !model_path.exists() {
-            return Ok(false);
+            return Err(anyhow::anyhow!("Model not found: {}", model_name));
         }
         
-        // 파일 크기 체크
-        let metadata = tokio::fs::metadata(&model_path).await?;
-        let file_size = metadata.len();
+        let expected = self.installer.expected_model_checksum(model_name).await;
+        let actual = sha256_file(&model_path).await?;
+        let matches = expected.as_ref().map(|expected| expected.eq_ignore_ascii_case(&actual));
         
-        // 예상 최소 파일 크기 (MB)
-        let min_expected_size = match model_name {
-            m if m.starts_with("tiny") => 39 * 1024 * 1024,      // ~39MB
-            m if m.starts_with("base") => 142 * 1024 * 1024,     // ~142MB  
-            m if m.starts_with("small") => 244 * 1024 * 1024,    // ~244MB
-            m if m.starts_with("medium") => 769 * 1024 * 1024,   // ~769MB
-            m if m.starts_with("large") => 1550 * 1024 * 1024,   // ~1550MB
-            _ => 10 * 1024 * 1024, // 기본 최소값 10MB
-        };
+        Ok(ModelChecksum {
+            model_name: model_name.to_string(),
+            expected,
+            actual,
+            matches,
+        })
+    }
+
+    /// 서비스 잠금 없이 모델을 검증할 수 있도록 필요한 경로와 설치기를 복사합니다
+    pub fn model_validator(&self) -> ModelValidator {
+        ModelValidator {
+            installer: self.installer.clone(),
+            models_path: self.models_path.clone(),
+            whisper_binary: self.resolve_whisper_binary(),
+        }
+    }
+
+    /// whisper.cpp의 quantize 도구로 받은 모델을 양자화해 ggml-<모델>-<형식>.bin을 만듭니다
+    ///
+    /// quantize 도구가 없으면 먼저 빌드하고, 만든 파일은 크기와 로딩 검사(validate_local)를 통과해야 남깁니다.
+    /// models 디렉토리에 생기므로 list_downloaded_models에 바로 나타납니다.
+    pub async fn quantize_model(
+        &self,
+        source_model: &str,
+        quant_type: &str,
+        app_handle: tauri::AppHandle,
+    ) -> anyhow::Result<String> {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        use std::process::Stdio;
         
-        if file_size < min_expected_size {
-            eprintln!("Model {} appears to be incomplete: {} bytes (expected >= {} bytes)", 
-                     model_name, file_size, min_expected_size);
-            return Ok(false);
+        if !QUANTIZE_TYPES.contains(&quant_type) {
+            return Err(anyhow::anyhow!("Unsupported quantization type: {} (expected one of {})", quant_type, QUANTIZE_TYPES.join(", ")));
+        }
+        if is_quantized_model_name(source_model) {
+            return Err(anyhow::anyhow!("Model {} is already quantized", source_model));
         }
         
-        // whisper.cpp로 모델 검증 시도 (간단한 헤더 체크)
-        if let Err(e) = self.test_model_loading(model_name).await {
-            eprintln!("Model {} failed validation test: {}", model_name, e);
-            return Ok(false);
+        let source_path = self.models_path.join(format!("ggml-{}.bin", source_model));
+        if !source_path.exists() {
+            return Err(anyhow::anyhow!("Model not found: {}", source_model));
+        }
+        let target_model = format!("{}-{}", source_model, quant_type);
+        let target_path = self.models_path.join(format!("ggml-{}.bin", target_model));
+        if target_path.exists() {
+            return Err(anyhow::anyhow!("Model already exists: {}", target_model));
         }
         
-        Ok(true)
+        let emit = |status: QuantizeStatus, message: Option<String>| {
+            app_handle.emit_all("quantize-progress", &QuantizeProgress {
+                source_model: source_model.to_string(),
+                target_model: target_model.clone(),
+                status,
+                message,
+            }).ok();
+        };
+        
+        let result = async {
+            let quantize_binary = match self.resolve_quantize_binary() {
+                Some(path) => path,
+                None => {
+                    emit(QuantizeStatus::Building, None);
+                    self.build_quantize_binary().await?
+                }
+            };
+            
+            emit(QuantizeStatus::Quantizing, None);
+            let mut child = tokio::process::Command::new(&quantize_binary)
+                .arg(&source_path)
+                .arg(&target_path)
+                .arg(quant_type)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            
+            // quantize는 텐서마다 한 줄씩 출력하므로 그대로 진행 메시지로 전달
+            let stdout = child.stdout.take().unwrap();
+            let app_handle_stdout = app_handle.clone();
+            let (source, target) = (source_model.to_string(), target_model.clone());
+            tokio::spawn(async move {
+                let mut reader = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = reader.next_line().await {
+                    app_handle_stdout.emit_all("quantize-progress", &QuantizeProgress {
+                        source_model: source.clone(),
+                        target_model: target.clone(),
+                        status: QuantizeStatus::Quantizing,
+                        message: Some(line),
+                    }).ok();
+                }
+            });
+            
+            let output = child.wait_with_output().await?;
+            if !output.status.success() {
+                return Err(anyhow::anyhow!(
+                    "quantize failed: {}", String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            
+            emit(QuantizeStatus::Validating, None);
+            if !self.model_validator().validate_local(&target_model).await? {
+                return Err(anyhow::anyhow!("Quantized model {} failed validation", target_model));
+            }
+            
+            Ok(())
+        }.await;
+        
+        match result {
+            Ok(()) => {
+                emit(QuantizeStatus::Completed, None);
+                Ok(target_model.clone())
+            }
+            Err(e) => {
+                tokio::fs::remove_file(&target_path).await.ok();
+                emit(QuantizeStatus::Failed, Some(e.to_string()));
+                Err(e)
+            }
+        }
     }
 
-    async fn test_model_loading(&self, model_name: &str) -> anyhow::Result<()> {
+    /// build/ 아래에서 quantize 도구를 찾습니다 (버전에 따라 이름이 다름)
+    fn resolve_quantize_binary(&self) -> Option<PathBuf> {
+        let build_dir = self.whisper_repo_path.join("build");
+        QUANTIZE_BINARY_NAMES.iter()
+            .find_map(|name| find_executable_recursive(&build_dir, name, BINARY_SEARCH_MAX_DEPTH))
+    }
+
+    /// 기존 CMake 빌드 디렉토리에 quantize 타깃만 추가로 빌드합니다
+    async fn build_quantize_binary(&self) -> anyhow::Result<PathBuf> {
         use tokio::process::Command as TokioCommand;
         
-        let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
+        if !self.whisper_repo_path.join("build").join("CMakeCache.txt").exists() {
+            let output = TokioCommand::new("cmake")
+                .args(["-B", "build", "-DCMAKE_BUILD_TYPE=Release"])
+                .current_dir(&self.whisper_repo_path)
+                .output()
+                .await?;
+            if !output.status.success() {
+                return Err(anyhow::anyhow!("CMake configure failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+            }
+        }
         
-        // whisper-cli 바이너리 찾기
-        let whisper_cli_binary = self.whisper_repo_path.join("build").join("bin").join("whisper-cli");
-        let fallback_cli_binary = self.whisper_repo_path.join("build").join("whisper-cli");
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
-        
-        let binary_path = if whisper_cli_binary.exists() {
-            &whisper_cli_binary
-        } else if fallback_cli_binary.exists() {
-            &fallback_cli_binary
-        } else if main_binary.exists() {
-            &main_binary
-        } else if fallback_binary.exists() {
-            &fallback_binary
-        } else {
-            return Err(anyhow::anyhow!("Whisper binary not found"));
-        };
+        let mut last_error = String::new();
+        for target in QUANTIZE_BINARY_NAMES {
+            let output = TokioCommand::new("cmake")
+                .args(["--build", "build", "--config", "Release", "--target", target])
+                .current_dir(&self.whisper_repo_path)
+                .output()
+                .await?;
+            if output.status.success() {
+                break;
+            }
+            last_error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        }
+        
+        self.resolve_quantize_binary()
+            .ok_or_else(|| anyhow::anyhow!("Failed to build quantize tool: {}", last_error))
+    }
 
-        // 모델 로딩 테스트 (매우 짧은 더미 파일로)
-        let output = TokioCommand::new(binary_path)
-            .args([
-                "-m", &model_path.to_string_lossy(),
-                "--help"  // 단순히 help만 표시하여 모델 로딩 확인
-            ])
-            .output()
-            .await?;
-            
-        // stderr에서 모델 로딩 에러 체크
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if stderr.contains("ERROR not all tensors loaded") || 
-           stderr.contains("failed to load model") ||
-           stderr.contains("failed to initialize whisper context") {
-            return Err(anyhow::anyhow!("Model validation failed: {}", stderr));
+    pub async fn repair_model(&self, model_name: &str) -> anyhow::Result<()> {
+        redownload_model(&self.installer, &self.models_path, model_name).await?;
+        self.model_validator().verify_repaired(model_name).await
+    }
+
+    pub async fn get_download_capabilities(&self) -> DownloadCapabilities {
+        self.installer.get_download_capabilities().await
+    }
+
+    pub async fn get_model_download_size(&self, model_name: &str) -> anyhow::Result<ModelDownloadSize> {
+        self.installer.get_model_download_size(model_name).await
+    }
+
+    /// 설정의 verify_after_download가 켜져 있으면 다운로드 후 샘플 변환 결과를 `download-verified` 이벤트로 알립니다
+    pub async fn download_model_with_progress(&self, model_name: &str, app_handle: tauri::AppHandle) -> anyhow::Result<()> {
+        self.installer.download_model_with_progress(model_name, app_handle.clone()).await?;
+        
+        if SettingsService::new().load().verify_after_download {
+            let verification = self.verify_model_with_sample(model_name).await;
+            app_handle.emit_all("download-verified", &verification).ok();
         }
         
         Ok(())
     }
 
-    pub async fn repair_model(&self, model_name: &str) -> anyhow::Result<()> {
-        eprintln!("Attempting to repair model: {}", model_name);
+    /// whisper.cpp 저장소에 포함된 샘플 오디오(samples/jfk.wav)를 해당 모델로 실제 변환해 봅니다
+    ///
+    /// 파일은 온전하지만 설치된 바이너리와 버전이 맞지 않아 로드되지 않는 모델을 잡기 위한 검사입니다.
+    pub async fn verify_model_with_sample(&self, model_name: &str) -> ModelVerification {
+        let error = self.run_sample_transcription(model_name).await.err().map(|e| e.to_string());
+        let repairable = match &error {
+            Some(_) => self.list_available_models().await
+                .map(|models| models.iter().any(|m| m == model_name))
+                .unwrap_or(false),
+            None => false,
+        };
         
-        // 기존 손상된 파일 삭제
+        ModelVerification {
+            model_name: model_name.to_string(),
+            passed: error.is_none(),
+            error,
+            repairable,
+        }
+    }
+
+    async fn run_sample_transcription(&self, model_name: &str) -> anyhow::Result<()> {
         let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
-        if model_path.exists() {
-            tokio::fs::remove_file(&model_path).await?;
-            eprintln!("Removed corrupted model file: {:?}", model_path);
+        if !model_path.exists() {
+            return Err(anyhow::anyhow!("Model not found: {}", model_name));
+        }
+        
+        let sample_path = self.whisper_repo_path.join(VERIFICATION_SAMPLE);
+        if !sample_path.exists() {
+            return Err(anyhow::anyhow!("Sample audio not found: {}", sample_path.display()));
         }
         
-        // 모델 재다운로드
-        self.installer.download_model(model_name).await?;
+        let binary_path = self.resolve_whisper_binary()
+            .ok_or_else(|| anyhow::anyhow!("Whisper binary not found"))?;
         
-        // 재다운로드 후 검증
-        if !self.validate_model(model_name).await? {
-            return Err(anyhow::anyhow!("Model repair failed - downloaded model is still invalid"));
+        let output = tokio::process::Command::new(&binary_path)
+            .arg("-m").arg(&model_path)
+            .arg("-f").arg(&sample_path)
+            .arg("--no-timestamps")
+            .output()
+            .await?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+            return Err(anyhow::anyhow!("Sample transcription failed: {}", last_line.trim()));
+        }
+        if String::from_utf8_lossy(&output.stdout).trim().is_empty() {
+            return Err(anyhow::anyhow!("Sample transcription produced no text"));
         }
         
-        eprintln!("Model {} successfully repaired", model_name);
         Ok(())
     }
 
-    pub async fn download_model_with_progress(&self, model_name: &str, app_handle: tauri::AppHandle) -> anyhow::Result<()> {
-        self.installer.download_model_with_progress(model_name, app_handle).await
-    }
-
     pub async fn is_model_downloaded(&self, model_name: &str) -> bool {
         let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
         model_path.exists()
     }
 
+    /// 모델 이름(models 디렉토리의 ggml-<name>.bin) 또는 파일 경로로 모델 헤더를 읽습니다
+    pub async fn read_model_header(&self, model_name_or_path: &str) -> anyhow::Result<ModelHeader> {
+        let candidate = PathBuf::from(model_name_or_path);
+        let model_path = if candidate.is_file() {
+            candidate
+        } else {
+            self.models_path.join(format!("ggml-{}.bin", model_name_or_path))
+        };
+        
+        if !model_path.exists() {
+            return Err(anyhow::anyhow!("Model not found: {}", model_name_or_path));
+        }
+        
+        tokio::task::spawn_blocking(move || crate::utils::model_header::read_model_header(&model_path)).await?
+    }
+
     pub async fn delete_model(&self, model_name: &str) -> anyhow::Result<()> {
         let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
         
@@ -329,26 +615,10 @@ impl WhisperService {
             return Err(anyhow::anyhow!("Model not found: {}", model_name));
         }
 
-        // whisper-cli 바이너리 찾기 (최신 whisper.cpp에서 권장)
-        let whisper_cli_binary = self.whisper_repo_path.join("build").join("bin").join("whisper-cli");
-        let fallback_cli_binary = self.whisper_repo_path.join("build").join("whisper-cli");
-        // 백워드 호환성을 위한 main 바이너리
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
-        
-        let binary_path = if whisper_cli_binary.exists() {
-            &whisper_cli_binary
-        } else if fallback_cli_binary.exists() {
-            &fallback_cli_binary
-        } else if main_binary.exists() {
-            &main_binary
-        } else if fallback_binary.exists() {
-            &fallback_binary
-        } else {
-            return Err(anyhow::anyhow!("Whisper binary not found"));
-        };
+        let binary_path = self.resolve_whisper_binary()
+            .ok_or_else(|| anyhow::anyhow!("Whisper binary not found"))?;
 
-        let mut cmd = TokioCommand::new(binary_path)
+        let mut cmd = TokioCommand::new(&binary_path)
             .args([
                 "-m", &model_path.to_string_lossy(),
                 "-f", file_path,
@@ -361,12 +631,13 @@ impl WhisperService {
         let stdout = cmd.stdout.take().unwrap();
         let stderr = cmd.stderr.take().unwrap();
 
+        let total_duration = probe_media_duration(&self.media_tools, std::path::Path::new(file_path)).await;
         let app_handle_clone = app_handle.clone();
         tokio::spawn(async move {
             let mut reader = BufReader::new(stdout).lines();
             while let Ok(Some(line)) = reader.next_line().await {
                 app_handle_clone.emit_all("transcription-log", &line).ok();
-                if let Some(progress) = parse_whisper_output_line(&line) {
+                if let Some(progress) = parse_whisper_output_line(&line, total_duration) {
                     app_handle_clone.emit_all("transcription-progress", &progress).ok();
                 }
             }
@@ -411,40 +682,304 @@ impl WhisperService {
         }
     }
 
-    pub async fn export_to_srt(&self, transcription: &str, output_path: &str) -> anyhow::Result<String> {
-        let srt_content = convert_to_srt(transcription);
+    /// `layout`이 있으면 줄 길이/줄 수 제한에 맞게 큐를 다시 나눕니다
+    pub async fn export_to_srt(
+        &self,
+        transcription: &str,
+        output_path: &str,
+        layout: Option<&SubtitleLayout>,
+    ) -> anyhow::Result<String> {
+        let srt_content = match layout {
+            Some(layout) => segments_to_srt(&reflow_segments(&segments_or_estimated(transcription), layout)),
+            None => convert_to_srt(transcription),
+        };
         tokio::fs::write(output_path, srt_content).await?;
         Ok(format!("SRT exported to: {}", output_path))
     }
 
+    pub async fn export_to_vtt(&self, transcription: &str, output_path: &str) -> anyhow::Result<String> {
+        let vtt_content = convert_to_vtt(transcription);
+        tokio::fs::write(output_path, vtt_content).await?;
+        Ok(format!("VTT exported to: {}", output_path))
+    }
+
+    pub async fn export_to_styled_vtt(
+        &self,
+        transcription: &str,
+        output_path: &str,
+        style: &VttStyleOptions,
+        layout: Option<&SubtitleLayout>,
+    ) -> anyhow::Result<String> {
+        let mut segments = segments_or_estimated(transcription);
+        if let Some(layout) = layout {
+            segments = reflow_segments(&segments, layout);
+        }
+        let vtt_content = segments_to_styled_vtt(&segments, style);
+        tokio::fs::write(output_path, vtt_content).await?;
+        Ok(format!("VTT exported to: {}", output_path))
+    }
+
+    pub async fn export_to_markdown(
+        &self,
+        transcription: &str,
+        output_path: &str,
+        base_media_url: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let markdown_content = convert_to_markdown(transcription, base_media_url);
+        tokio::fs::write(output_path, markdown_content).await?;
+        Ok(format!("Markdown exported to: {}", output_path))
+    }
+
+    pub async fn export_to_ass(&self, transcription: &str, output_path: &str) -> anyhow::Result<String> {
+        let ass_content = convert_to_ass(transcription);
+        tokio::fs::write(output_path, ass_content).await?;
+        Ok(format!("ASS exported to: {}", output_path))
+    }
+
     pub async fn export_to_fcpxml(&self, transcription: &str, output_path: &str) -> anyhow::Result<String> {
         let fcpxml_content = convert_to_fcpxml(transcription);
         tokio::fs::write(output_path, fcpxml_content).await?;
         Ok(format!("FCPXML exported to: {}", output_path))
     }
 
+    /// 수정된 원본 결과(txt/srt/vtt 중 가장 최근에 바뀐 파일)에서 요청한 형식들을 다시 만들어 교체합니다
+    ///
+    /// txt가 원본이면 줄 수가 기존 자막 큐 수와 같을 때 기존 타이밍을 유지하고,
+    /// 다르면 SRT 내보내기와 같은 5초 간격으로 추정합니다.
+    pub async fn regenerate_exports(&self, history_id: &str, formats: &[String]) -> anyhow::Result<TranscriptionHistory> {
+        let history = self.history_service.get_history(history_id).await?;
+        
+        let derivable = |format: &String| EXPORT_FORMATS.iter().any(|spec| spec.id == format && spec.derivable);
+        if let Some(format) = formats.iter().find(|f| !derivable(f)) {
+            return Err(anyhow::anyhow!("Unsupported export format: {}", format));
+        }
+        
+        let (source_format, segments) = regeneration_segments(&history).await?;
+        
+        // vtt 변환은 자막 원문을 입력으로 받으므로 세그먼트에서 만든 SRT를 기준으로 사용
+        let canonical = segments_to_srt(&segments);
+        let files_dir = self.history_service.get_history_directory(history_id).join("files");
+        let mut regenerated = Vec::new();
+        
+        for format in formats.iter().filter(|f| **f != source_format) {
+            let content = derive_export_content(format, &canonical, &segments)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported export format: {}", format))?;
+            let target_path = files_dir.join(format!("result.{}", format));
+            tokio::fs::write(&target_path, content).await?;
+            regenerated.push((target_path, format.clone()));
+        }
+        
+        self.history_service.replace_result_files(history_id, regenerated).await
+    }
+
+    /// 여러 히스토리의 한 형식을 대상 폴더로 한꺼번에 내보냅니다
+    ///
+    /// 파일 이름은 설정의 `export_filename_template`을 따르고, 해당 형식의 결과가 없는 항목은
+    /// txt/srt/vtt 결과에서 즉석으로 변환해 저장합니다 (히스토리에는 등록하지 않음).
+    /// 항목별 실패는 전체를 중단하지 않고 결과 목록에 기록됩니다.
+    pub async fn batch_export(
+        &self,
+        history_ids: &[String],
+        format: &str,
+        dest_dir: &std::path::Path,
+    ) -> anyhow::Result<Vec<BatchExportResult>> {
+        if !EXPORT_FORMATS.iter().any(|spec| spec.id == format) {
+            return Err(anyhow::anyhow!("Unsupported export format: {}", format));
+        }
+        tokio::fs::create_dir_all(dest_dir).await?;
+        
+        let template = SettingsService::new().load().export_filename_template;
+        let mut used_names = std::collections::HashSet::new();
+        let mut results = Vec::new();
+        
+        for history_id in history_ids {
+            let result = match self.export_history_format(history_id, format, dest_dir, &template, &mut used_names).await {
+                Ok((output_path, derived)) => BatchExportResult {
+                    history_id: history_id.clone(),
+                    output_path: Some(output_path.to_string_lossy().to_string()),
+                    derived,
+                    error: None,
+                },
+                Err(e) => BatchExportResult {
+                    history_id: history_id.clone(),
+                    output_path: None,
+                    derived: false,
+                    error: Some(e.to_string()),
+                },
+            };
+            results.push(result);
+        }
+        
+        Ok(results)
+    }
+
+    /// 히스토리 하나를 내보냅니다 (저장 경로, 변환 여부)
+    async fn export_history_format(
+        &self,
+        history_id: &str,
+        format: &str,
+        dest_dir: &std::path::Path,
+        template: &str,
+        used_names: &mut std::collections::HashSet<String>,
+    ) -> anyhow::Result<(PathBuf, bool)> {
+        let history = self.history_service.get_history(history_id).await?;
+        
+        // 같은 이름이 겹치면 "-2", "-3"... 을 붙여 덮어쓰지 않도록 함
+        let stem = render_filename_template(template, &history);
+        let mut file_name = format!("{}.{}", stem, format);
+        let mut counter = 2;
+        while used_names.contains(&file_name) || dest_dir.join(&file_name).exists() {
+            file_name = format!("{}-{}.{}", stem, counter, format);
+            counter += 1;
+        }
+        used_names.insert(file_name.clone());
+        let target_path = dest_dir.join(file_name);
+        
+        let source_path = self.history_service.get_result_file_path(history_id, format);
+        if source_path.exists() {
+            tokio::fs::copy(&source_path, &target_path).await?;
+            return Ok((target_path, false));
+        }
+        
+        let content = derived_format_content(&history, format).await?;
+        tokio::fs::write(&target_path, content).await?;
+        
+        Ok((target_path, true))
+    }
+
+    /// 설정된 번역 명령으로 결과를 다른 언어로 번역해 result.<언어>.<형식>으로 등록합니다
+    ///
+    /// 큐 텍스트 라인만 번역 명령에 넘기고 큐 번호/타이밍 라인은 그대로 두므로 자막 타이밍이 유지됩니다.
+    pub async fn translate_result(
+        &self,
+        history_id: &str,
+        format: &str,
+        target_language: &str,
+    ) -> anyhow::Result<TranscriptionHistory> {
+        let template = SettingsService::new().load().translator_command
+            .ok_or_else(|| anyhow::anyhow!("No translator configured. Set a translator command in settings first"))?;
+        
+        let target_language = target_language.trim();
+        if target_language.is_empty() || !target_language.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(anyhow::anyhow!("Invalid target language: {:?}", target_language));
+        }
+        
+        let history = self.history_service.get_history(history_id).await?;
+        let result = history.results.iter()
+            .find(|r| r.format == format && r.language.is_none())
+            .ok_or_else(|| anyhow::anyhow!("Result file not found: {}", format))?;
+        
+        let content = tokio::fs::read_to_string(&result.file_path).await?;
+        let lines = extract_translatable_lines(&content);
+        if lines.is_empty() {
+            return Err(anyhow::anyhow!("No text to translate in {} result", format));
+        }
+        
+        let source_language = history.options_used.get("language")
+            .map(String::as_str)
+            .unwrap_or("auto");
+        let command = render_translator_command(&template, source_language, target_language);
+        
+        let mut input = lines.iter().map(|(_, text)| text.as_str()).collect::<Vec<_>>().join("\n");
+        input.push('\n');
+        let output = run_translator(&command, &input).await?;
+        
+        let translated_lines: Vec<&str> = output.lines().collect();
+        if translated_lines.len() != lines.len() {
+            return Err(anyhow::anyhow!(
+                "Translator returned {} lines for {} input lines", translated_lines.len(), lines.len()
+            ));
+        }
+        let translated: Vec<(usize, String)> = lines.iter()
+            .zip(translated_lines)
+            .map(|((index, _), text)| (*index, text.trim().to_string()))
+            .collect();
+        
+        let output_path = self.history_service.get_history_directory(history_id)
+            .join("files")
+            .join(format!("result.{}.{}", target_language, format));
+        tokio::fs::write(&output_path, reinject_translated_lines(&content, &translated)).await?;
+        
+        self.history_service.register_translated_result(history_id, output_path, format, target_language).await
+    }
+    
+    /// 요청한 형식들(없는 형식은 다른 결과에서 변환)과 manifest.json을 담은 zip 납품 패키지를 만듭니다
+    pub async fn export_delivery_package(
+        &self,
+        history_id: &str,
+        formats: &[String],
+        output_path: &std::path::Path,
+    ) -> anyhow::Result<DeliveryPackage> {
+        if formats.is_empty() {
+            return Err(anyhow::anyhow!("No formats requested for the delivery package"));
+        }
+        if let Some(unknown) = formats.iter().find(|format| !EXPORT_FORMATS.iter().any(|spec| spec.id == format.as_str())) {
+            return Err(anyhow::anyhow!("Unsupported export format: {}", unknown));
+        }
+        
+        let history = self.history_service.get_history(history_id).await?;
+        let stem = render_filename_template(&SettingsService::new().load().export_filename_template, &history);
+        
+        let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+        let mut files = Vec::new();
+        let mut derived_formats = Vec::new();
+        for format in formats {
+            let file_name = format!("{}.{}", stem, format);
+            if entries.iter().any(|(name, _)| *name == file_name) {
+                continue;
+            }
+            
+            let source_path = self.history_service.get_result_file_path(history_id, format);
+            let (content, derived) = if source_path.exists() {
+                (tokio::fs::read(&source_path).await?, false)
+            } else {
+                (derived_format_content(&history, format).await?.into_bytes(), true)
+            };
+            if derived {
+                derived_formats.push(format.clone());
+            }
+            
+            files.push(serde_json::json!({
+                "format": format,
+                "file_name": file_name,
+                "derived": derived,
+            }));
+            entries.push((file_name, content));
+        }
+        
+        let manifest = serde_json::json!({
+            "history_id": history.id,
+            "original_file_name": history.original_file_name,
+            "original_file_path": history.original_file_path,
+            "model_used": history.model_used,
+            "options_used": history.options_used,
+            "whisper_commit": history.whisper_commit,
+            "created_at": history.created_at,
+            "completed_at": history.completed_at,
+            "duration_seconds": history.duration_seconds,
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+            "files": files,
+        });
+        entries.push((DELIVERY_MANIFEST_FILE.to_string(), serde_json::to_vec_pretty(&manifest)?));
+        
+        let included_formats = formats.to_vec();
+        let zip_path = output_path.to_path_buf();
+        tokio::task::spawn_blocking(move || write_zip_archive(&zip_path, &entries)).await??;
+        
+        Ok(DeliveryPackage {
+            output_path: output_path.to_string_lossy().to_string(),
+            included_formats,
+            derived_formats,
+        })
+    }
+
     pub async fn get_whisper_options(&self) -> anyhow::Result<WhisperOptions> {
         use tokio::process::Command as TokioCommand;
         
-        // whisper-cli 바이너리 찾기 (최신 whisper.cpp에서 권장)
-        let whisper_cli_binary = self.whisper_repo_path.join("build").join("bin").join("whisper-cli");
-        let fallback_cli_binary = self.whisper_repo_path.join("build").join("whisper-cli");
-        // 백워드 호환성을 위한 main 바이너리
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
-        
-        let binary_path = if whisper_cli_binary.exists() {
-            Some(&whisper_cli_binary)
-        } else if fallback_cli_binary.exists() {
-            Some(&fallback_cli_binary)
-        } else if main_binary.exists() {
-            Some(&main_binary)
-        } else if fallback_binary.exists() {
-            Some(&fallback_binary)
-        } else {
+        let binary_path = self.resolve_whisper_binary();
+        if binary_path.is_none() {
             eprintln!("Whisper binary not found, using default options");
-            None
-        };
+        }
 
         if let Some(binary) = binary_path {
             eprintln!("Attempting to get whisper options from: {}", binary.display());
@@ -482,23 +1017,277 @@ impl WhisperService {
         config: &WhisperConfig,
         app_handle: tauri::AppHandle
     ) -> anyhow::Result<String> {
-        use tokio::process::Command as TokioCommand;
-        use tokio::io::{AsyncBufReadExt, BufReader};
-        use std::process::Stdio;
+        let (history_id, config) = self.create_transcription_entry(config).await?;
+        self.transcription_runner().run(history_id, config, app_handle).await
+    }
+    
+    /// 변환을 바로 시작하지 않고 대기열에 추가합니다 (히스토리는 Queued 상태로 생성)
+    ///
+    /// 실행 중인 변환 수가 설정의 max_concurrent보다 적으면 대기열 처리 태스크가 곧바로 시작합니다 (pause_queue로 멈춘 동안은 대기).
+    pub async fn enqueue_transcription(&self, config: &WhisperConfig) -> anyhow::Result<String> {
+        let (history_id, config) = self.create_transcription_entry(config).await?;
+        self.history_service.update_history_status(&history_id, TranscriptionStatus::Queued).await?;
         
-        let model_path = self.models_path.join(format!("ggml-{}.bin", config.model));
+        self.transcription_queue.jobs.lock().unwrap().push_back(QueuedTranscription {
+            history_id: history_id.clone(),
+            config,
+        });
+        self.transcription_queue.changed.notify_one();
         
-        if !model_path.exists() {
-            return Err(anyhow::anyhow!("Model not found: {}", config.model));
+        Ok(history_id)
+    }
+    
+    /// 폴더 안의 오디오/동영상 파일(하위 폴더 제외)을 이름 순으로 모두 대기열에 넣고 히스토리 ID를 반환합니다
+    ///
+    /// 파일 하나가 실패해도 해당 히스토리만 실패로 표시되고 나머지는 계속 진행되며,
+    /// 모든 파일이 끝날 때까지 `batch-progress` 이벤트로 진행 상황을 알립니다.
+    pub async fn start_batch_transcription(
+        &self,
+        dir_path: &str,
+        config: &WhisperConfig,
+        app_handle: tauri::AppHandle,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut files = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir_path).await
+            .map_err(|e| anyhow::anyhow!("Failed to read directory {}: {}", dir_path, e))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_file() && is_media_file(&path.to_string_lossy()) {
+                files.push(path);
+            }
         }
-
-        // 히스토리 항목 생성
-        let input_path = PathBuf::from(&config.input_file);
-        let original_file_name = input_path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        files.sort();
+        
+        if files.is_empty() {
+            return Err(anyhow::anyhow!("No audio or video files found in {}", dir_path));
+        }
+        
+        // 대기열이 동시에 실행하는 수만큼만 자원을 쓰므로 그 수로 제한을 확인
+        let max_concurrent = SettingsService::new().load().max_concurrent.max(1);
+        let limit_check = crate::utils::resource_limits::check_batch_limits(files.len().min(max_concurrent));
+        if !limit_check.within_limits {
+            return Err(anyhow::anyhow!("Batch exceeds resource limits: {}", limit_check.warnings.join("; ")));
+        }
+        
+        let mut batch = Vec::new();
+        for file in files {
+            let file_config = WhisperConfig {
+                input_file: file.to_string_lossy().to_string(),
+                ..config.clone()
+            };
+            let file_name = file.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            
+            match self.enqueue_transcription(&file_config).await {
+                Ok(history_id) => batch.push((history_id, file_name)),
+                Err(e) => {
+                    eprintln!("Failed to enqueue {}: {}", file.display(), e);
+                    app_handle.emit_all("transcription-error", &format!("{}: {}", file_name, e)).ok();
+                }
+            }
+        }
+        
+        if batch.is_empty() {
+            return Err(anyhow::anyhow!("Failed to enqueue any file in {}", dir_path));
+        }
+        
+        let history_ids = batch.iter().map(|(history_id, _)| history_id.clone()).collect();
+        spawn_batch_monitor(
+            self.history_service.clone(),
+            self.active_transcriptions.clone(),
+            self.transcription_queue.clone(),
+            batch,
+            app_handle,
+        );
+        
+        Ok(history_ids)
+    }
+    
+    /// 실행 중인 변환(시작 순)과 대기 중인 변환(대기열 순)을 순서대로 반환합니다
+    pub fn get_queue(&self) -> Vec<QueueEntry> {
+        let mut running: Vec<(String, std::time::Instant)> = self.active_transcriptions.lock().unwrap()
+            .iter()
+            .map(|(history_id, active)| (history_id.clone(), active.started))
+            .collect();
+        running.sort_by_key(|(_, started)| *started);
+        // 입력을 준비 중인 항목은 이미 Running이지만 프로세스는 가장 나중에 시작됨
+        let starting = self.transcription_queue.starting.lock().unwrap().clone();
+        
+        let queued: Vec<String> = self.transcription_queue.jobs.lock().unwrap()
+            .iter()
+            .map(|job| job.history_id.clone())
+            .collect();
+        
+        running.into_iter()
+            .map(|(history_id, _)| history_id)
+            .chain(starting)
+            .map(|history_id| (history_id, TranscriptionStatus::Running))
+            .chain(queued.into_iter().map(|history_id| (history_id, TranscriptionStatus::Queued)))
+            .enumerate()
+            .map(|(i, (history_id, status))| QueueEntry { history_id, position: i + 1, status })
+            .collect()
+    }
+    
+    /// 대기열에서 새 변환이 시작되지 않도록 멈추고 `queue-status` 이벤트를 보냅니다
+    ///
+    /// 대기열은 그대로 유지되고, 이미 실행 중인 변환은 끝까지 진행됩니다.
+    pub fn pause_queue(&self, app_handle: &tauri::AppHandle) -> QueueStatus {
+        self.transcription_queue.paused.store(true, std::sync::atomic::Ordering::SeqCst);
+        self.emit_queue_status(app_handle)
+    }
+    
+    /// 멈춘 대기열을 다시 시작하고 `queue-status` 이벤트를 보냅니다 (빈 자리가 있으면 바로 다음 변환을 시작)
+    pub fn resume_queue(&self, app_handle: &tauri::AppHandle) -> QueueStatus {
+        self.transcription_queue.paused.store(false, std::sync::atomic::Ordering::SeqCst);
+        self.transcription_queue.changed.notify_one();
+        self.emit_queue_status(app_handle)
+    }
+    
+    pub fn get_queue_status(&self) -> QueueStatus {
+        QueueStatus {
+            paused: self.transcription_queue.paused.load(std::sync::atomic::Ordering::SeqCst),
+            queued: self.transcription_queue.jobs.lock().unwrap().len(),
+            running: self.active_transcriptions.lock().unwrap().len()
+                + self.transcription_queue.starting.lock().unwrap().len(),
+        }
+    }
+    
+    fn emit_queue_status(&self, app_handle: &tauri::AppHandle) -> QueueStatus {
+        let status = self.get_queue_status();
+        app_handle.emit_all("queue-status", &status).ok();
+        status
+    }
+    
+    /// 지정한 시각(RFC 3339)에 대기열에 넣을 변환을 예약합니다
+    pub async fn schedule_transcription(&self, config: &WhisperConfig, run_at: &str) -> anyhow::Result<ScheduledTranscription> {
+        chrono::DateTime::parse_from_rfc3339(run_at)
+            .map_err(|e| anyhow::anyhow!("Invalid run_at timestamp '{}': {}", run_at, e))?;
+        
+        let job = ScheduledTranscription {
+            id: uuid::Uuid::new_v4().to_string(),
+            config: config.clone(),
+            run_at: run_at.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        };
+        
+        let mut jobs = self.load_scheduled_jobs().await?;
+        jobs.push(job.clone());
+        self.save_scheduled_jobs(&jobs).await?;
+        
+        Ok(job)
+    }
+    
+    /// 예약된 변환을 실행 시각 순으로 반환합니다
+    pub async fn list_scheduled(&self) -> anyhow::Result<Vec<ScheduledTranscription>> {
+        let mut jobs = self.load_scheduled_jobs().await?;
+        // 시각을 알 수 없는 예약은 목록 끝으로
+        jobs.sort_by_key(|job| {
+            let time = scheduled_time(&job.run_at);
+            (time.is_none(), time)
+        });
+        Ok(jobs)
+    }
+    
+    pub async fn cancel_scheduled(&self, id: &str) -> anyhow::Result<()> {
+        let mut jobs = self.load_scheduled_jobs().await?;
+        let before = jobs.len();
+        jobs.retain(|job| job.id != id);
+        if jobs.len() == before {
+            return Err(anyhow::anyhow!("Scheduled transcription not found: {}", id));
+        }
+        
+        self.save_scheduled_jobs(&jobs).await
+    }
+    
+    /// 실행 시각이 지난 예약을 목록에서 빼고 대기열에 넣습니다 (꺼져 있던 동안 지난 예약도 포함)
+    async fn dispatch_due_scheduled(&self, app_handle: &tauri::AppHandle) -> anyhow::Result<()> {
+        let now = chrono::Utc::now();
+        let (due, pending): (Vec<_>, Vec<_>) = self.load_scheduled_jobs().await?
+            .into_iter()
+            .partition(|job| is_scheduled_due(&job.run_at, now));
+        if due.is_empty() {
+            return Ok(());
+        }
+        
+        // 같은 예약이 두 번 실행되지 않도록 대기열에 넣기 전에 먼저 저장
+        self.save_scheduled_jobs(&pending).await?;
+        for job in due {
+            if let Err(e) = self.enqueue_transcription(&job.config).await {
+                eprintln!("Failed to enqueue scheduled transcription {}: {}", job.id, e);
+                app_handle.emit_all("transcription-error", &e.to_string()).ok();
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn load_scheduled_jobs(&self) -> anyhow::Result<Vec<ScheduledTranscription>> {
+        if !self.scheduled_jobs_file.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let json_content = tokio::fs::read_to_string(&self.scheduled_jobs_file).await?;
+        Ok(serde_json::from_str(&json_content)?)
+    }
+    
+    async fn save_scheduled_jobs(&self, jobs: &[ScheduledTranscription]) -> anyhow::Result<()> {
+        if let Some(parent) = self.scheduled_jobs_file.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let json_content = serde_json::to_string_pretty(jobs)?;
+        tokio::fs::write(&self.scheduled_jobs_file, json_content).await?;
+        Ok(())
+    }
+    
+    /// 빈 자리가 있는 만큼 대기열 앞쪽부터 꺼냅니다
+    ///
+    /// 꺼낸 항목은 프로세스가 시작될 때까지 `starting`에 남아 동시 실행 수에 포함됩니다.
+    fn take_startable_jobs(&self) -> Vec<QueuedTranscription> {
+        let max_concurrent = SettingsService::new().load().max_concurrent.max(1);
+        
+        let mut taken = Vec::new();
+        if self.transcription_queue.paused.load(std::sync::atomic::Ordering::SeqCst) {
+            return taken;
+        }
+        loop {
+            let running = self.active_transcriptions.lock().unwrap().len()
+                + self.transcription_queue.starting.lock().unwrap().len();
+            if running >= max_concurrent {
+                break;
+            }
+            let job = match self.transcription_queue.jobs.lock().unwrap().pop_front() {
+                Some(job) => job,
+                None => break,
+            };
+            
+            self.transcription_queue.starting.lock().unwrap().push(job.history_id.clone());
+            taken.push(job);
+        }
+        taken
+    }
+    
+    /// 모델을 확인하고 기본 옵션을 적용한 뒤 히스토리 항목을 만듭니다 (실제로 사용할 설정을 함께 반환)
+    async fn create_transcription_entry(&self, config: &WhisperConfig) -> anyhow::Result<(String, WhisperConfig)> {
+        let model_path = self.models_path.join(format!("ggml-{}.bin", config.model));
+        
+        if !model_path.exists() {
+            return Err(anyhow::anyhow!("Model not found: {}", config.model));
+        }
+
+        // 출력 형식을 지정하지 않았으면 설정의 기본 형식 사용 (히스토리에도 실제 사용한 형식이 남도록 먼저 적용)
+        let mut config = config.clone();
+        let settings = SettingsService::new().load();
+        apply_default_output_formats(&mut config.options, &settings.default_output_formats);
+        apply_word_timing_options(&mut config.options);
+
+        // 히스토리 항목 생성
+        let input_path = PathBuf::from(&config.input_file);
+        let original_file_name = input_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
         
         let history = self.history_service.create_history_entry(
             original_file_name.clone(),
@@ -508,101 +1297,374 @@ impl WhisperService {
         ).await?;
         
         let history_id = history.id.clone();
-
-        // whisper-cli 바이너리 찾기 (최신 whisper.cpp에서 권장)
-        let whisper_cli_binary = self.whisper_repo_path.join("build").join("bin").join("whisper-cli");
-        let fallback_cli_binary = self.whisper_repo_path.join("build").join("whisper-cli");
-        // 백워드 호환성을 위한 main 바이너리
-        let main_binary = self.whisper_repo_path.join("build").join("bin").join("main");
-        let fallback_binary = self.whisper_repo_path.join("build").join("main");
-        
-        let binary_path = if whisper_cli_binary.exists() {
-            &whisper_cli_binary
-        } else if fallback_cli_binary.exists() {
-            &fallback_cli_binary
-        } else if main_binary.exists() {
-            &main_binary
-        } else if fallback_binary.exists() {
-            &fallback_binary
-        } else {
-            // 히스토리 실패로 마크
-            self.history_service.mark_history_failed(
-                &history_id, 
-                "Whisper binary not found".to_string()
+        
+        // 결과 차이를 바이너리 버전과 대조할 수 있도록 설치된 커밋을 기록
+        if let Some(commit) = self.installer.get_installed_commit().await {
+            self.history_service.record_whisper_commit(&history_id, commit).await.ok();
+        }
+        if !config.export_formats.is_empty() || config.preserve_source_mtime {
+            self.history_service.record_output_preferences(
+                &history_id,
+                config.export_formats.clone(),
+                config.preserve_source_mtime,
             ).await.ok();
-            return Err(anyhow::anyhow!("Whisper binary not found"));
+        }
+        
+        Ok((history_id, config))
+    }
+    
+    /// 서비스 잠금 없이 변환을 시작할 수 있도록 실행에 필요한 경로와 공유 상태를 복사합니다
+    fn transcription_runner(&self) -> TranscriptionRunner {
+        TranscriptionRunner {
+            models_path: self.models_path.clone(),
+            binary_path: self.resolve_whisper_binary(),
+            history_service: self.history_service.clone(),
+            media_tools: self.media_tools.clone(),
+            active_transcriptions: self.active_transcriptions.clone(),
+            transcription_queue: self.transcription_queue.clone(),
+            resource_sampler: self.resource_sampler.clone(),
+        }
+    }
+    
+    /// 실행 중인 변환의 whisper 프로세스를 종료합니다
+    ///
+    /// 실제 종료와 히스토리 실패 처리, `transcription-cancelled` 이벤트는 프로세스를 기다리는 태스크에서 합니다.
+    ///
+    /// 아직 시작하지 않은 대기열 항목은 대기열에서 빼고 바로 Cancelled로 표시합니다.
+    pub fn cancel_transcription(&self, history_id: &str) -> anyhow::Result<()> {
+        let removed = {
+            let mut jobs = self.transcription_queue.jobs.lock().unwrap();
+            let before = jobs.len();
+            jobs.retain(|job| job.history_id != history_id);
+            jobs.len() != before
         };
+        if removed {
+            let history_service = self.history_service.clone();
+            let history_id = history_id.to_string();
+            tokio::spawn(async move {
+                history_service.mark_history_cancelled(&history_id, Vec::new()).await.ok();
+            });
+            return Ok(());
+        }
+        
+        if self.transcription_queue.is_starting(history_id) {
+            return Err(anyhow::anyhow!("Transcription is still preparing its input, try again shortly: {}", history_id));
+        }
+        
+        let active = self.active_transcriptions.lock().unwrap();
+        let transcription = active.get(history_id)
+            .ok_or_else(|| anyhow::anyhow!("No running transcription for history: {}", history_id))?;
+        
+        transcription.cancel.notify_one();
+        Ok(())
+    }
+    
+    /// 추적 중인 프로세스 없이 Running/Queued로 남은 히스토리를 강제로 Failed(또는 Cancelled)로 바꿉니다
+    ///
+    /// 외부에서 프로세스가 종료되어 상태가 갱신되지 않은 경우를 위한 수동 복구용이므로,
+    /// 실제로 실행 중이거나 대기열에 있는 변환은 건드리지 않습니다.
+    pub async fn force_fail_history(&self, history_id: &str, reason: &str, cancelled: bool) -> anyhow::Result<TranscriptionHistory> {
+        if self.active_transcriptions.lock().unwrap().contains_key(history_id) || self.transcription_queue.is_starting(history_id) {
+            return Err(anyhow::anyhow!("Transcription is still running: {}", history_id));
+        }
+        if self.transcription_queue.jobs.lock().unwrap().iter().any(|job| job.history_id == history_id) {
+            return Err(anyhow::anyhow!("Transcription is still queued: {}", history_id));
+        }
+        
+        let history = self.history_service.get_history(history_id).await?;
+        if !matches!(history.status, TranscriptionStatus::Running | TranscriptionStatus::Queued | TranscriptionStatus::Idle) {
+            return Err(anyhow::anyhow!("History is not in progress: {}", history_id));
+        }
+        
+        if cancelled {
+            self.history_service.mark_history_cancelled(history_id, Vec::new()).await
+        } else {
+            self.history_service.mark_history_failed(history_id, reason.to_string()).await
+        }
+    }
+    
+    /// 실행 중인 변환의 남은 시간을 경과 시간과 진행률로 추정합니다
+    ///
+    /// 조회할 때마다 진행률 표본을 쌓고 최근 표본 구간의 평균 속도로 계산하므로,
+    /// whisper가 30초 윈도우 단위로 출력하면서 생기는 진행률 계단에 따른 흔들림이 줄어듭니다.
+    pub fn get_transcription_eta(&self, history_id: &str) -> anyhow::Result<TranscriptionEta> {
+        let mut active = self.active_transcriptions.lock().unwrap();
+        let transcription = active.get_mut(history_id)
+            .ok_or_else(|| anyhow::anyhow!("No running transcription for history: {}", history_id))?;
+        
+        let elapsed_seconds = transcription.started.elapsed().as_secs_f64();
+        let progress = transcription.progress();
+        transcription.record_sample(elapsed_seconds, progress);
+        
+        Ok(TranscriptionEta {
+            history_id: history_id.to_string(),
+            progress,
+            elapsed_seconds,
+            remaining_seconds: transcription.estimate_remaining(elapsed_seconds, progress),
+        })
+    }
+    
+    /// 실행 중인 변환의 whisper 프로세스 메모리(RSS)와 CPU 사용률을 반환합니다
+    ///
+    /// 프로세스가 이미 끝났으면 `running: false`와 빈 값을 반환합니다.
+    pub fn get_transcription_resources(&self, history_id: &str) -> anyhow::Result<TranscriptionResources> {
+        let pid = self.active_transcriptions.lock().unwrap()
+            .get(history_id)
+            .ok_or_else(|| anyhow::anyhow!("No running transcription for history: {}", history_id))?
+            .pid;
+        
+        Ok(sample_transcription_resources(&self.resource_sampler, history_id, pid))
+    }
+    
+    /// 변환이 끝날 때까지 주기적으로 `transcription-resources` 이벤트를 보냅니다
+    fn spawn_resource_monitor(
+        history_id: String,
+        active_transcriptions: ActiveTranscriptions,
+        sampler: ResourceSampler,
+        app_handle: tauri::AppHandle,
+    ) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(RESOURCE_SAMPLE_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                let pid = match active_transcriptions.lock().unwrap().get(&history_id) {
+                    Some(active) => active.pid,
+                    None => break,
+                };
+                let resources = sample_transcription_resources(&sampler, &history_id, pid);
+                app_handle.emit_all("transcription-resources", &resources).ok();
+            }
+        });
+    }
+    
+    /// 사용할 whisper 실행 파일을 찾습니다 (whisper-cli 우선, 구버전 main 순)
+    ///
+    /// 알려진 위치에 없으면 build/ 아래를 재귀적으로 찾고, 찾은 경로는 캐시해 두었다가
+    /// 파일이 남아 있는 동안 재사용합니다 (사용자 지정 CMake 제너레이터 대응).
+    fn resolve_whisper_binary(&self) -> Option<PathBuf> {
+        // 설정된 사용자 지정 바이너리가 유효하면 아래 탐색을 모두 건너뜀
+        if let Some(Ok(path)) = self.check_custom_binary() {
+            return Some(path);
+        }
+        
+        let build_dir = self.whisper_repo_path.join("build");
+        let known = [
+            build_dir.join("bin").join("whisper-cli"),
+            build_dir.join("whisper-cli"),
+            build_dir.join("bin").join("main"),
+            build_dir.join("main"),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+        if known.is_some() {
+            return known;
+        }
+        
+        let mut cached = self.discovered_binary.lock().unwrap();
+        if let Some(path) = cached.as_ref().filter(|path| path.exists()) {
+            return Some(path.clone());
+        }
+        
+        let discovered = WHISPER_BINARY_NAMES.iter()
+            .find_map(|name| find_executable_recursive(&build_dir, name, BINARY_SEARCH_MAX_DEPTH));
+        if let Some(path) = &discovered {
+            eprintln!("Discovered whisper binary at {:?}", path);
+        }
+        *cached = discovered.clone();
+        discovered
+    }
 
-        // 히스토리 결과 디렉토리 생성 (files 서브디렉토리 포함)
-        let results_dir = self.history_service.get_history_directory(&history_id);
-        let files_dir = results_dir.join("files");
-        tokio::fs::create_dir_all(&files_dir).await?;
+    /// 설정된 사용자 지정 바이너리를 확인합니다 (설정이 없으면 None)
+    ///
+    /// --help 실행 결과는 경로와 수정 시간 기준으로 캐시하므로 다시 빌드하면 새로 확인합니다.
+    fn check_custom_binary(&self) -> Option<Result<PathBuf, String>> {
+        let path = SettingsService::new().load().custom_binary_path?;
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
         
-        let mut args = vec![
-            "-m".to_string(), 
-            model_path.to_string_lossy().to_string(),
-            "-f".to_string(), 
-            config.input_file.clone()
-        ];
+        let mut cached = self.custom_binary_probe.lock().unwrap();
+        if let Some(probe) = cached.as_ref().filter(|probe| probe.path == path && probe.modified == modified) {
+            return Some(probe.result.clone().map(|_| path));
+        }
+        
+        let result = probe_whisper_binary(&path);
+        if let Err(e) = &result {
+            eprintln!("Custom whisper binary rejected, using default lookup: {}", e);
+        }
+        *cached = Some(CustomBinaryProbe { path: path.clone(), modified, result: result.clone() });
+        Some(result.map(|_| path))
+    }
 
-        // 출력 경로를 files 디렉토리로 설정하여 중복 저장 방지
-        let output_file_base = files_dir.join("result");
+    /// 녹음 중인 파일처럼 길어지는 입력에서 아직 처리하지 않은 뒷부분만 변환해 기존 결과에 이어붙입니다
+    ///
+    /// whisper 프로세스를 시작한 뒤 바로 반환하며, 이어붙이기가 끝나면 `transcription-append-complete`
+    /// (실패하면 `transcription-error`) 이벤트를 보냅니다.
+    pub async fn transcribe_append(
+        &self,
+        history_id: &str,
+        file_path: &str,
+        app_handle: tauri::AppHandle,
+    ) -> anyhow::Result<()> {
+        use tokio::process::Command as TokioCommand;
+        use std::process::Stdio;
         
-        // --output-file 옵션으로 베이스 경로 지정 (확장자 제외)
-        args.push("--output-file".to_string());
-        args.push(output_file_base.to_string_lossy().to_string());
-        
-        // 출력 형식별 플래그 설정
-        let mut has_output_format = false;
-        for (key, value) in &config.options {
-            if key.starts_with("output-") {
-                // output-srt, output-txt 등은 플래그로만 사용
-                args.push(format!("--{}", key));
-                has_output_format = true;
-            } else if value.is_empty() {
-                args.push(format!("--{}", key));
-            } else {
-                args.push(format!("--{}", key));
-                args.push(value.clone());
+        let history = self.history_service.get_history(history_id).await?;
+        if matches!(history.status, TranscriptionStatus::Running) {
+            return Err(anyhow::anyhow!("History {} is still running", history_id));
+        }
+        if self.active_appends.lock().unwrap().contains(history_id) {
+            return Err(anyhow::anyhow!("Append is already running for history {}", history_id));
+        }
+        
+        let model_path = self.models_path.join(format!("ggml-{}.bin", history.model_used));
+        if !model_path.exists() {
+            return Err(anyhow::anyhow!("Model not found: {}", history.model_used));
+        }
+        let binary_path = self.resolve_whisper_binary()
+            .ok_or_else(|| anyhow::anyhow!("Whisper binary not found"))?;
+        
+        let processed_until = self.history_service.get_processed_until(history_id).await?;
+        let duration = probe_media_duration(&self.media_tools, std::path::Path::new(file_path)).await
+            .ok_or_else(|| anyhow::anyhow!("Could not determine duration of {} (ffprobe is required)", file_path))?;
+        
+        // 이미 처리한 구간은 다시 변환하지 않음
+        if duration - processed_until < MIN_APPEND_SECONDS {
+            return Err(anyhow::anyhow!(
+                "No new audio to transcribe (processed {:.1}s of {:.1}s)", processed_until, duration
+            ));
+        }
+        
+        // 이어붙일 구간은 srt로만 받아서 기존 결과 형식으로 변환
+        let mut options: std::collections::HashMap<String, String> = history.options_used.iter()
+            .filter(|(key, _)| !key.starts_with("output-"))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        options.insert("offset-t".to_string(), ((processed_until * 1000.0) as u64).to_string());
+        
+        let files_dir = self.history_service.get_history_directory(history_id).join("files");
+        let output_file_base = files_dir.join("append");
+        let args = build_transcription_args(&model_path, file_path, &output_file_base, &options);
+        
+        eprintln!("Executing whisper append command: {:?} {:?}", binary_path, args);
+        let child = TokioCommand::new(&binary_path)
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        
+        // 프로세스가 끝날 때까지 서비스 잠금을 잡지 않도록 나머지는 별도 태스크에서 처리
+        self.active_appends.lock().unwrap().insert(history_id.to_string());
+        let active_appends = self.active_appends.clone();
+        let history_service = self.history_service.clone();
+        let history_id = history_id.to_string();
+        tokio::spawn(async move {
+            let result = Self::finish_append(&history_service, &history_id, child, &output_file_base, processed_until, duration).await;
+            active_appends.lock().unwrap().remove(&history_id);
+            
+            match result {
+                Ok(_) => {
+                    app_handle.emit_all("transcription-append-complete", &history_id).ok();
+                }
+                Err(e) => {
+                    eprintln!("Append transcription failed for {}: {}", history_id, e);
+                    app_handle.emit_all("transcription-error", &e.to_string()).ok();
+                }
             }
+        });
+        
+        Ok(())
+    }
+    
+    /// 이어붙이기용 whisper 프로세스가 끝나면 새 구간의 srt를 읽어 기존 결과에 붙입니다
+    async fn finish_append(
+        history_service: &HistoryService,
+        history_id: &str,
+        child: tokio::process::Child,
+        output_file_base: &std::path::Path,
+        processed_until: f64,
+        duration: f64,
+    ) -> anyhow::Result<TranscriptionHistory> {
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Append transcription failed: {}", String::from_utf8_lossy(&output.stderr).trim()
+            ));
         }
         
-        // 기본 SRT 출력을 위한 설정 (사용자가 지정하지 않았을 경우)
-        if !has_output_format {
-            args.push("--output-srt".to_string());
+        let append_file = output_file_base.with_extension("srt");
+        let content = tokio::fs::read_to_string(&append_file).await?;
+        tokio::fs::remove_file(&append_file).await.ok();
+        
+        let mut segments = parse_subtitle_segments(&content);
+        
+        // whisper.cpp는 오프셋을 포함한 절대 시간을 출력하지만, 상대 시간이면 오프셋만큼 보정
+        if segments.first().is_some_and(|s| s.start + 0.5 < processed_until) {
+            for segment in segments.iter_mut() {
+                segment.start += processed_until;
+                segment.end += processed_until;
+            }
         }
+        segments.retain(|s| s.end > processed_until);
+        
+        history_service.append_transcription_segments(history_id, &segments, duration).await
+    }
 
-        // 실행될 명령어 로그 출력
-        eprintln!("Executing whisper command:");
-        eprintln!("Binary: {:?}", binary_path);
-        eprintln!("Args: {:?}", args);
-
-        let mut cmd = TokioCommand::new(binary_path)
+    /// whisper 프로세스를 실행하고 stdout/stderr를 실시간으로 이벤트 전송합니다
+    ///
+    /// 파싱된 오디오 위치(초)는 `monitor.audio_position`에 기록되어 진행 속도 감시에 사용되고,
+    /// 세그먼트가 출력될 때마다 (오디오 구간, 프로세스 시작 후 경과 시간)이 `monitor.segment_timeline`에 쌓입니다.
+    /// 모든 출력 줄은 나중에 다시 볼 수 있도록 `monitor.log_path`에도 저장됩니다.
+    fn spawn_whisper_process(
+        binary_path: &std::path::Path,
+        args: &[String],
+        app_handle: &tauri::AppHandle,
+        monitor: ProcessMonitor,
+        priority: ProcessPriority,
+    ) -> std::io::Result<tokio::process::Child> {
+        use tokio::process::Command as TokioCommand;
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        use std::process::Stdio;
+        
+        let mut command = TokioCommand::new(binary_path);
+        command
             .args(args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| {
-                // 프로세스 시작 실패 시 히스토리 실패로 마크
-                let history_service = self.history_service.clone();
-                let history_id = history_id.clone();
-                let error_message = e.to_string();
-                tokio::spawn(async move {
-                    history_service.mark_history_failed(&history_id, error_message).await.ok();
-                });
-                e
-            })?;
+            .stderr(Stdio::piped());
+        apply_priority_class(&mut command, priority);
+        let mut cmd = command.spawn()?;
+        if let Some(pid) = cmd.id() {
+            apply_niceness(pid, priority);
+        }
 
         let stdout = cmd.stdout.take().unwrap();
         let stderr = cmd.stderr.take().unwrap();
 
         // stdout 처리
         let app_handle_clone = app_handle.clone();
+        let ProcessMonitor { history_id, audio_position, audio_duration, segment_timeline, phase_weights, log_path } = monitor;
+        let audio_duration_stderr = audio_duration.clone();
+        let log_file = open_process_log(&log_path);
+        let log_file_stderr = log_file.clone();
+        let started = std::time::Instant::now();
         tokio::spawn(async move {
             let mut reader = BufReader::new(stdout).lines();
             while let Ok(Some(line)) = reader.next_line().await {
                 app_handle_clone.emit_all("transcription-log", &line).ok();
-                if let Some(progress) = parse_whisper_output_line(&line) {
+                append_process_log(&log_file, &line);
+                if let Some(segment) = parse_segment_line(&line) {
+                    segment_timeline.lock().unwrap().push((segment.start, segment.end, started.elapsed().as_secs_f64()));
+                    app_handle_clone.emit_all("transcription-segment", &TranscriptionSegmentEvent {
+                        history_id: history_id.clone(),
+                        start: segment.start,
+                        end: segment.end,
+                        text: segment.text,
+                    }).ok();
+                }
+                let total_duration = *audio_duration.lock().unwrap();
+                if let Some(mut progress) = parse_whisper_output_line(&line, total_duration) {
+                    if let Some(current_time) = progress.current_time {
+                        *audio_position.lock().unwrap() = current_time;
+                    }
+                    progress.progress = phase_weights.overall(progress.phase, progress.progress);
                     app_handle_clone.emit_all("transcription-progress", &progress).ok();
                 }
             }
@@ -614,86 +1676,114 @@ impl WhisperService {
             let mut reader = BufReader::new(stderr).lines();
             while let Ok(Some(line)) = reader.next_line().await {
                 app_handle_stderr.emit_all("transcription-log", &line).ok();
-            }
-        });
-
-        // 프로세스 완료 처리
-        let app_handle_final = app_handle;
-        let history_service = self.history_service.clone();
-        let history_id_final = history_id.clone();
-        let input_path_final = input_path.clone();
-        let config_options = config.options.clone();
-        
-        tokio::spawn(async move {
-            match cmd.wait().await {
-                Ok(status) => {
-                    if status.success() {
-                        // 결과 파일들 수집 및 히스토리에 저장
-                        match Self::collect_and_save_result_files(
-                            &history_service,
-                            &history_id_final,
-                            &input_path_final,
-                            &config_options,
-                        ).await {
-                            Ok(_) => {
-                                app_handle_final.emit_all("transcription-complete", &history_id_final).ok();
-                            }
-                            Err(e) => {
-                                history_service.mark_history_failed(
-                                    &history_id_final, 
-                                    format!("Failed to save results: {}", e)
-                                ).await.ok();
-                                app_handle_final.emit_all("transcription-error", &e.to_string()).ok();
-                            }
-                        }
-                    } else {
-                        history_service.mark_history_failed(
-                            &history_id_final, 
-                            "Transcription process failed".to_string()
-                        ).await.ok();
-                        app_handle_final.emit_all("transcription-error", "Process failed").ok();
-                    }
-                }
-                Err(e) => {
-                    history_service.mark_history_failed(
-                        &history_id_final, 
-                        e.to_string()
-                    ).await.ok();
-                    app_handle_final.emit_all("transcription-error", &e.to_string()).ok();
+                append_process_log(&log_file_stderr, &line);
+                if let Some(duration) = parse_processing_duration(&line) {
+                    audio_duration_stderr.lock().unwrap().get_or_insert(duration);
                 }
             }
         });
-
-        Ok(history_id)
+        
+        Ok(cmd)
     }
     
-    async fn collect_and_save_result_files(
+    /// 취소된 변환의 files 디렉토리에 남아 있는 결과 파일들을 부분 결과로 등록합니다
+    async fn recover_partial_results(
         history_service: &HistoryService,
         history_id: &str,
-        _input_path: &PathBuf,
-        options: &std::collections::HashMap<String, String>,
-    ) -> anyhow::Result<()> {
-        // files 디렉토리에서 결과 파일들 찾기 (whisper.cpp가 직접 저장한 위치)
-        let results_dir = history_service.get_history_directory(history_id);
-        let files_dir = results_dir.join("files");
+    ) -> anyhow::Result<TranscriptionHistory> {
+        let partial_files = Self::partial_result_files(history_service, history_id);
         
-        let mut result_files = Vec::new();
+        eprintln!("Recovered {} partial result file(s) for {}", partial_files.len(), history_id);
+        history_service.mark_history_cancelled(history_id, partial_files).await
+    }
+    
+    /// 중단된 변환의 files 디렉토리에 whisper가 쓰다 만 결과 파일들을 찾습니다
+    fn partial_result_files(history_service: &HistoryService, history_id: &str) -> Vec<(PathBuf, String)> {
+        let files_dir = history_service.get_history_directory(history_id).join("files");
         
-        // 생성될 수 있는 출력 형식들 체크
-        let output_formats = vec![
-            ("output-txt", "txt"),
-            ("output-srt", "srt"),
-            ("output-vtt", "vtt"),
-            ("output-csv", "csv"),
-            ("output-json", "json"),
-            ("output-lrc", "lrc"),
-        ];
+        whisper_output_formats()
+            .map(|(_, format)| (files_dir.join(format!("result.{}", format)), format.to_string()))
+            .filter(|(path, _)| path.exists())
+            .collect()
+    }
+    
+    /// whisper가 직접 만들지 않은 형식들을 srt/vtt 결과에서 동시에 변환해 저장합니다
+    async fn derive_export_formats(
+        files_dir: &std::path::Path,
+        existing: &[(PathBuf, String)],
+        export_formats: &[String],
+    ) -> Vec<(PathBuf, String)> {
+        let pending: Vec<&String> = export_formats.iter()
+            .filter(|format| !existing.iter().any(|(_, f)| f == *format))
+            .collect();
+        if pending.is_empty() {
+            return Vec::new();
+        }
+        
+        let source = ["srt", "vtt"].iter()
+            .find_map(|format| existing.iter().find(|(_, f)| f == format));
+        let source_content = match source {
+            Some((path, _)) => match tokio::fs::read_to_string(path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Failed to read subtitle source for export: {}", e);
+                    return Vec::new();
+                }
+            },
+            None => {
+                eprintln!("No srt/vtt result to derive export formats from");
+                return Vec::new();
+            }
+        };
+        let segments = parse_subtitle_segments(&source_content);
+        
+        let conversions = pending.into_iter().map(|format| {
+            let (source_content, segments) = (&source_content, &segments);
+            async move {
+                let content = match derive_export_content(format, source_content, segments) {
+                    Some(content) => content,
+                    None => {
+                        eprintln!("Unsupported export format: {}", format);
+                        return None;
+                    }
+                };
+                
+                let target_path = files_dir.join(format!("result.{}", format));
+                match tokio::fs::write(&target_path, content).await {
+                    Ok(()) => Some((target_path, format.clone())),
+                    Err(e) => {
+                        eprintln!("Failed to write {} export: {}", format, e);
+                        None
+                    }
+                }
+            }
+        });
+        
+        futures_util::future::join_all(conversions).await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+    
+    async fn collect_and_save_result_files(
+        history_service: &HistoryService,
+        history_id: &str,
+        input_path: &PathBuf,
+        options: &std::collections::HashMap<String, String>,
+        export_formats: &[String],
+        preserve_source_mtime: bool,
+    ) -> anyhow::Result<()> {
+        // files 디렉토리에서 결과 파일들 찾기 (whisper.cpp가 직접 저장한 위치)
+        let results_dir = history_service.get_history_directory(history_id);
+        let files_dir = results_dir.join("files");
+        
+        let mut result_files = Vec::new();
         
         eprintln!("Looking for result files in files directory: {:?}", files_dir);
         
-        for (option_key, format) in output_formats {
-            // 해당 옵션이 활성화되어 있거나, 기본 srt 출력인 경우 
-            if options.contains_key(option_key) || format == "srt" {
+        for (option_key, format) in whisper_output_formats() {
+            // 시작 시 기본 형식이 옵션에 채워지므로 활성화된 형식만 찾음
+            if options.contains_key(option_key) {
                 let result_file_path = files_dir.join(format!("result.{}", format));
                 
                 eprintln!("Checking for result file: {:?}", result_file_path);
@@ -711,42 +1801,1846 @@ impl WhisperService {
             return Err(anyhow::anyhow!("No result files found in files directory"));
         }
         
+        if let Err(e) = rewrite_word_level_srt(&files_dir, options).await {
+            eprintln!("Word-level SRT generation failed for {}: {}", history_id, e);
+        }
+        
+        // 요청된 추가 형식들을 자막 결과에서 동시에 파생
+        let derived_files = Self::derive_export_formats(&files_dir, &result_files, export_formats).await;
+        result_files.extend(derived_files);
+        
+        // 완료 순서와 관계없이 항상 같은 순서로 등록
+        result_files.sort_by_key(|(_, format)| export_format_rank(format));
+        
+        if preserve_source_mtime {
+            copy_source_mtime(input_path, &result_files);
+        }
+        
         // 결과 파일들을 히스토리에 등록 (이미 올바른 위치에 있으므로 복사하지 않음)
         history_service.register_existing_results(history_id, result_files).await?;
         
+        // 요청 언어와 결과 문자 체계 비교 (실패해도 변환 결과에는 영향 없음)
+        if let Err(e) = history_service.check_language_mismatch(history_id).await {
+            eprintln!("Language mismatch check failed for {}: {}", history_id, e);
+        }
+        
+        if let Err(e) = history_service.check_encoding_issues(history_id).await {
+            eprintln!("Encoding check failed for {}: {}", history_id, e);
+        }
+        
+        if let Err(e) = history_service.record_loaded_model(history_id).await {
+            eprintln!("Loaded model check failed for {}: {}", history_id, e);
+        }
+        
+        if let Err(e) = history_service.record_average_confidence(history_id).await {
+            eprintln!("Average confidence calculation failed for {}: {}", history_id, e);
+        }
+        
+        if let Err(e) = history_service.record_preview(history_id).await {
+            eprintln!("Preview generation failed for {}: {}", history_id, e);
+        }
+        
+        Ok(())
+    }
+}
+
+/// 자막 세그먼트에서 지정한 형식의 내용을 만듭니다 (지원하지 않는 형식은 None)
+///
+/// 여기서 처리하는 형식은 `EXPORT_FORMATS`의 `derivable`과 일치해야 합니다.
+fn derive_export_content(format: &str, source_content: &str, segments: &[SubtitleSegment]) -> Option<String> {
+    match format {
+        "txt" => Some(segments_to_plain_text(segments)),
+        "srt" => Some(segments_to_srt(segments)),
+        "vtt" => Some(convert_to_styled_vtt(source_content, &VttStyleOptions::default())),
+        "fcpxml" => Some(segments_to_fcpxml(segments)),
+        _ => None,
+    }
+}
+
+/// 저장된 결과가 없는 형식의 내용을 기준 결과에서 다시 만듭니다
+async fn derived_format_content(history: &TranscriptionHistory, format: &str) -> anyhow::Result<String> {
+    let (_, segments) = regeneration_segments(history).await?;
+    let canonical = segments_to_srt(&segments);
+    derive_export_content(format, &canonical, &segments)
+        .ok_or_else(|| anyhow::anyhow!("No {} result and it cannot be derived", format))
+}
+
+/// 다운로드 후 검증에 사용하는 whisper.cpp 저장소 내 샘플 오디오
+const VERIFICATION_SAMPLE: &str = "samples/jfk.wav";
+
+/// 납품 패키지 안의 설정/출처 설명 파일 이름
+const DELIVERY_MANIFEST_FILE: &str = "manifest.json";
+
+/// (파일 이름, 내용) 목록을 deflate 압축 zip으로 씁니다
+fn write_zip_archive(path: &std::path::Path, entries: &[(String, Vec<u8>)]) -> anyhow::Result<()> {
+    use std::io::Write;
+    
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in entries {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(content)?;
+    }
+    zip.finish()?;
+    
+    Ok(())
+}
+
+/// 다시 만들 기준 결과를 읽어 세그먼트로 만듭니다 (기준 형식, 세그먼트)
+///
+/// txt가 원본이면 줄 수가 기존 자막 큐 수와 같을 때 기존 타이밍을 유지합니다.
+async fn regeneration_segments(history: &TranscriptionHistory) -> anyhow::Result<(String, Vec<SubtitleSegment>)> {
+    let source = select_regeneration_source(&history.results).await
+        .ok_or_else(|| anyhow::anyhow!("No txt/srt/vtt result to regenerate from"))?;
+    let source_content = tokio::fs::read_to_string(&source.file_path).await?;
+    
+    let segments = if source.format == "txt" {
+        let timed = match history.results.iter().find(|r| r.format == "srt" || r.format == "vtt") {
+            Some(result) => parse_subtitle_segments(&tokio::fs::read_to_string(&result.file_path).await?),
+            None => Vec::new(),
+        };
+        retime_edited_text(&source_content, &timed)
+    } else {
+        parse_subtitle_segments(&source_content)
+    };
+    if segments.is_empty() {
+        return Err(anyhow::anyhow!("Source {} result has no segments", source.format));
+    }
+    
+    Ok((source.format.clone(), segments))
+}
+
+/// 다시 만들 때 기준으로 삼을 결과를 고릅니다 (가장 최근에 수정된 txt/srt/vtt, 같으면 srt > vtt > txt)
+async fn select_regeneration_source(results: &[TranscriptionResult]) -> Option<&TranscriptionResult> {
+    let mut best: Option<(&TranscriptionResult, std::time::SystemTime)> = None;
+    for format in ["srt", "vtt", "txt"] {
+        if let Some(result) = results.iter().find(|r| r.format == format) {
+            if let Ok(modified) = tokio::fs::metadata(&result.file_path).await.and_then(|m| m.modified()) {
+                if best.map_or(true, |(_, best_modified)| modified > best_modified) {
+                    best = Some((result, modified));
+                }
+            }
+        }
+    }
+    best.map(|(result, _)| result)
+}
+
+/// 수정된 텍스트의 각 줄에 기존 자막 타이밍을 입힙니다 (줄 수가 다르면 5초 간격 추정)
+fn retime_edited_text(text: &str, timed: &[SubtitleSegment]) -> Vec<SubtitleSegment> {
+    let lines: Vec<&str> = text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    
+    if lines.len() != timed.len() {
+        return segments_or_estimated(text);
+    }
+    
+    lines.into_iter()
+        .zip(timed)
+        .map(|(line, segment)| SubtitleSegment {
+            start: segment.start,
+            end: segment.end,
+            text: line.to_string(),
+        })
+        .collect()
+}
+
+/// 입력 변환이 있을 때 전체 진행률에서 변환 단계가 차지하는 비율
+const PREPROCESSING_PROGRESS_SHARE: f32 = 0.15;
+
+/// 단계별 진행률을 전체 진행률 구간에 배분합니다
+///
+/// 변환 단계가 있으면 0~15%는 변환, 15~100%는 모델 로딩과 whisper 변환에 쓰고, 없으면 두 단계가
+/// 전체 구간을 사용합니다. 결과 저장 단계는 변환이 끝난 뒤이므로 항상 100%입니다.
+#[derive(Debug, Clone, Copy)]
+struct PhaseWeights {
+    preprocessing_share: f32,
+}
+
+impl PhaseWeights {
+    fn new(preprocessed: bool) -> Self {
+        Self {
+            preprocessing_share: if preprocessed { PREPROCESSING_PROGRESS_SHARE } else { 0.0 },
+        }
+    }
+    
+    /// 단계 내 진행률(0.0 ~ 1.0)을 전체 진행률로 변환합니다
+    fn overall(&self, phase: TranscriptionPhase, phase_progress: f32) -> f32 {
+        let phase_progress = phase_progress.clamp(0.0, 1.0);
+        match phase {
+            TranscriptionPhase::Queued => 0.0,
+            TranscriptionPhase::Preprocessing => phase_progress * self.preprocessing_share,
+            TranscriptionPhase::LoadingModel | TranscriptionPhase::Transcribing => {
+                self.preprocessing_share + phase_progress * (1.0 - self.preprocessing_share)
+            }
+            TranscriptionPhase::Finalizing => 1.0,
+        }
+    }
+}
+
+/// 출력된 세그먼트 기록: (오디오 시작, 오디오 끝, 프로세스 시작 후 출력 시점) 초 단위
+type SegmentTimeline = std::sync::Arc<std::sync::Mutex<Vec<(f64, f64, f64)>>>;
+
+/// 입력 오디오 전체 길이 (초, 진행률 계산용으로 출력 처리 태스크와 공유)
+type AudioDuration = std::sync::Arc<std::sync::Mutex<Option<f64>>>;
+
+/// 실행 중인 whisper 프로세스의 출력을 받아 기록할 곳들 (재시도 시에도 같은 값을 공유)
+#[derive(Clone)]
+struct ProcessMonitor {
+    history_id: String,
+    audio_position: std::sync::Arc<std::sync::Mutex<f32>>,
+    audio_duration: AudioDuration,
+    segment_timeline: SegmentTimeline,
+    phase_weights: PhaseWeights,
+    log_path: PathBuf,                  // stdout/stderr를 함께 기록할 파일 (재시도 시 이어서 기록)
+}
+
+/// 사용자 지정 바이너리의 --help 확인 결과
+struct CustomBinaryProbe {
+    path: PathBuf,
+    modified: Option<std::time::SystemTime>,
+    result: Result<(), String>,
+}
+
+/// whisper 실행 파일인지 --help로 확인합니다 (구버전 main은 usage를 출력하고 0이 아닌 값으로 끝나기도 함)
+fn probe_whisper_binary(path: &std::path::Path) -> Result<(), String> {
+    if !is_executable(path) {
+        return Err(format!("Not an executable file: {}", path.display()));
+    }
+    
+    let output = std::process::Command::new(path)
+        .arg("--help")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", path.display(), e))?;
+    
+    let help = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    if output.status.success() || help.contains("usage:") {
         Ok(())
+    } else {
+        Err(format!("{} --help exited with {}", path.display(), output.status))
+    }
+}
+
+/// 변환 완료 후 훅 명령의 최대 실행 시간 (초과하면 종료)
+const POST_HOOK_TIMEOUT_SECS: u64 = 60;
+
+/// 설정된 변환 완료 훅을 실행하고 출력을 히스토리 로그에 덧붙입니다
+async fn run_post_hook(history_service: &HistoryService, history_id: &str) {
+    if let Some(template) = SettingsService::new().load().post_hook_command {
+        run_post_hook_template(history_service, history_id, &template).await;
+    }
+}
+
+/// 훅 명령 템플릿을 실행합니다 (실패해도 경고만 남기고 히스토리 상태는 바꾸지 않음)
+async fn run_post_hook_template(history_service: &HistoryService, history_id: &str, template: &str) {
+    let history = match history_service.get_history(history_id).await {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Post hook skipped, failed to load history {}: {}", history_id, e);
+            return;
+        }
+    };
+    
+    // 결과가 여러 개면 등록 순서상 첫 번째(txt > srt > ...) 파일을 넘김
+    let result_path = history.results.first()
+        .map(|r| r.file_path.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let command = render_post_hook_command(template, history_id, &result_path, &history.original_file_name);
+    
+    let log = open_process_log(&history_service.get_history_directory(history_id).join(HISTORY_LOG_FILE));
+    append_process_log(&log, &format!("[post-hook] {}", command.command_line));
+    
+    let message = match execute_post_hook(&command).await {
+        Ok(output) => {
+            for line in String::from_utf8_lossy(&output.stdout).lines().chain(String::from_utf8_lossy(&output.stderr).lines()) {
+                append_process_log(&log, &format!("[post-hook] {}", line));
+            }
+            format!("[post-hook] exited with {}", output.status)
+        }
+        Err(e) => format!("[post-hook] failed: {}", e),
+    };
+    eprintln!("{}", message);
+    append_process_log(&log, &message);
+}
+
+/// 자리표시자를 채운 셸 명령 (값은 명령 문자열 대신 환경 변수로 넘김)
+struct ShellCommand {
+    command_line: String,
+    env: Vec<(&'static str, String)>,
+}
+
+/// 명령 템플릿의 자리표시자를 따옴표로 감싼 환경 변수 참조로 바꿉니다
+///
+/// cmd는 `%`처럼 따옴표로도 막을 수 없는 문자가 있어 값을 명령 문자열에 직접 넣으면 파일 이름으로 명령이 바뀔 수 있으므로,
+/// 셸이 한 번만 확장하는 환경 변수로 넘깁니다. `(자리표시자, 환경 변수 이름, 값)` 목록을 받습니다.
+fn render_shell_template(template: &str, values: &[(&str, &'static str, &str)]) -> ShellCommand {
+    let mut command_line = template.to_string();
+    let mut env = Vec::new();
+    for &(placeholder, name, value) in values {
+        if !command_line.contains(placeholder) {
+            continue;
+        }
+        command_line = command_line.replace(placeholder, &env_reference(name));
+        env.push((name, shell_env_value(value)));
+    }
+    
+    ShellCommand { command_line, env }
+}
+
+#[cfg(not(windows))]
+fn env_reference(name: &str) -> String {
+    format!("\"${}\"", name)
+}
+
+#[cfg(windows)]
+fn env_reference(name: &str) -> String {
+    format!("\"%{}%\"", name)
+}
+
+#[cfg(not(windows))]
+fn shell_env_value(value: &str) -> String {
+    value.to_string()
+}
+
+/// 확장된 값이 따옴표를 닫지 못하도록 큰따옴표를 뺍니다 (Windows 경로에는 쓸 수 없는 문자)
+#[cfg(windows)]
+fn shell_env_value(value: &str) -> String {
+    value.replace('"', "")
+}
+
+/// 명령을 플랫폼 셸로 실행할 Command를 만듭니다
+fn shell_command(command: &ShellCommand) -> tokio::process::Command {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.args(["/C", &command.command_line]);
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.args(["-c", &command.command_line]);
+        shell
+    };
+    shell.envs(command.env.iter().map(|(name, value)| (*name, value.as_str())));
+    shell
+}
+
+async fn execute_post_hook(command: &ShellCommand) -> anyhow::Result<std::process::Output> {
+    let child = shell_command(command)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    
+    tokio::time::timeout(std::time::Duration::from_secs(POST_HOOK_TIMEOUT_SECS), child.wait_with_output())
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {} seconds", POST_HOOK_TIMEOUT_SECS))?
+        .map_err(Into::into)
+}
+
+/// 훅 명령의 자리표시자를 채웁니다 (값은 환경 변수로 넘겨 파일 이름으로 명령이 바뀌지 않음)
+fn render_post_hook_command(template: &str, history_id: &str, result_path: &str, file_name: &str) -> ShellCommand {
+    render_shell_template(template, &[
+        ("{history_id}", "WHISPER_GUI_HISTORY_ID", history_id),
+        ("{result_path}", "WHISPER_GUI_RESULT_PATH", result_path),
+        ("{file_name}", "WHISPER_GUI_FILE_NAME", file_name),
+    ])
+}
+
+/// 번역 명령의 최대 실행 시간 (긴 자막을 외부 API로 보내는 경우를 고려해 넉넉하게)
+const TRANSLATOR_TIMEOUT_SECS: u64 = 600;
+
+/// 번역 명령에 텍스트를 stdin으로 넘기고 stdout을 번역문으로 받습니다
+async fn run_translator(command: &ShellCommand, input: &str) -> anyhow::Result<String> {
+    use tokio::io::AsyncWriteExt;
+    
+    let mut child = shell_command(command)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    
+    // 출력을 읽기 전에 입력을 모두 쓰면 파이프가 차서 멈출 수 있으므로 따로 씀
+    let mut stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("Failed to open translator stdin"))?;
+    let input = input.to_string();
+    let writer = tokio::spawn(async move {
+        stdin.write_all(input.as_bytes()).await.ok();
+    });
+    
+    let output = tokio::time::timeout(std::time::Duration::from_secs(TRANSLATOR_TIMEOUT_SECS), child.wait_with_output())
+        .await
+        .map_err(|_| anyhow::anyhow!("Translator timed out after {} seconds", TRANSLATOR_TIMEOUT_SECS))??;
+    writer.await.ok();
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Translator exited with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn render_translator_command(template: &str, source_language: &str, target_language: &str) -> ShellCommand {
+    render_shell_template(template, &[
+        ("{source_language}", "WHISPER_GUI_SOURCE_LANGUAGE", source_language),
+        ("{target_language}", "WHISPER_GUI_TARGET_LANGUAGE", target_language),
+    ])
+}
+
+type ProcessLog = Option<std::sync::Arc<std::sync::Mutex<std::fs::File>>>;
+
+/// 프로세스 로그 파일을 추가 모드로 엽니다 (실패해도 변환은 계속 진행)
+fn open_process_log(path: &std::path::Path) -> ProcessLog {
+    match std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Some(std::sync::Arc::new(std::sync::Mutex::new(file))),
+        Err(e) => {
+            eprintln!("Failed to open process log {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+fn append_process_log(log: &ProcessLog, line: &str) {
+    use std::io::Write;
+    
+    if let Some(file) = log {
+        writeln!(file.lock().unwrap(), "{}", line).ok();
+    }
+}
+
+/// Unix에서 우선순위별로 적용할 nice 값 (High는 권한이 없으면 실패하고 기본 우선순위로 실행)
+#[cfg(unix)]
+fn apply_niceness(pid: u32, priority: ProcessPriority) {
+    let niceness = match priority {
+        ProcessPriority::Low => 10,
+        ProcessPriority::Normal => return,
+        ProcessPriority::High => -5,
+    };
+    
+    // SAFETY: 방금 생성한 자식 프로세스의 pid에 대해 우선순위만 변경
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, niceness) };
+    if result != 0 {
+        eprintln!("Failed to set process priority {:?}: {}", priority, std::io::Error::last_os_error());
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_niceness(_pid: u32, _priority: ProcessPriority) {}
+
+/// Windows에서는 생성 시 우선순위 클래스를 지정
+#[cfg(windows)]
+fn apply_priority_class(command: &mut tokio::process::Command, priority: ProcessPriority) {
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+    const ABOVE_NORMAL_PRIORITY_CLASS: u32 = 0x0000_8000;
+    match priority {
+        ProcessPriority::Low => { command.creation_flags(BELOW_NORMAL_PRIORITY_CLASS); }
+        ProcessPriority::Normal => {}
+        ProcessPriority::High => { command.creation_flags(ABOVE_NORMAL_PRIORITY_CLASS); }
+    }
+}
+
+#[cfg(not(windows))]
+fn apply_priority_class(_command: &mut tokio::process::Command, _priority: ProcessPriority) {}
+
+/// 실행 중인 변환 목록 (history_id → 진행 상황)
+type ActiveTranscriptions = std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, ActiveTranscription>>>;
+
+/// transcribe_append가 진행 중인 history_id 목록
+type ActiveAppends = std::sync::Arc<std::sync::Mutex<std::collections::HashSet<String>>>;
+
+/// 시작을 기다리는 변환 대기열
+#[derive(Default)]
+struct TranscriptionQueue {
+    jobs: std::sync::Mutex<std::collections::VecDeque<QueuedTranscription>>,
+    starting: std::sync::Mutex<Vec<String>>, // 대기열에서 꺼내 입력을 준비 중인 history_id (프로세스 시작 전)
+    paused: std::sync::atomic::AtomicBool,   // pause_queue로 멈추면 새 변환을 시작하지 않음
+    changed: tokio::sync::Notify,   // 대기열에 추가되거나 실행 중인 변환이 끝나면 알림
+}
+
+struct QueuedTranscription {
+    history_id: String,
+    config: WhisperConfig,          // 기본 옵션이 적용된 설정
+}
+
+impl TranscriptionQueue {
+    fn is_starting(&self, history_id: &str) -> bool {
+        self.starting.lock().unwrap().iter().any(|id| id == history_id)
+    }
+    
+    /// 프로세스를 시작했거나 시작하지 못한 항목을 `starting`에서 빼고 대기열 처리 태스크를 깨웁니다
+    fn finish_starting(&self, history_id: &str) {
+        self.starting.lock().unwrap().retain(|id| id != history_id);
+        self.changed.notify_one();
+    }
+}
+
+/// 서비스 잠금 없이 변환 하나를 시작하는 데 필요한 경로와 공유 상태 (WhisperService::transcription_runner로 만듦)
+#[derive(Clone)]
+struct TranscriptionRunner {
+    models_path: PathBuf,
+    binary_path: Option<PathBuf>,
+    history_service: HistoryService,
+    media_tools: MediaTools,
+    active_transcriptions: ActiveTranscriptions,
+    transcription_queue: std::sync::Arc<TranscriptionQueue>,
+    resource_sampler: ResourceSampler,
+}
+
+impl TranscriptionRunner {
+    /// 히스토리 항목이 만들어진 변환의 whisper 프로세스를 시작합니다 (완료 처리는 백그라운드 태스크에서)
+    async fn run(
+        &self,
+        history_id: String,
+        mut config: WhisperConfig,
+        app_handle: tauri::AppHandle
+    ) -> anyhow::Result<String> {
+        let model_path = self.models_path.join(format!("ggml-{}.bin", config.model));
+        let input_path = PathBuf::from(&config.input_file);
+        let settings = SettingsService::new().load();
+
+        let binary_path = match self.binary_path.clone() {
+            Some(path) => path,
+            None => {
+                // 히스토리 실패로 마크
+                self.history_service.mark_history_failed(
+                    &history_id, 
+                    "Whisper binary not found".to_string()
+                ).await.ok();
+                return Err(anyhow::anyhow!("Whisper binary not found"));
+            }
+        };
+
+        // whisper가 안정적으로 읽지 못하는 입력은 16kHz 모노 WAV로 변환해 사용 (히스토리에는 원본 경로 유지)
+        let preprocessed = config.audio_track.is_some() || requires_transcoding(&config.input_file);
+        if preprocessed {
+            app_handle.emit_all("transcription-progress", &ProgressInfo {
+                progress: 0.0,
+                current_time: None,
+                message: "입력 파일 변환 중...".to_string(),
+                phase: TranscriptionPhase::Preprocessing,
+            }).ok();
+        }
+        let temp_input = match self.prepare_input_file(&config.input_file, &history_id, config.audio_track).await {
+            Ok(temp_input) => temp_input,
+            Err(e) => {
+                self.history_service.mark_history_failed(&history_id, e.to_string()).await.ok();
+                return Err(e);
+            }
+        };
+        if let Some(temp_input) = &temp_input {
+            config.input_file = temp_input.to_string_lossy().to_string();
+        }
+
+        // 언어가 auto이고 감지된 언어에 대한 규칙이 있으면 해당 옵션을 덮어씀
+        let language_rules = settings.language_rules;
+        let auto_language = config.options.get("language").is_some_and(|language| language == "auto");
+        if auto_language && !language_rules.is_empty() {
+            match detect_language(&binary_path, &model_path, &config.input_file).await {
+                Some(language) => {
+                    if let Some(rule_options) = language_rules.get(&language) {
+                        config.options.insert("language".to_string(), language.clone());
+                        config.options.extend(rule_options.clone());
+                        self.history_service.record_language_rule(&history_id, &language, config.options.clone()).await.ok();
+                        app_handle.emit_all(
+                            "transcription-log",
+                            &format!("Detected language '{}', applying language rule", language)
+                        ).ok();
+                    }
+                }
+                None => eprintln!("Language detection failed, language rules were not applied"),
+            }
+        }
+
+        // 히스토리 결과 디렉토리 생성 (files 서브디렉토리 포함)
+        let results_dir = self.history_service.get_history_directory(&history_id);
+        let files_dir = results_dir.join("files");
+        tokio::fs::create_dir_all(&files_dir).await?;
+        
+        // 출력 경로를 files 디렉토리로 설정하여 중복 저장 방지
+        let output_file_base = files_dir.join("result");
+        let initial_threads = config.options.get("threads")
+            .and_then(|t| t.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_THREAD_COUNT);
+        
+        let args = build_transcription_args(&model_path, &config.input_file, &output_file_base, &config.options);
+
+        // 실행될 명령어 로그 출력
+        eprintln!("Executing whisper command:");
+        eprintln!("Binary: {:?}", binary_path);
+        eprintln!("Args: {:?}", args);
+
+        let audio_position = std::sync::Arc::new(std::sync::Mutex::new(0.0f32));
+        let segment_timeline: SegmentTimeline = Default::default();
+        // ffprobe로 길이를 알 수 없으면 whisper가 stderr에 출력하는 처리 정보에서 채움
+        let audio_duration: AudioDuration = std::sync::Arc::new(std::sync::Mutex::new(
+            probe_media_duration(&self.media_tools, std::path::Path::new(&config.input_file)).await
+        ));
+        self.active_transcriptions.lock().unwrap().insert(
+            history_id.clone(),
+            ActiveTranscription::new(audio_position.clone(), audio_duration.clone()),
+        );
+        // 입력을 변환했으면 앞 구간을 변환 단계 몫으로 남겨 둠
+        let phase_weights = PhaseWeights::new(preprocessed);
+        let monitor = ProcessMonitor {
+            history_id: history_id.clone(),
+            audio_position: audio_position.clone(),
+            audio_duration,
+            segment_timeline: segment_timeline.clone(),
+            phase_weights,
+            log_path: self.history_service.get_history_directory(&history_id).join(HISTORY_LOG_FILE),
+        };
+        let priority = config.process_priority;
+        if priority != ProcessPriority::Normal {
+            self.history_service.record_process_priority(&history_id, priority).await.ok();
+        }
+        let cmd = WhisperService::spawn_whisper_process(&binary_path, &args, &app_handle, monitor.clone(), priority)
+            .map_err(|e| {
+                // 프로세스 시작 실패 시 히스토리 실패로 마크
+                self.active_transcriptions.lock().unwrap().remove(&history_id);
+                self.transcription_queue.changed.notify_one();
+                if let Some(temp_input) = &temp_input {
+                    std::fs::remove_file(temp_input).ok();
+                }
+                let history_service = self.history_service.clone();
+                let history_id = history_id.clone();
+                let error_message = e.to_string();
+                tokio::spawn(async move {
+                    history_service.mark_history_failed(&history_id, error_message).await.ok();
+                });
+                e
+            })?;
+        if let Some(active) = self.active_transcriptions.lock().unwrap().get_mut(&history_id) {
+            active.pid = cmd.id();
+        }
+        WhisperService::spawn_resource_monitor(
+            history_id.clone(),
+            self.active_transcriptions.clone(),
+            self.resource_sampler.clone(),
+            app_handle.clone(),
+        );
+
+        // 프로세스 완료 처리
+        let app_handle_final = app_handle;
+        let history_service = self.history_service.clone();
+        let history_id_final = history_id.clone();
+        let input_path_final = input_path.clone();
+        let config_final = config.clone();
+        let binary_path_final = binary_path.clone();
+        let active_transcriptions = self.active_transcriptions.clone();
+        let transcription_queue = self.transcription_queue.clone();
+        let cancel = self.active_transcriptions.lock().unwrap().get(&history_id)
+            .map(|active| active.cancel.clone())
+            .unwrap_or_default();
+        
+        tokio::spawn(async move {
+            let mut cmd = cmd;
+            let mut threads = initial_threads;
+            let mut attempts = Vec::new();
+            let mut cancelled = false;
+            
+            // adaptive_threads가 켜져 있으면 진행이 비정상적으로 느릴 때 스레드 수를 줄여 재시도
+            let wait_result = loop {
+                let started = std::time::Instant::now();
+                let outcome = tokio::select! {
+                    outcome = async {
+                        if config_final.adaptive_threads {
+                            wait_or_abort_if_slow(&mut cmd, &audio_position, &config_final.model, started).await
+                        } else {
+                            cmd.wait().await.map(Some)
+                        }
+                    } => Some(outcome),
+                    _ = cancel.notified() => None,
+                };
+                
+                // 사용자가 취소하면 프로세스를 강제 종료 (Unix SIGKILL / Windows TerminateProcess)
+                let outcome = match outcome {
+                    Some(outcome) => outcome,
+                    None => {
+                        cancelled = true;
+                        cmd.kill().await.ok();
+                        break cmd.wait().await;
+                    }
+                };
+                
+                match outcome {
+                    Ok(None) if threads > 1 => {
+                        cmd.kill().await.ok();
+                        attempts.push(ThreadAttempt { threads, aborted_as_slow: true });
+                        threads = (threads / 2).max(1);
+                        
+                        app_handle_final.emit_all(
+                            "transcription-log",
+                            &format!("Progress is too slow, retrying with {} threads...", threads)
+                        ).ok();
+                        
+                        let mut retry_options = config_final.options.clone();
+                        retry_options.insert("threads".to_string(), threads.to_string());
+                        let retry_args = build_transcription_args(
+                            &model_path, &config_final.input_file, &output_file_base, &retry_options
+                        );
+                        
+                        *audio_position.lock().unwrap() = 0.0;
+                        segment_timeline.lock().unwrap().clear();
+                        if let Some(active) = active_transcriptions.lock().unwrap().get_mut(&history_id_final) {
+                            active.restart();
+                        }
+                        match WhisperService::spawn_whisper_process(
+                            &binary_path_final, &retry_args, &app_handle_final, monitor.clone(), priority,
+                        ) {
+                            Ok(retry_cmd) => {
+                                if let Some(active) = active_transcriptions.lock().unwrap().get_mut(&history_id_final) {
+                                    active.pid = retry_cmd.id();
+                                }
+                                cmd = retry_cmd;
+                            }
+                            Err(e) => break Err(e),
+                        }
+                    }
+                    // 스레드를 더 줄일 수 없으면 끝까지 기다림
+                    Ok(None) => break cmd.wait().await,
+                    Ok(Some(status)) => break Ok(status),
+                    Err(e) => break Err(e),
+                }
+            };
+            
+            active_transcriptions.lock().unwrap().remove(&history_id_final);
+            // 빈 자리가 생겼으므로 대기 중인 변환을 시작하도록 알림
+            transcription_queue.changed.notify_one();
+            if let Some(temp_input) = &temp_input {
+                tokio::fs::remove_file(temp_input).await.ok();
+            }
+            
+            if config_final.adaptive_threads {
+                attempts.push(ThreadAttempt { threads, aborted_as_slow: false });
+                history_service.record_thread_attempts(&history_id_final, attempts).await.ok();
+            }
+            
+            let timings = compute_segment_timings(&segment_timeline.lock().unwrap());
+            if !timings.is_empty() {
+                history_service.record_segment_timings(&history_id_final, timings).await.ok();
+            }
+            
+            if cancelled {
+                // 종료 상태로 "Process failed"를 덮어쓰지 않도록 여기서 처리를 끝냄 (그때까지의 결과는 부분 결과로 보존)
+                if let Err(e) = WhisperService::recover_partial_results(&history_service, &history_id_final).await {
+                    eprintln!("Failed to recover partial results for {}: {}", history_id_final, e);
+                    history_service.mark_history_cancelled(&history_id_final, Vec::new()).await.ok();
+                }
+                app_handle_final.emit_all("transcription-cancelled", &history_id_final).ok();
+                return;
+            }
+            
+            match wait_result {
+                Ok(status) => {
+                    if status.success() {
+                        app_handle_final.emit_all("transcription-progress", &ProgressInfo {
+                            progress: phase_weights.overall(TranscriptionPhase::Finalizing, 0.0),
+                            current_time: None,
+                            message: "결과 저장 중...".to_string(),
+                            phase: TranscriptionPhase::Finalizing,
+                        }).ok();
+                        
+                        // 결과 파일들 수집 및 히스토리에 저장
+                        match WhisperService::collect_and_save_result_files(
+                            &history_service,
+                            &history_id_final,
+                            &input_path_final,
+                            &config_final.options,
+                            &config_final.export_formats,
+                            config_final.preserve_source_mtime,
+                        ).await {
+                            Ok(_) => {
+                                app_handle_final.emit_all("transcription-complete", &history_id_final).ok();
+                                // 훅 실패는 로그에만 남기고 변환 상태에는 영향을 주지 않음
+                                run_post_hook(&history_service, &history_id_final).await;
+                            }
+                            Err(e) => {
+                                history_service.mark_history_failed(
+                                    &history_id_final, 
+                                    format!("Failed to save results: {}", e)
+                                ).await.ok();
+                                app_handle_final.emit_all("transcription-error", &e.to_string()).ok();
+                            }
+                        }
+                    } else if let Some(signal) = termination_signal(&status) {
+                        // 취소 요청 없이 외부에서 중단된 경우 실패로 처리하되 그때까지의 결과는 부분 결과로 보존
+                        let message = format!("Transcription process was terminated by signal {}", signal);
+                        history_service.mark_history_interrupted(
+                            &history_id_final,
+                            WhisperService::partial_result_files(&history_service, &history_id_final),
+                            message.clone(),
+                        ).await.ok();
+                        app_handle_final.emit_all("transcription-error", &message).ok();
+                    } else {
+                        history_service.mark_history_failed(
+                            &history_id_final, 
+                            "Transcription process failed".to_string()
+                        ).await.ok();
+                        app_handle_final.emit_all("transcription-error", "Process failed").ok();
+                    }
+                }
+                Err(e) => {
+                    history_service.mark_history_failed(
+                        &history_id_final, 
+                        e.to_string()
+                    ).await.ok();
+                    app_handle_final.emit_all("transcription-error", &e.to_string()).ok();
+                }
+            }
+        });
+
+        Ok(history_id)
+    }
+    
+    /// 변환이 필요한 입력이면 ffmpeg로 16kHz 모노 WAV 임시 파일을 만들어 경로를 반환합니다
+    ///
+    /// 오디오 트랙을 지정하면 해당 트랙(또는 모든 트랙을 합친 결과)만 추출하고 선택 결과를 히스토리에 기록합니다.
+    /// 변환이 필요 없으면 None을 반환하고, 임시 파일은 변환이 끝난 뒤 호출자가 지웁니다.
+    async fn prepare_input_file(
+        &self,
+        input_file: &str,
+        history_id: &str,
+        audio_track: Option<AudioTrackPreference>,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        if audio_track.is_none() && !requires_transcoding(input_file) {
+            return Ok(None);
+        }
+        
+        let mut track_args = Vec::new();
+        if let Some(preference) = audio_track {
+            let audio_stream_count = probe_audio_stream_count(&self.media_tools, std::path::Path::new(input_file)).await.unwrap_or(1);
+            let (args, fallback) = audio_track_args(preference, audio_stream_count);
+            if fallback {
+                eprintln!("{} has {} audio track(s), using the first track", input_file, audio_stream_count);
+            }
+            self.history_service.record_audio_track(history_id, AudioTrackChoice {
+                preference,
+                audio_stream_count,
+                fallback,
+            }).await.ok();
+            track_args = args;
+        }
+        
+        let temp_path = std::env::temp_dir().join(format!("whisper-gui-{}.wav", history_id));
+        eprintln!("Transcoding {} to {:?}", input_file, temp_path);
+        
+        let output = tokio::process::Command::new(&self.media_tools.ffmpeg)
+            .args(transcode_args(input_file, &track_args, &temp_path))
+            .output()
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => anyhow::anyhow!(
+                    "ffmpeg is required to convert this input file. Install ffmpeg or set its path in settings"
+                ),
+                _ => anyhow::anyhow!("Failed to run ffmpeg: {}", e),
+            })?;
+        
+        if !output.status.success() {
+            tokio::fs::remove_file(&temp_path).await.ok();
+            return Err(anyhow::anyhow!(
+                "Failed to convert input file: {}", String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        
+        Ok(Some(temp_path))
+    }
+}
+
+/// `batch-progress` 확인 간격 (초)
+const BATCH_PROGRESS_POLL_INTERVAL_SECS: u64 = 2;
+
+/// 끝나지 않은 일괄 변환 항목이 대기열과 실행 목록 어디에도 없는 채로 이 시간이 지나면 확인을 멈춤 (초)
+const BATCH_MONITOR_UNTRACKED_TIMEOUT_SECS: u64 = 600;
+
+/// 일괄 변환의 히스토리 상태를 주기적으로 확인해 바뀔 때마다 `batch-progress`를 보냅니다
+///
+/// 완료/실패/취소되었거나 삭제된 히스토리는 끝난 것으로 셉니다. 재시작 등으로 상태가 갱신되지 않는 항목만 남아
+/// `BATCH_MONITOR_UNTRACKED_TIMEOUT_SECS` 동안 추적되지 않으면 끝난 것으로 보고 멈춥니다.
+fn spawn_batch_monitor(
+    history_service: HistoryService,
+    active_transcriptions: ActiveTranscriptions,
+    transcription_queue: std::sync::Arc<TranscriptionQueue>,
+    batch: Vec<(String, String)>, // (history_id, 파일 이름)
+    app_handle: tauri::AppHandle,
+) {
+    tokio::spawn(async move {
+        let total = batch.len();
+        let mut last_reported: Option<(usize, Option<String>)> = None;
+        let mut untracked_since: Option<std::time::Instant> = None;
+        
+        loop {
+            let mut completed = 0;
+            let mut current_file = None;
+            let mut pending = Vec::new();
+            for (history_id, file_name) in &batch {
+                match history_service.get_history(history_id).await.map(|history| history.status) {
+                    Ok(TranscriptionStatus::Running) => {
+                        if current_file.is_none() {
+                            current_file = Some(file_name.clone());
+                        }
+                        pending.push(history_id);
+                    }
+                    Ok(TranscriptionStatus::Queued) | Ok(TranscriptionStatus::Idle) => pending.push(history_id),
+                    _ => completed += 1,
+                }
+            }
+            
+            // 남은 항목 중 하나라도 대기열이나 실행 목록에 있으면 아직 진행 중
+            let tracked = pending.iter().any(|history_id| {
+                active_transcriptions.lock().unwrap().contains_key(history_id.as_str())
+                    || transcription_queue.is_starting(history_id)
+                    || transcription_queue.jobs.lock().unwrap().iter().any(|job| &job.history_id == *history_id)
+            });
+            let timed_out = if tracked || pending.is_empty() {
+                untracked_since = None;
+                false
+            } else {
+                untracked_since.get_or_insert_with(std::time::Instant::now).elapsed()
+                    >= std::time::Duration::from_secs(BATCH_MONITOR_UNTRACKED_TIMEOUT_SECS)
+            };
+            if timed_out {
+                eprintln!("Batch monitor stopped: {} unfinished batch entries are no longer queued or running", pending.len());
+                completed = total;
+                current_file = None;
+            }
+            
+            let state = (completed, current_file.clone());
+            if last_reported.as_ref() != Some(&state) {
+                app_handle.emit_all("batch-progress", &BatchProgress { completed, total, current_file }).ok();
+                last_reported = Some(state);
+            }
+            
+            if completed >= total {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(BATCH_PROGRESS_POLL_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// 예약된 변환 목록 파일 이름 (데이터 디렉토리 기준)
+const SCHEDULED_JOBS_FILE: &str = "scheduled.json";
+
+/// 예약 시각 확인 간격 (초)
+const SCHEDULER_POLL_INTERVAL_SECS: u64 = 30;
+
+/// 예약 시각을 UTC로 변환합니다 (형식이 잘못되면 None이므로 실행되지 않고 목록 끝에 정렬됨)
+fn scheduled_time(run_at: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(run_at).ok().map(|time| time.with_timezone(&chrono::Utc))
+}
+
+/// 예약 시각이 지났는지 확인합니다 (형식이 잘못된 예약은 실행하지 않음)
+fn is_scheduled_due(run_at: &str, now: chrono::DateTime<chrono::Utc>) -> bool {
+    scheduled_time(run_at).is_some_and(|time| time <= now)
+}
+
+/// 실행 시각이 지난 예약을 주기적으로 대기열에 넣는 태스크 (앱 시작 시 한 번 실행)
+pub async fn run_transcription_scheduler(
+    service: std::sync::Arc<tokio::sync::Mutex<WhisperService>>,
+    app_handle: tauri::AppHandle,
+) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(SCHEDULER_POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(e) = service.lock().await.dispatch_due_scheduled(&app_handle).await {
+            eprintln!("Failed to dispatch scheduled transcriptions: {}", e);
+        }
+    }
+}
+
+/// 대기열을 처리하는 태스크 (앱 시작 시 한 번 실행)
+///
+/// 변환 완료 태스크가 서비스 잠금 없이 다음 변환을 시작할 수 없으므로 알림을 받아 여기서 시작합니다.
+pub async fn run_transcription_queue(
+    service: std::sync::Arc<tokio::sync::Mutex<WhisperService>>,
+    app_handle: tauri::AppHandle,
+) {
+    let queue = {
+        let service = service.lock().await;
+        if let Err(e) = service.history_service.fail_interrupted_queued().await {
+            eprintln!("Failed to reconcile queued history entries: {}", e);
+        }
+        service.transcription_queue.clone()
+    };
+    
+    loop {
+        queue.changed.notified().await;
+        dispatch_queued_transcriptions(&service, &app_handle).await;
+    }
+}
+
+/// 빈 자리가 있는 만큼 대기열 앞쪽부터 변환을 시작합니다
+///
+/// 서비스 잠금은 대기열에서 꺼내고 실행에 필요한 것을 복사할 때만 잡고,
+/// 입력 변환(ffmpeg)과 언어 감지, 프로세스 시작은 각 변환의 태스크에서 잠금 없이 진행합니다.
+async fn dispatch_queued_transcriptions(
+    service: &std::sync::Arc<tokio::sync::Mutex<WhisperService>>,
+    app_handle: &tauri::AppHandle,
+) {
+    let (jobs, runner) = {
+        let service = service.lock().await;
+        (service.take_startable_jobs(), service.transcription_runner())
+    };
+    
+    for job in jobs {
+        let runner = runner.clone();
+        let app_handle = app_handle.clone();
+        tokio::spawn(async move {
+            runner.history_service.update_history_status(&job.history_id, TranscriptionStatus::Running).await.ok();
+            if let Err(e) = runner.run(job.history_id.clone(), job.config, app_handle.clone()).await {
+                eprintln!("Failed to start queued transcription {}: {}", job.history_id, e);
+                runner.history_service.mark_history_failed(&job.history_id, e.to_string()).await.ok();
+                app_handle.emit_all("transcription-error", &e.to_string()).ok();
+            }
+            runner.transcription_queue.finish_starting(&job.history_id);
+        });
+    }
+}
+
+/// 프로세스 자원 사용량 측정기 (변환 간 공유)
+type ResourceSampler = std::sync::Arc<std::sync::Mutex<sysinfo::System>>;
+
+/// `transcription-resources` 이벤트 간격 (초)
+const RESOURCE_SAMPLE_INTERVAL_SECS: u64 = 2;
+
+/// PID의 현재 RSS(bytes)와 CPU 사용률(%)을 측정합니다 (프로세스가 없으면 None)
+///
+/// CPU 사용률은 같은 측정기의 직전 측정 이후 사용량이므로 첫 측정은 0에 가깝고,
+/// 여러 코어를 쓰면 100%를 넘을 수 있습니다.
+fn sample_process_usage(system: &mut sysinfo::System, pid: u32) -> Option<(u64, f32)> {
+    use sysinfo::{PidExt, ProcessExt};
+    
+    let pid = sysinfo::Pid::from_u32(pid);
+    if !system.refresh_process(pid) {
+        return None;
+    }
+    system.process(pid).map(|process| (process.memory(), process.cpu_usage()))
+}
+
+fn sample_transcription_resources(sampler: &ResourceSampler, history_id: &str, pid: Option<u32>) -> TranscriptionResources {
+    let usage = pid.and_then(|pid| sample_process_usage(&mut sampler.lock().unwrap(), pid));
+    
+    TranscriptionResources {
+        history_id: history_id.to_string(),
+        pid,
+        running: usage.is_some(),
+        rss_bytes: usage.map(|(rss, _)| rss),
+        cpu_percent: usage.map(|(_, cpu)| cpu),
+    }
+}
+
+/// ETA 평활화에 사용할 최근 진행률 표본 수
+const ETA_SAMPLE_WINDOW: usize = 8;
+
+/// 실행 중인 변환 하나의 진행 상황
+struct ActiveTranscription {
+    started: std::time::Instant,
+    audio_position: std::sync::Arc<std::sync::Mutex<f32>>, // stdout에서 파싱한 현재 오디오 위치 (초)
+    audio_duration: AudioDuration,
+    samples: std::collections::VecDeque<(f64, f64)>,       // (경과 초, 진행률)
+    pid: Option<u32>,                                      // 현재 whisper 프로세스 (재시도하면 바뀜)
+    cancel: std::sync::Arc<tokio::sync::Notify>,           // cancel_transcription 요청을 대기 태스크에 전달
+}
+
+impl ActiveTranscription {
+    fn new(audio_position: std::sync::Arc<std::sync::Mutex<f32>>, audio_duration: AudioDuration) -> Self {
+        Self {
+            started: std::time::Instant::now(),
+            audio_position,
+            audio_duration,
+            samples: std::collections::VecDeque::new(),
+            pid: None,
+            cancel: std::sync::Arc::new(tokio::sync::Notify::new()),
+        }
+    }
+    
+    /// 스레드 수를 줄여 재시도하는 경우 처음부터 다시 측정
+    fn restart(&mut self) {
+        self.started = std::time::Instant::now();
+        self.samples.clear();
+    }
+    
+    fn progress(&self) -> f64 {
+        match *self.audio_duration.lock().unwrap() {
+            Some(duration) if duration > 0.0 => {
+                (*self.audio_position.lock().unwrap() as f64 / duration).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+    
+    fn record_sample(&mut self, elapsed_seconds: f64, progress: f64) {
+        self.samples.push_back((elapsed_seconds, progress));
+        while self.samples.len() > ETA_SAMPLE_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+    
+    /// 최근 표본 구간의 진행 속도로 남은 시간을 계산하고, 구간 내 진행이 없으면 전체 평균 속도를 사용
+    fn estimate_remaining(&self, elapsed_seconds: f64, progress: f64) -> Option<f64> {
+        if progress <= 0.0 {
+            return None;
+        }
+        if progress >= 1.0 {
+            return Some(0.0);
+        }
+        
+        let windowed_rate = match (self.samples.front(), self.samples.back()) {
+            (Some(&(first_t, first_p)), Some(&(last_t, last_p))) if last_t > first_t && last_p > first_p => {
+                Some((last_p - first_p) / (last_t - first_t))
+            }
+            _ => None,
+        };
+        let rate = windowed_rate.unwrap_or(progress / elapsed_seconds.max(f64::EPSILON));
+        Some((1.0 - progress) / rate)
+    }
+}
+
+/// whisper stdout의 "[00:00:01.000 --> 00:00:04.000]  text" 라인에서 오디오 구간과 텍스트를 파싱합니다
+fn parse_segment_line(line: &str) -> Option<SubtitleSegment> {
+    let inner = line.trim_start().strip_prefix('[')?;
+    let (range, text) = inner.split_once(']')?;
+    let (start, end) = range.split_once("-->")?;
+    Some(SubtitleSegment {
+        start: parse_subtitle_timestamp(start)?,
+        end: parse_subtitle_timestamp(end)?,
+        text: text.trim().to_string(),
+    })
+}
+
+/// 세그먼트 출력 시점 기록에서 세그먼트별 처리 시간을 계산합니다
+///
+/// 각 세그먼트의 처리 시간은 직전 세그먼트 출력 이후 경과 시간입니다. 첫 세그먼트에는
+/// 모델 로딩 시간이 포함되고, whisper는 30초 윈도우 단위로 출력하므로 같은 윈도우의
+/// 뒤쪽 세그먼트는 0에 가까운 값이 나옵니다.
+pub fn compute_segment_timings(timeline: &[(f64, f64, f64)]) -> Vec<SegmentTiming> {
+    let mut previous_emitted = 0.0;
+    timeline.iter()
+        .map(|&(start, end, emitted_at)| {
+            let processing_seconds = (emitted_at - previous_emitted).max(0.0);
+            previous_emitted = emitted_at;
+            SegmentTiming { start, end, processing_seconds }
+        })
+        .collect()
+}
+
+/// 프로세스가 스스로 종료하지 않고 시그널로 중단되었으면 그 시그널 번호를 반환합니다
+///
+/// 취소 여부는 cancel_transcription 요청으로만 판단하므로 여기서 찾은 시그널은 실패 메시지에만 사용합니다.
+#[cfg(unix)]
+fn termination_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn termination_signal(_status: &std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+/// 재귀 탐색 시 찾을 실행 파일 이름 (우선순위 순)
+const WHISPER_BINARY_NAMES: [&str; 2] = ["whisper-cli", "main"];
+
+/// quantize 도구 실행 파일 이름 (최신 버전은 whisper- 접두사가 붙음)
+const QUANTIZE_BINARY_NAMES: [&str; 2] = ["whisper-quantize", "quantize"];
+
+/// whisper.cpp quantize 도구가 지원하는 양자화 형식
+const QUANTIZE_TYPES: &[&str] = &["q4_0", "q4_1", "q5_0", "q5_1", "q8_0"];
+
+/// 모델 이름이 양자화 변형인지 확인합니다 (예: "large-v3-q5_0")
+fn is_quantized_model_name(model_name: &str) -> bool {
+    QUANTIZE_TYPES.iter().any(|quant_type| model_name.ends_with(&format!("-{}", quant_type)))
+}
+
+/// build/ 아래 재귀 탐색 최대 깊이
+const BINARY_SEARCH_MAX_DEPTH: usize = 6;
+
+/// 디렉토리 아래에서 이름이 일치하는 실행 파일을 찾습니다 (CMakeFiles 등 중간 산출물은 건너뜀)
+fn find_executable_recursive(dir: &std::path::Path, name: &str, depth: usize) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+    let entries: Vec<_> = std::fs::read_dir(dir).ok()?.filter_map(|e| e.ok()).collect();
+    
+    // 같은 깊이의 파일을 먼저 확인해 얕은 위치를 우선
+    for entry in &entries {
+        let path = entry.path();
+        if entry.file_name() == file_name.as_str() && path.is_file() && is_executable(&path) {
+            return Some(path);
+        }
+    }
+    
+    if depth == 0 {
+        return None;
+    }
+    
+    entries.iter()
+        .filter(|entry| entry.path().is_dir() && entry.file_name() != "CMakeFiles")
+        .find_map(|entry| find_executable_recursive(&entry.path(), name, depth - 1))
+}
+
+/// 서비스 잠금 없이 모델 파일을 검증합니다 (큰 모델의 SHA256 계산 동안 다른 명령과 대기열이 멈추지 않도록)
+#[derive(Clone)]
+pub struct ModelValidator {
+    installer: std::sync::Arc<WhisperInstaller>,
+    models_path: PathBuf,
+    whisper_binary: Option<PathBuf>,
+}
+
+impl ModelValidator {
+    pub async fn validate(&self, model_name: &str) -> anyhow::Result<bool> {
+        self.check_model(model_name, true).await
+    }
+
+    /// 직접 만든(양자화한) 모델을 검증합니다
+    ///
+    /// 로컬에서 만든 파일은 같은 이름의 공식 모델 SHA256과 일치할 수 없으므로 크기와 로딩만 확인합니다.
+    pub async fn validate_local(&self, model_name: &str) -> anyhow::Result<bool> {
+        self.check_model(model_name, false).await
+    }
+
+    async fn check_model(&self, model_name: &str, verify_checksum: bool) -> anyhow::Result<bool> {
+        let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
+        
+        if !model_path.exists() {
+            return Ok(false);
+        }
+        
+        // 파일 크기 체크
+        let metadata = tokio::fs::metadata(&model_path).await?;
+        let file_size = metadata.len();
+        
+        // 예상 최소 파일 크기 (MB)
+        let min_expected_size = match model_name {
+            m if m.starts_with("tiny") => 39 * 1024 * 1024,      // ~39MB
+            m if m.starts_with("base") => 142 * 1024 * 1024,     // ~142MB  
+            m if m.starts_with("small") => 244 * 1024 * 1024,    // ~244MB
+            m if m.starts_with("medium") => 769 * 1024 * 1024,   // ~769MB
+            m if m.starts_with("large") => 1550 * 1024 * 1024,   // ~1550MB
+            _ => 10 * 1024 * 1024, // 기본 최소값 10MB
+        };
+        // 양자화 모델은 f16 원본보다 훨씬 작음 (q4_0 기준 약 1/3.5)
+        let min_expected_size = if is_quantized_model_name(model_name) { min_expected_size / 4 } else { min_expected_size };
+        
+        if file_size < min_expected_size {
+            eprintln!("Model {} appears to be incomplete: {} bytes (expected >= {} bytes)", 
+                     model_name, file_size, min_expected_size);
+            return Ok(false);
+        }
+        
+        // 알려진 공식 모델이면 SHA256 비교 (체크섬 표를 받지 못했으면 건너뜀)
+        if verify_checksum {
+            match self.installer.verify_model_checksum(model_name, &model_path).await {
+                Ok(Some(false)) => {
+                    eprintln!("Model {} failed SHA256 verification (corrupted or truncated download)", model_name);
+                    return Ok(false);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Checksum verification failed for {}: {}", model_name, e),
+            }
+        }
+        
+        // whisper.cpp로 모델 검증 시도 (간단한 헤더 체크)
+        if let Err(e) = self.test_model_loading(model_name).await {
+            eprintln!("Model {} failed validation test: {}", model_name, e);
+            return Ok(false);
+        }
+        
+        Ok(true)
+    }
+
+    /// 다운로드된 모든 모델을 검증합니다
+    pub async fn validate_all(&self, model_names: Vec<String>) -> Vec<ModelValidation> {
+        let mut validations = Vec::new();
+        for model_name in model_names {
+            let valid = self.validate(&model_name).await.unwrap_or_else(|e| {
+                eprintln!("Failed to validate model {}: {}", model_name, e);
+                false
+            });
+            validations.push(ModelValidation { model_name, valid });
+        }
+        validations
+    }
+
+    /// 다시 받은 모델을 검증합니다
+    async fn verify_repaired(&self, model_name: &str) -> anyhow::Result<()> {
+        if !self.validate(model_name).await? {
+            return Err(anyhow::anyhow!("Model repair failed - downloaded model is still invalid"));
+        }
+        
+        eprintln!("Model {} successfully repaired", model_name);
+        Ok(())
+    }
+
+    async fn test_model_loading(&self, model_name: &str) -> anyhow::Result<()> {
+        use tokio::process::Command as TokioCommand;
+        
+        let model_path = self.models_path.join(format!("ggml-{}.bin", model_name));
+        
+        let binary_path = self.whisper_binary.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Whisper binary not found"))?;
+
+        // 모델 로딩 테스트 (매우 짧은 더미 파일로)
+        let output = TokioCommand::new(binary_path)
+            .args([
+                "-m", &model_path.to_string_lossy(),
+                "--help"  // 단순히 help만 표시하여 모델 로딩 확인
+            ])
+            .output()
+            .await?;
+            
+        // stderr에서 모델 로딩 에러 체크
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("ERROR not all tensors loaded") || 
+           stderr.contains("failed to load model") ||
+           stderr.contains("failed to initialize whisper context") {
+            return Err(anyhow::anyhow!("Model validation failed: {}", stderr));
+        }
+        
+        Ok(())
+    }
+}
+
+/// 검증 결과에서 실패한 모델을 다시 받을 수 있는 모델과 받을 곳을 알 수 없는 모델로 나눕니다
+///
+/// 모델 목록(다운로드 스크립트 또는 기본 목록)에 없는 모델은 받을 곳을 알 수 없으므로 복구 불가로 분류합니다.
+fn plan_model_repairs(validations: Vec<ModelValidation>, known_models: &[String]) -> (Vec<String>, Vec<String>) {
+    validations.into_iter()
+        .filter(|validation| !validation.valid)
+        .map(|validation| validation.model_name)
+        .partition(|model_name| known_models.contains(model_name))
+}
+
+/// 검증에 실패한 모델만 제한된 동시 실행 수로 다시 다운로드합니다
+///
+/// 서비스 잠금은 모델 목록과 검증에 필요한 경로를 복사할 때만 잡고, 검증과 다운로드는 잠금 없이 진행합니다.
+pub async fn repair_all_invalid_models(
+    service: std::sync::Arc<tokio::sync::Mutex<WhisperService>>,
+    app_handle: tauri::AppHandle,
+) -> anyhow::Result<ModelRepairSummary> {
+    let (validator, downloaded, known_models) = {
+        let service = service.lock().await;
+        (service.model_validator(), service.list_downloaded_models().await?, service.list_available_models().await?)
+    };
+    let (repairable, unrepairable) = plan_model_repairs(validator.validate_all(downloaded).await, &known_models);
+    
+    let summary = run_model_repairs(
+        repairable,
+        unrepairable,
+        |model_name| {
+            let validator = validator.clone();
+            async move {
+                redownload_model(&validator.installer, &validator.models_path, &model_name).await?;
+                validator.verify_repaired(&model_name).await
+            }
+        },
+        |progress| {
+            app_handle.emit_all("model-repair-progress", progress).ok();
+        },
+    ).await;
+    
+    Ok(summary)
+}
+
+/// 각 모델의 복구를 `MODEL_REPAIR_WORKERS`개씩 동시에 실행하고 진행 상황을 알린 뒤 결과를 모읍니다
+async fn run_model_repairs<F, Fut>(
+    repairable: Vec<String>,
+    unrepairable: Vec<String>,
+    repair: F,
+    report: impl Fn(&ModelRepairProgress),
+) -> ModelRepairSummary
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<()>>,
+{
+    use futures_util::StreamExt;
+    
+    for model_name in &unrepairable {
+        report(&ModelRepairProgress {
+            model_name: model_name.clone(),
+            status: ModelRepairStatus::Unrepairable,
+            error: None,
+        });
+    }
+    
+    let (repair, report) = (&repair, &report);
+    let outcomes: Vec<ModelRepairProgress> = futures_util::stream::iter(repairable)
+        .map(|model_name| async move {
+            report(&ModelRepairProgress {
+                model_name: model_name.clone(),
+                status: ModelRepairStatus::Repairing,
+                error: None,
+            });
+            
+            let outcome = match repair(model_name.clone()).await {
+                Ok(()) => ModelRepairProgress { model_name, status: ModelRepairStatus::Repaired, error: None },
+                Err(e) => ModelRepairProgress { model_name, status: ModelRepairStatus::Failed, error: Some(e.to_string()) },
+            };
+            report(&outcome);
+            outcome
+        })
+        .buffer_unordered(MODEL_REPAIR_WORKERS)
+        .collect()
+        .await;
+    
+    let mut summary = ModelRepairSummary { unrepairable, ..Default::default() };
+    for outcome in outcomes {
+        match outcome.status {
+            ModelRepairStatus::Repaired => summary.repaired.push(outcome.model_name),
+            _ => summary.failed.push(outcome),
+        }
+    }
+    summary
+}
+
+/// 손상된 모델 파일을 지우고 다시 받습니다 (체크섬이 틀리면 파일을 지우고 한 번 더 받음)
+async fn redownload_model(installer: &WhisperInstaller, models_path: &std::path::Path, model_name: &str) -> anyhow::Result<()> {
+    // 손상된 파일을 지운 뒤 다시 받기 전까지도 진행 중으로 셈
+    let _download = installer.track_download();
+    eprintln!("Attempting to repair model: {}", model_name);
+    
+    // 기존 손상된 파일 삭제
+    let model_path = models_path.join(format!("ggml-{}.bin", model_name));
+    if model_path.exists() {
+        tokio::fs::remove_file(&model_path).await?;
+        eprintln!("Removed corrupted model file: {:?}", model_path);
+    }
+    
+    let mut attempt = 1;
+    while let Err(e) = installer.download_model(model_name).await {
+        if attempt >= MODEL_REPAIR_DOWNLOAD_ATTEMPTS {
+            return Err(e);
+        }
+        eprintln!("Model {} download attempt {} failed: {}", model_name, attempt, e);
+        attempt += 1;
+    }
+    
+    Ok(())
+}
+
+/// 동시에 다시 다운로드할 최대 모델 수 (모델 파일이 커서 대역폭을 나눠 쓰지 않도록 작게 유지)
+const MODEL_REPAIR_WORKERS: usize = 2;
+
+/// 복구 시 모델을 다시 받는 최대 횟수 (다운로드 실패 또는 체크섬 불일치)
+const MODEL_REPAIR_DOWNLOAD_ATTEMPTS: usize = 2;
+
+/// 이어서 변환할 최소 새 오디오 길이 (초)
+const MIN_APPEND_SECONDS: f64 = 1.0;
+
+/// whisper.cpp의 기본 스레드 수
+const DEFAULT_THREAD_COUNT: u32 = 4;
+
+/// 느린 진행 판정 전 최소 관찰 시간 (초)
+const SLOW_PROGRESS_MIN_OBSERVATION_SECS: f64 = 60.0;
+
+/// 기대 속도 대비 이 비율보다 느리면 비정상으로 판정
+const SLOW_PROGRESS_RATIO: f64 = 0.1;
+
+/// whisper는 30초 윈도우 단위로 세그먼트를 출력하므로 한 윈도우만큼의 지연은 허용
+const WHISPER_WINDOW_SECS: f64 = 30.0;
+
+/// `--detect-language`로 언어만 감지합니다 (stderr의 "auto-detected language: en (p = 0.97)" 파싱)
+async fn detect_language(binary_path: &std::path::Path, model_path: &std::path::Path, input_file: &str) -> Option<String> {
+    let output = tokio::process::Command::new(binary_path)
+        .args(["-m", &model_path.to_string_lossy(), "-f", input_file, "--detect-language"])
+        .output()
+        .await
+        .ok()?;
+    
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stderr.lines()
+        .find_map(|line| line.split_once("auto-detected language:"))
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .map(|language| language.to_lowercase())
+}
+
+/// 결과 파일들의 수정 시간을 원본 미디어의 수정 시간으로 맞춥니다 (실패해도 결과는 유지)
+fn copy_source_mtime(source: &std::path::Path, result_files: &[(PathBuf, String)]) {
+    let source_mtime = match std::fs::metadata(source) {
+        Ok(metadata) => filetime::FileTime::from_last_modification_time(&metadata),
+        Err(e) => {
+            eprintln!("Failed to read source modification time {:?}: {}", source, e);
+            return;
+        }
+    };
+    
+    for (path, _) in result_files {
+        if let Err(e) = filetime::set_file_mtime(path, source_mtime) {
+            eprintln!("Failed to set modification time on {:?}: {}", path, e);
+        }
+    }
+}
+
+/// whisper.cpp에 넘기기 전에 WAV로 변환해야 하는 입력인지 확인합니다
+///
+/// whisper.cpp가 안정적으로 읽는 것은 WAV뿐이므로, 동영상 컨테이너나 opus/AAC(M4A) 같은 입력은
+/// 모두 ffmpeg로 16kHz 모노 WAV를 만들어 사용합니다.
+fn requires_transcoding(input_file: &str) -> bool {
+    let passthrough_extensions = ["wav"];
+    
+    !std::path::Path::new(input_file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| passthrough_extensions.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// 입력을 16kHz 모노 16bit PCM WAV로 변환하는 ffmpeg 인자 (track_args는 오디오 트랙 선택 인자)
+fn transcode_args(input_file: &str, track_args: &[String], output_path: &std::path::Path) -> Vec<String> {
+    let mut args: Vec<String> = ["-nostdin", "-hide_banner", "-loglevel", "error", "-y", "-i", input_file]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+    args.extend(track_args.iter().cloned());
+    args.extend(["-vn", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"].iter().map(|arg| arg.to_string()));
+    args.push(output_path.to_string_lossy().to_string());
+    args
+}
+
+/// 출력 형식 옵션이 하나도 없으면 기본 형식들의 출력 플래그를 채웁니다 (기본 형식도 없으면 srt)
+fn apply_default_output_formats(
+    options: &mut std::collections::HashMap<String, String>,
+    default_formats: &[String],
+) {
+    if options.keys().any(|key| key.starts_with("output-")) {
+        return;
+    }
+    
+    let mut flags: Vec<&str> = whisper_output_formats()
+        .filter(|(_, format)| default_formats.iter().any(|f| f == format))
+        .map(|(option_key, _)| option_key)
+        .collect();
+    if flags.is_empty() {
+        flags.push("output-srt");
+    }
+    
+    for flag in flags {
+        options.insert(flag.to_string(), String::new());
+    }
+}
+
+/// `max-len` 옵션 값 (없거나 0이면 whisper.cpp와 같이 제한 없음으로 보고 None)
+fn max_segment_length(options: &std::collections::HashMap<String, String>) -> Option<usize> {
+    options.get("max-len")
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|max_len| *max_len > 0)
+}
+
+/// `max-len`이 있으면 단어 경계에서 자르고 토큰 타이밍이 담긴 JSON도 함께 출력하도록 옵션을 채웁니다
+///
+/// 토큰 오프셋은 --output-json-full(whisper.cpp v1.5.0 이상)에서만 나오며,
+/// 이전 버전이라 JSON에 토큰이 없으면 whisper.cpp가 만든 SRT를 그대로 사용합니다.
+fn apply_word_timing_options(options: &mut std::collections::HashMap<String, String>) {
+    if max_segment_length(options).is_none() {
+        return;
+    }
+    
+    for flag in ["split-on-word", "output-json", "output-json-full"] {
+        options.entry(flag.to_string()).or_default();
+    }
+}
+
+/// `max-len` 변환이면 JSON 토큰 타이밍으로 result.srt를 단어 묶음 단위 큐로 다시 씁니다
+async fn rewrite_word_level_srt(
+    files_dir: &std::path::Path,
+    options: &std::collections::HashMap<String, String>,
+) -> anyhow::Result<()> {
+    let max_len = match max_segment_length(options) {
+        Some(max_len) => max_len,
+        None => return Ok(()),
+    };
+    
+    let srt_path = files_dir.join("result.srt");
+    let json_path = files_dir.join("result.json");
+    if !srt_path.exists() || !json_path.exists() {
+        return Ok(());
+    }
+    
+    let content = tokio::fs::read_to_string(&json_path).await?;
+    match parse_whisper_json_words(&content)? {
+        Some(lines) if !lines.is_empty() => {
+            tokio::fs::write(&srt_path, convert_words_to_srt(&lines, max_len)).await?;
+        }
+        _ => eprintln!("JSON output has no token offsets, keeping whisper.cpp SRT for max-len {}", max_len),
+    }
+    
+    Ok(())
+}
+
+/// 변환 인자 목록을 구성합니다
+fn build_transcription_args(
+    model_path: &std::path::Path,
+    input_file: &str,
+    output_file_base: &std::path::Path,
+    options: &std::collections::HashMap<String, String>,
+) -> Vec<String> {
+    let mut args = vec![
+        "-m".to_string(), 
+        model_path.to_string_lossy().to_string(),
+        "-f".to_string(), 
+        input_file.to_string()
+    ];
+    
+    // --output-file 옵션으로 베이스 경로 지정 (확장자 제외)
+    args.push("--output-file".to_string());
+    args.push(output_file_base.to_string_lossy().to_string());
+    
+    // 출력 형식별 플래그 설정
+    let mut has_output_format = false;
+    for (key, value) in options {
+        if key.starts_with("output-") {
+            // output-srt, output-txt 등은 플래그로만 사용
+            args.push(format!("--{}", key));
+            has_output_format = true;
+        } else if value.is_empty() {
+            args.push(format!("--{}", key));
+        } else {
+            args.push(format!("--{}", key));
+            args.push(value.clone());
+        }
+    }
+    
+    // 기본 SRT 출력을 위한 설정 (사용자가 지정하지 않았을 경우)
+    if !has_output_format {
+        args.push("--output-srt".to_string());
+    }
+    
+    args
+}
+
+/// 모델별 CPU 기준 기대 처리 속도 (오디오 초 / 실제 초)
+fn expected_realtime_factor(model_name: &str) -> f64 {
+    match model_name {
+        m if m.starts_with("tiny") => 10.0,
+        m if m.starts_with("base") => 6.0,
+        m if m.starts_with("small") => 2.5,
+        m if m.starts_with("medium") => 1.0,
+        m if m.contains("turbo") => 1.0,
+        m if m.starts_with("large") => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// 경과 시간 대비 처리된 오디오 위치가 기대 속도보다 비정상적으로 느린지 판정합니다
+pub fn is_progress_too_slow(elapsed_secs: f64, audio_position_secs: f64, model_name: &str) -> bool {
+    if elapsed_secs < SLOW_PROGRESS_MIN_OBSERVATION_SECS {
+        return false;
+    }
+    
+    let minimum_expected_position = expected_realtime_factor(model_name) * SLOW_PROGRESS_RATIO * elapsed_secs;
+    audio_position_secs + WHISPER_WINDOW_SECS < minimum_expected_position
+}
+
+/// 프로세스 종료를 기다리되, 진행이 비정상적으로 느리면 None을 반환합니다
+async fn wait_or_abort_if_slow(
+    cmd: &mut tokio::process::Child,
+    audio_position: &std::sync::Arc<std::sync::Mutex<f32>>,
+    model_name: &str,
+    started: std::time::Instant,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+    
+    loop {
+        tokio::select! {
+            status = cmd.wait() => return status.map(Some),
+            _ = interval.tick() => {
+                let position = *audio_position.lock().unwrap() as f64;
+                if is_progress_too_slow(started.elapsed().as_secs_f64(), position, model_name) {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// GUI 바이너리 자체의 빌드 정보를 반환합니다 (설치된 whisper.cpp 버전과는 별개)
+pub fn get_app_info() -> AppInfo {
+    AppInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        target_triple: env!("WHISPER_GUI_BUILD_TARGET").to_string(),
+        build_profile: env!("WHISPER_GUI_BUILD_PROFILE").to_string(),
+        whisper_cpp_repo: WHISPER_CPP_REPO_URL.to_string(),
+        whisper_cpp_ref: WHISPER_CPP_PINNED_REF.map(|r| r.to_string()),
     }
 }
 
 fn convert_to_srt(transcription: &str) -> String {
-    let lines: Vec<&str> = transcription.lines().collect();
-    let mut srt_content = String::new();
-    let mut subtitle_index = 1;
+    segments_to_srt(&segments_or_estimated(transcription))
+}
+
+/// 스타일 옵션을 WebVTT 큐 설정 문자열로 변환합니다 (옵션이 없으면 빈 문자열)
+pub fn vtt_cue_settings(style: &VttStyleOptions) -> String {
+    let mut settings = Vec::new();
     
-    for (i, line) in lines.iter().enumerate() {
-        if line.trim().is_empty() {
+    if let Some(position) = style.position {
+        settings.push(match position {
+            VttPosition::Top => "line:0",
+            VttPosition::Middle => "line:50%",
+            VttPosition::Bottom => "line:-1",
+        });
+    }
+    
+    if let Some(align) = style.align {
+        settings.push(match align {
+            VttAlign::Start => "align:start",
+            VttAlign::Center => "align:center",
+            VttAlign::End => "align:end",
+            VttAlign::Left => "align:left",
+            VttAlign::Right => "align:right",
+        });
+    }
+    
+    settings.join(" ")
+}
+
+/// 이미 타임스탬프가 있는 SRT/VTT나 whisper.cpp 콘솔 출력이면 그 타이밍을 사용하고,
+/// 타임스탬프가 전혀 없는 일반 텍스트면 줄마다 5초 간격으로 추정합니다
+fn segments_or_estimated(transcription: &str) -> Vec<SubtitleSegment> {
+    let segments = parse_subtitle_segments(transcription);
+    if !segments.is_empty() {
+        return segments;
+    }
+    
+    let whisper_segments = parse_whisper_segments(transcription);
+    if !whisper_segments.is_empty() {
+        return whisper_segments.into_iter()
+            .map(|(start, end, text)| SubtitleSegment { start: start as f64, end: end as f64, text })
+            .collect();
+    }
+    
+    transcription.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| SubtitleSegment {
+            start: (i * 5) as f64,
+            end: (i * 5 + 4) as f64,
+            text: line.trim().to_string(),
+        })
+        .collect()
+}
+
+/// whisper.cpp 콘솔 출력("[00:01:23.456 --> 00:01:25.789]  text")을 (시작, 끝, 텍스트) 목록으로 파싱합니다
+///
+/// 타임스탬프가 없는 줄은 직전 큐의 텍스트에 이어붙입니다 (첫 큐보다 앞선 줄은 첫 큐에 붙임).
+fn parse_whisper_segments(transcription: &str) -> Vec<(f32, f32, String)> {
+    let mut segments: Vec<(f32, f32, String)> = Vec::new();
+    let mut leading_text: Vec<&str> = Vec::new();
+    
+    for line in transcription.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
             continue;
         }
         
-        let start_time = i * 5;
-        let end_time = start_time + 4;
+        let timed = trimmed.strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .and_then(|(timing, text)| {
+                let (start, end) = timing.split_once("-->")?;
+                Some((
+                    parse_timestamp_to_seconds(start.trim())?,
+                    parse_timestamp_to_seconds(end.trim())?,
+                    text.trim(),
+                ))
+            });
         
-        srt_content.push_str(&format!("{}\n", subtitle_index));
-        srt_content.push_str(&format!(
-            "{:02}:{:02}:{:02},000 --> {:02}:{:02}:{:02},000\n",
-            start_time / 3600, (start_time % 3600) / 60, start_time % 60,
-            end_time / 3600, (end_time % 3600) / 60, end_time % 60
+        match (timed, segments.last_mut()) {
+            (Some((start, end, text)), _) => {
+                leading_text.push(text);
+                segments.push((start, end, leading_text.join(" ")));
+                leading_text.clear();
+            }
+            (None, Some((_, _, text))) => {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(trimmed);
+            }
+            (None, None) => leading_text.push(trimmed),
+        }
+    }
+    
+    segments
+}
+
+/// 화자별로 색이 다른 스타일을 가진 ASS 자막을 만듭니다 (화자 정보가 없으면 기본 스타일 하나)
+fn convert_to_ass(transcription: &str) -> String {
+    speaker_segments_to_ass(&label_speakers(&segments_or_estimated(transcription)))
+}
+
+/// 세그먼트마다 타임스탬프를 붙인 Markdown 목록을 만듭니다
+///
+/// `base_media_url`이 있으면 타임스탬프를 `t=<초>` 파라미터가 붙은 링크로 만들고,
+/// 없으면 `[MM:SS]` 접두어만 붙입니다.
+fn convert_to_markdown(transcription: &str, base_media_url: Option<&str>) -> String {
+    let base_media_url = base_media_url.map(str::trim).filter(|url| !url.is_empty());
+    
+    segments_or_estimated(transcription).iter()
+        .map(|segment| {
+            // "0:05" → "00:05" (1시간 이상은 "1:02:03" 그대로)
+            let label = format_chapter_timestamp(segment.start);
+            let label = if label.len() < 5 { format!("0{}", label) } else { label };
+            let text = segment.text.replace('\n', " ");
+            
+            match base_media_url {
+                Some(url) => {
+                    let separator = if url.contains('?') { '&' } else { '?' };
+                    format!("- [[{}]]({}{}t={}) {}\n", label, url, separator, segment.start as u64, text)
+                }
+                None => format!("- [{}] {}\n", label, text),
+            }
+        })
+        .collect()
+}
+
+fn convert_to_vtt(transcription: &str) -> String {
+    segments_to_vtt(&segments_or_estimated(transcription))
+}
+
+fn convert_to_styled_vtt(transcription: &str, style: &VttStyleOptions) -> String {
+    segments_to_styled_vtt(&segments_or_estimated(transcription), style)
+}
+
+fn segments_to_styled_vtt(segments: &[SubtitleSegment], style: &VttStyleOptions) -> String {
+    let cue_settings = vtt_cue_settings(style);
+    let class = style.class.as_deref()
+        .map(|c| c.trim().trim_start_matches('.'))
+        .filter(|c| !c.is_empty());
+    
+    let mut vtt_content = String::from("WEBVTT\n\n");
+    
+    for segment in segments {
+        vtt_content.push_str(&format!(
+            "{} --> {}",
+            format_vtt_timestamp(segment.start),
+            format_vtt_timestamp(segment.end)
         ));
-        srt_content.push_str(&format!("{}\n\n", line.trim()));
+        if !cue_settings.is_empty() {
+            vtt_content.push(' ');
+            vtt_content.push_str(&cue_settings);
+        }
+        vtt_content.push('\n');
         
-        subtitle_index += 1;
+        match class {
+            Some(class) => vtt_content.push_str(&format!("<c.{}>{}</c>\n\n", class, segment.text)),
+            None => vtt_content.push_str(&format!("{}\n\n", segment.text)),
+        }
     }
     
-    srt_content
+    vtt_content
 }
 
 fn convert_to_fcpxml(transcription: &str) -> String {
-    let lines: Vec<&str> = transcription.lines().collect();
+    segments_to_fcpxml(&segments_or_estimated(transcription))
+}
+
+fn segments_to_fcpxml(segments: &[SubtitleSegment]) -> String {
     let mut fcpxml_content = String::new();
     
     fcpxml_content.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -762,22 +3656,21 @@ fn convert_to_fcpxml(transcription: &str) -> String {
                     <spine>
 "#);
 
-    for (i, line) in lines.iter().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let start_time = i * 5;
+    for (i, segment) in segments.iter().enumerate() {
+        // FCPXML 시간은 유리수 초로 표기 (밀리초 단위)
+        let start_millis = (segment.start.max(0.0) * 1000.0).round() as u64;
+        let duration_millis = ((segment.end - segment.start).max(0.0) * 1000.0).round() as u64;
         fcpxml_content.push_str(&format!(
-            r#"                        <title ref="r1" name="Subtitle {}" start="{}s" duration="4s">
+            r#"                        <title ref="r1" name="Subtitle {}" start="{}/1000s" duration="{}/1000s">
                             <text>
                                 <text-style ref="ts1">{}</text-style>
                             </text>
                         </title>
 "#,
             i + 1,
-            start_time,
-            line.trim()
+            start_millis,
+            duration_millis,
+            segment.text
         ));
     }
 
@@ -1029,55 +3922,18 @@ fn add_missing_common_options(options: &mut Vec<WhisperOption>) {
 }
 
 fn add_default_options(options: &mut Vec<WhisperOption>) {
-    let common_options = vec![
-        WhisperOption {
-            name: "output-txt".to_string(),
-            short_name: None,
-            description: "텍스트 파일 출력 생성".to_string(),
-            option_type: WhisperOptionType::Flag,
-            default_value: None,
-            possible_values: None,
-        },
-        WhisperOption {
-            name: "output-srt".to_string(),
-            short_name: None,
-            description: "SRT 자막 파일 출력 생성".to_string(),
-            option_type: WhisperOptionType::Flag,
-            default_value: None,
-            possible_values: None,
-        },
-        WhisperOption {
-            name: "output-vtt".to_string(),
-            short_name: None,
-            description: "WebVTT 자막 파일 출력 생성".to_string(),
-            option_type: WhisperOptionType::Flag,
-            default_value: None,
-            possible_values: None,
-        },
-        WhisperOption {
-            name: "output-csv".to_string(),
-            short_name: None,
-            description: "CSV 파일 출력 생성".to_string(),
-            option_type: WhisperOptionType::Flag,
-            default_value: None,
-            possible_values: None,
-        },
-        WhisperOption {
-            name: "output-json".to_string(),
-            short_name: None,
-            description: "JSON 파일 출력 생성".to_string(),
-            option_type: WhisperOptionType::Flag,
-            default_value: None,
-            possible_values: None,
-        },
-        WhisperOption {
-            name: "output-lrc".to_string(),
+    // 출력 형식 플래그는 형식 정의 테이블에서 생성
+    let output_options = EXPORT_FORMATS.iter()
+        .filter_map(|format| format.whisper_option.map(|name| WhisperOption {
+            name: name.to_string(),
             short_name: None,
-            description: "LRC 가사 파일 출력 생성".to_string(),
+            description: format.option_description.to_string(),
             option_type: WhisperOptionType::Flag,
             default_value: None,
             possible_values: None,
-        },
+        }));
+    
+    let common_options = output_options.chain(vec![
         WhisperOption {
             name: "language".to_string(),
             short_name: Some("l".to_string()),
@@ -1131,7 +3987,7 @@ fn add_default_options(options: &mut Vec<WhisperOption>) {
             default_value: None,
             possible_values: None,
         },
-    ];
+    ]);
     
     for default_option in common_options {
         if !options.iter().any(|opt| opt.name == default_option.name) {
@@ -1140,7 +3996,13 @@ fn add_default_options(options: &mut Vec<WhisperOption>) {
     }
 }
 
-pub fn parse_whisper_output_line(line: &str) -> Option<ProgressInfo> {
+/// 전체 길이를 모를 때 타임스탬프 진행률 계산에 사용하는 길이 (초)
+const FALLBACK_PROGRESS_DURATION_SECS: f32 = 300.0;
+
+/// whisper 출력 한 줄에서 진행 정보를 파싱합니다
+///
+/// 타임스탬프 줄은 `total_duration`(초)에 대한 비율로 진행률을 계산합니다.
+pub fn parse_whisper_output_line(line: &str, total_duration: Option<f64>) -> Option<ProgressInfo> {
     // whisper.cpp 타임스탬프 진행률 파싱 (예: [00:01:23.456 --> 00:01:25.789])
     if line.contains("[") && line.contains("-->") && line.contains("]") {
         if let Some(start) = line.find("[") {
@@ -1148,12 +4010,14 @@ pub fn parse_whisper_output_line(line: &str) -> Option<ProgressInfo> {
                 if let Some(_end) = line.find("]") {
                     let timestamp_part = &line[start+1..arrow_pos].trim();
                     if let Some(time_seconds) = parse_timestamp_to_seconds(timestamp_part) {
-                        // 임시로 시간을 기반으로 진행률 추정 (최대 300초 기준)
-                        let estimated_progress = (time_seconds / 300.0).min(1.0);
+                        let duration = total_duration
+                            .filter(|duration| *duration > 0.0)
+                            .map_or(FALLBACK_PROGRESS_DURATION_SECS, |duration| duration as f32);
                         return Some(ProgressInfo {
-                            progress: estimated_progress,
+                            progress: (time_seconds / duration).min(1.0),
                             current_time: Some(time_seconds),
                             message: line.to_string(),
+                            phase: TranscriptionPhase::Transcribing,
                         });
                     }
                 }
@@ -1171,6 +4035,7 @@ pub fn parse_whisper_output_line(line: &str) -> Option<ProgressInfo> {
                         progress: progress / 100.0,
                         current_time: None,
                         message: line.to_string(),
+                        phase: TranscriptionPhase::Transcribing,
                     });
                 }
             }
@@ -1183,6 +4048,7 @@ pub fn parse_whisper_output_line(line: &str) -> Option<ProgressInfo> {
             progress: 1.0,
             current_time: None,
             message: "처리 완료".to_string(),
+            phase: TranscriptionPhase::Finalizing,
         });
     }
     
@@ -1192,12 +4058,26 @@ pub fn parse_whisper_output_line(line: &str) -> Option<ProgressInfo> {
             progress: 0.1,
             current_time: None,
             message: "모델 로딩 중...".to_string(),
+            phase: TranscriptionPhase::LoadingModel,
         });
     }
     
     None
 }
 
+/// whisper의 처리 시작 줄에서 입력 길이(초)를 읽습니다
+///
+/// 예: `main: processing 'audio.wav' (176000 samples, 11.0 sec), 4 threads, ...`
+fn parse_processing_duration(line: &str) -> Option<f64> {
+    if !line.contains("processing") {
+        return None;
+    }
+    
+    let marker = " samples, ";
+    let rest = &line[line.rfind(marker)? + marker.len()..];
+    rest[..rest.find(" sec")?].trim().parse().ok()
+}
+
 fn parse_timestamp_to_seconds(timestamp: &str) -> Option<f32> {
     // 타임스탬프 형식: "00:01:23.456" → 초 단위로 변환
     let parts: Vec<&str> = timestamp.split(':').collect();
@@ -1208,4 +4088,666 @@ fn parse_timestamp_to_seconds(timestamp: &str) -> Option<f32> {
         }
     }
     None
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn test_config(input_file: &str) -> WhisperConfig {
+        serde_json::from_value(serde_json::json!({
+            "model": "base",
+            "input_file": input_file,
+            "options": {},
+        })).unwrap()
+    }
+    
+    fn test_data_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("whisper-gui-test-{}", uuid::Uuid::new_v4()))
+    }
+    
+    fn segment(start: f64, end: f64, text: &str) -> SubtitleSegment {
+        SubtitleSegment { start, end, text: text.to_string() }
+    }
+    
+    #[test]
+    fn get_queue_lists_queued_jobs_in_order() {
+        let service = WhisperService::with_data_dir(test_data_dir());
+        for history_id in ["first", "second", "third"] {
+            service.transcription_queue.jobs.lock().unwrap().push_back(QueuedTranscription {
+                history_id: history_id.to_string(),
+                config: test_config(&format!("{}.wav", history_id)),
+            });
+        }
+        
+        let queue = service.get_queue();
+        let order: Vec<(&str, usize)> = queue.iter()
+            .map(|entry| (entry.history_id.as_str(), entry.position))
+            .collect();
+        assert_eq!(order, vec![("first", 1), ("second", 2), ("third", 3)]);
+        assert!(queue.iter().all(|entry| matches!(entry.status, TranscriptionStatus::Queued)));
+        assert!(service.has_pending_transcriptions());
+    }
+    
+    #[test]
+    fn taken_jobs_stay_pending_until_started() {
+        let service = WhisperService::with_data_dir(test_data_dir());
+        for history_id in ["first", "second"] {
+            service.transcription_queue.jobs.lock().unwrap().push_back(QueuedTranscription {
+                history_id: history_id.to_string(),
+                config: test_config(&format!("{}.wav", history_id)),
+            });
+        }
+        
+        let taken = service.take_startable_jobs();
+        assert_eq!(taken[0].history_id, "first");
+        let queue = service.get_queue();
+        assert_eq!(queue[0].history_id, "first");
+        assert!(matches!(queue[0].status, TranscriptionStatus::Running));
+        assert!(service.cancel_transcription("first").is_err());
+        
+        for job in &taken {
+            service.transcription_queue.finish_starting(&job.history_id);
+        }
+        service.transcription_queue.jobs.lock().unwrap().clear();
+        assert!(!service.has_pending_transcriptions());
+    }
+    
+    #[test]
+    fn paused_queue_keeps_jobs_until_resumed() {
+        let service = WhisperService::with_data_dir(test_data_dir());
+        service.transcription_queue.jobs.lock().unwrap().push_back(QueuedTranscription {
+            history_id: "first".to_string(),
+            config: test_config("first.wav"),
+        });
+        
+        service.transcription_queue.paused.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(service.take_startable_jobs().is_empty());
+        let status = service.get_queue_status();
+        assert!(status.paused);
+        assert_eq!((status.queued, status.running), (1, 0));
+        
+        service.transcription_queue.paused.store(false, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(service.take_startable_jobs().len(), 1);
+        assert_eq!(service.get_queue_status().running, 1);
+    }
+    
+    #[tokio::test]
+    async fn list_scheduled_sorts_invalid_times_last() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let jobs: Vec<ScheduledTranscription> = ["not a time", "2030-01-02T00:00:00Z", "2030-01-01T00:00:00+09:00"]
+            .iter()
+            .map(|run_at| ScheduledTranscription {
+                id: run_at.to_string(),
+                config: test_config("a.wav"),
+                run_at: run_at.to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            })
+            .collect();
+        service.save_scheduled_jobs(&jobs).await.unwrap();
+        
+        let ids: Vec<String> = service.list_scheduled().await.unwrap().into_iter().map(|job| job.id).collect();
+        assert_eq!(ids, vec!["2030-01-01T00:00:00+09:00", "2030-01-02T00:00:00Z", "not a time"]);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    async fn history_with_partial_srt(history_service: &HistoryService) -> String {
+        let history = history_service.create_history_entry(
+            "talk.wav".to_string(),
+            PathBuf::from("talk.wav"),
+            "base".to_string(),
+            Default::default(),
+        ).await.unwrap();
+        let files_dir = history_service.get_history_directory(&history.id).join("files");
+        tokio::fs::write(files_dir.join("result.srt"), "1\n00:00:00,000 --> 00:00:01,000\nhello\n").await.unwrap();
+        history.id
+    }
+    
+    #[tokio::test]
+    async fn cancelled_run_keeps_partial_file() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let history_id = history_with_partial_srt(&service.history_service).await;
+        
+        let history = WhisperService::recover_partial_results(&service.history_service, &history_id).await.unwrap();
+        assert!(matches!(history.status, TranscriptionStatus::Cancelled));
+        assert!(history.results.iter().any(|result| result.format == "srt" && result.partial));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn interrupted_run_is_failed_but_keeps_partial_file() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let history_id = history_with_partial_srt(&service.history_service).await;
+        
+        let partial_files = WhisperService::partial_result_files(&service.history_service, &history_id);
+        let history = service.history_service
+            .mark_history_interrupted(&history_id, partial_files, "terminated by signal 9".to_string())
+            .await.unwrap();
+        assert!(matches!(history.status, TranscriptionStatus::Failed));
+        assert!(history.results.iter().any(|result| result.format == "srt" && result.partial));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn run_model_repairs_collects_outcomes_and_reports_each_model() {
+        let reported = std::sync::Mutex::new(Vec::new());
+        let summary = run_model_repairs(
+            vec!["base".to_string(), "small".to_string()],
+            vec!["custom".to_string()],
+            |model_name| async move {
+                if model_name == "small" {
+                    Err(anyhow::anyhow!("checksum mismatch"))
+                } else {
+                    Ok(())
+                }
+            },
+            |progress| reported.lock().unwrap().push(format!("{}:{:?}", progress.model_name, progress.status)),
+        ).await;
+        
+        assert_eq!(summary.repaired, vec!["base".to_string()]);
+        assert_eq!(summary.unrepairable, vec!["custom".to_string()]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].model_name, "small");
+        assert_eq!(summary.failed[0].error.as_deref(), Some("checksum mismatch"));
+        
+        let reported = reported.into_inner().unwrap();
+        assert_eq!(reported[0], "custom:Unrepairable");
+        for expected in ["base:Repairing", "base:Repaired", "small:Repairing", "small:Failed"] {
+            assert!(reported.iter().any(|event| event == expected), "missing {}", expected);
+        }
+    }
+    
+    #[test]
+    fn plan_model_repairs_splits_invalid_models_by_known_source() {
+        let validations = vec![
+            ModelValidation { model_name: "base".to_string(), valid: true },
+            ModelValidation { model_name: "small".to_string(), valid: false },
+            ModelValidation { model_name: "custom".to_string(), valid: false },
+        ];
+        let known_models = vec!["base".to_string(), "small".to_string()];
+        
+        let (repairable, unrepairable) = plan_model_repairs(validations, &known_models);
+        assert_eq!(repairable, vec!["small".to_string()]);
+        assert_eq!(unrepairable, vec!["custom".to_string()]);
+    }
+    
+    #[tokio::test]
+    async fn force_fail_history_refuses_tracked_transcriptions() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let history_id = history_with_partial_srt(&service.history_service).await;
+        service.history_service.update_history_status(&history_id, TranscriptionStatus::Running).await.unwrap();
+        
+        service.active_transcriptions.lock().unwrap().insert(
+            history_id.clone(),
+            ActiveTranscription::new(Default::default(), Default::default()),
+        );
+        let error = service.force_fail_history(&history_id, "stuck", false).await.unwrap_err();
+        assert!(error.to_string().contains("still running"));
+        
+        // 추적이 끝난 뒤에는 강제로 실패 처리됨
+        service.active_transcriptions.lock().unwrap().remove(&history_id);
+        let history = service.force_fail_history(&history_id, "stuck", false).await.unwrap();
+        assert!(matches!(history.status, TranscriptionStatus::Failed));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn render_post_hook_command_passes_values_as_env() {
+        let file_name = "a'; rm -rf ~; echo '%PATH%.wav";
+        let command = render_post_hook_command("notify {file_name} {history_id}", "id-1", "/tmp/result.txt", file_name);
+        
+        assert!(!command.command_line.contains(file_name));
+        assert!(!command.command_line.contains("{file_name}"));
+        assert_eq!(command.env, vec![
+            ("WHISPER_GUI_HISTORY_ID", "id-1".to_string()),
+            ("WHISPER_GUI_FILE_NAME", file_name.to_string()),
+        ]);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn post_hook_receives_file_name_literally() {
+        let file_name = "a'; echo injected; '$(id).wav";
+        let command = render_post_hook_command("printf %s {file_name}", "id-1", "", file_name);
+        
+        let output = execute_post_hook(&command).await.unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), file_name);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failing_post_hook_leaves_transcription_completed() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let history_id = history_with_partial_srt(&service.history_service).await;
+        let options: std::collections::HashMap<String, String> = [("output-srt".to_string(), String::new())].into();
+        WhisperService::collect_and_save_result_files(
+            &service.history_service, &history_id, &PathBuf::from("talk.wav"), &options, &[], false,
+        ).await.unwrap();
+        
+        for template in ["exit 3", "/nonexistent/whisper-gui-hook {result_path}"] {
+            run_post_hook_template(&service.history_service, &history_id, template).await;
+            
+            let history = service.history_service.get_history(&history_id).await.unwrap();
+            assert!(matches!(history.status, TranscriptionStatus::Completed));
+            assert!(history.error_message.is_none());
+            assert!(history.results.iter().any(|result| result.format == "srt"));
+        }
+        
+        let logs = service.history_service.get_history_logs(&history_id).await.unwrap();
+        assert!(logs.contains("[post-hook] exited with exit status: 3"));
+        assert!(logs.contains("[post-hook] exited with exit status: 127"));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn invalid_scheduled_time_is_never_due() {
+        let now = chrono::Utc::now();
+        assert!(!is_scheduled_due("garbage", now));
+        assert!(is_scheduled_due("2000-01-01T00:00:00Z", now));
+        assert!(!is_scheduled_due("2999-01-01T00:00:00Z", now));
+    }
+    
+    #[test]
+    fn app_info_reports_build_and_whisper_source() {
+        let info = get_app_info();
+        assert_eq!(info.app_version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.target_triple.is_empty());
+        assert!(!info.build_profile.is_empty());
+        assert_eq!(info.whisper_cpp_repo, WHISPER_CPP_REPO_URL);
+        assert_eq!(info.whisper_cpp_ref.as_deref(), WHISPER_CPP_PINNED_REF);
+    }
+    
+    #[test]
+    fn slow_progress_needs_observation_window() {
+        assert!(!is_progress_too_slow(SLOW_PROGRESS_MIN_OBSERVATION_SECS - 1.0, 0.0, "tiny"));
+        // base는 600초 동안 최소 360초 위치까지 와야 함 (윈도우 30초 여유)
+        assert!(is_progress_too_slow(600.0, 300.0, "base"));
+        assert!(!is_progress_too_slow(600.0, 340.0, "base"));
+        // large는 기대 속도가 낮아 같은 위치라도 느리다고 보지 않음
+        assert!(!is_progress_too_slow(600.0, 0.0, "large-v2"));
+    }
+    
+    #[test]
+    fn turbo_models_use_their_own_realtime_factor() {
+        assert_eq!(expected_realtime_factor("large-v3-turbo"), 1.0);
+        assert_eq!(expected_realtime_factor("large-v3"), 0.5);
+        assert_eq!(expected_realtime_factor("small.en-q5_1"), 2.5);
+        assert_eq!(expected_realtime_factor("custom"), 1.0);
+    }
+    
+    #[test]
+    fn vtt_cue_settings_join_position_and_align() {
+        assert_eq!(vtt_cue_settings(&VttStyleOptions::default()), "");
+        let style = VttStyleOptions {
+            position: Some(VttPosition::Top),
+            align: Some(VttAlign::End),
+            class: None,
+        };
+        assert_eq!(vtt_cue_settings(&style), "line:0 align:end");
+    }
+    
+    #[test]
+    fn styled_vtt_emits_setting_for_each_alignment() {
+        let segments = vec![segment(0.0, 1.0, "hello")];
+        let cases = [
+            (VttAlign::Start, "align:start"),
+            (VttAlign::Center, "align:center"),
+            (VttAlign::End, "align:end"),
+            (VttAlign::Left, "align:left"),
+            (VttAlign::Right, "align:right"),
+        ];
+        for (align, expected) in cases {
+            let style = VttStyleOptions { position: None, align: Some(align), class: None };
+            assert_eq!(vtt_cue_settings(&style), expected);
+            assert_eq!(
+                segments_to_styled_vtt(&segments, &style),
+                format!("WEBVTT\n\n00:00:00.000 --> 00:00:01.000 {}\nhello\n\n", expected)
+            );
+        }
+        
+        let positions = [(VttPosition::Top, "line:0"), (VttPosition::Middle, "line:50%"), (VttPosition::Bottom, "line:-1")];
+        for (position, expected) in positions {
+            let style = VttStyleOptions { position: Some(position), align: Some(VttAlign::Center), class: None };
+            assert_eq!(vtt_cue_settings(&style), format!("{} align:center", expected));
+        }
+    }
+    
+    #[test]
+    fn styled_vtt_wraps_text_in_normalized_class() {
+        let style = VttStyleOptions {
+            position: Some(VttPosition::Bottom),
+            align: None,
+            class: Some(" .speaker ".to_string()),
+        };
+        let segments = vec![segment(3661.5, 3662.25, "hello")];
+        assert_eq!(
+            segments_to_styled_vtt(&segments, &style),
+            "WEBVTT\n\n01:01:01.500 --> 01:01:02.250 line:-1\n<c.speaker>hello</c>\n\n"
+        );
+    }
+    
+    #[test]
+    fn segment_timings_measure_gaps_between_emissions() {
+        let timings = compute_segment_timings(&[(0.0, 4.0, 3.0), (4.0, 8.0, 3.0), (8.0, 12.0, 5.5)]);
+        let processing: Vec<f64> = timings.iter().map(|t| t.processing_seconds).collect();
+        assert_eq!(processing, vec![3.0, 0.0, 2.5]);
+        assert_eq!((timings[2].start, timings[2].end), (8.0, 12.0));
+        // 시계가 뒤로 가더라도 음수 처리 시간은 나오지 않음
+        assert_eq!(compute_segment_timings(&[(0.0, 1.0, 2.0), (1.0, 2.0, 1.0)])[1].processing_seconds, 0.0);
+    }
+    
+    #[test]
+    fn parse_segment_line_reads_whisper_stdout() {
+        let segment = parse_segment_line("[00:00:01.000 --> 00:00:04.500]   hello world").unwrap();
+        assert_eq!((segment.start, segment.end, segment.text.as_str()), (1.0, 4.5, "hello world"));
+        assert!(parse_segment_line("whisper_init_from_file: loading model").is_none());
+        assert!(parse_segment_line("[not a range] text").is_none());
+    }
+    
+    #[test]
+    fn markdown_links_timestamps_to_media_url() {
+        let srt = "1\n00:00:05,000 --> 00:00:07,000\nhello\nthere\n\n2\n00:01:05,000 --> 00:01:07,000\nbye\n";
+        assert_eq!(convert_to_markdown(srt, None), "- [00:05] hello there\n- [01:05] bye\n");
+        assert_eq!(
+            convert_to_markdown(srt, Some(" https://example.com/watch?v=abc ")),
+            "- [[00:05]](https://example.com/watch?v=abc&t=5) hello there\n- [[01:05]](https://example.com/watch?v=abc&t=65) bye\n"
+        );
+        // 빈 URL은 링크 없이 출력
+        assert_eq!(convert_to_markdown(srt, Some("  ")), convert_to_markdown(srt, None));
+    }
+    
+    #[test]
+    fn plain_text_gets_estimated_segments() {
+        let segments = segments_or_estimated("first\n\nsecond\n");
+        let texts: Vec<(f64, &str)> = segments.iter().map(|s| (s.start, s.text.as_str())).collect();
+        assert_eq!(texts, vec![(0.0, "first"), (10.0, "second")]);
+    }
+    
+    #[test]
+    fn retime_edited_text_keeps_timing_when_line_counts_match() {
+        let timed = vec![
+            segment(1.0, 2.0, "helo"),
+            segment(3.0, 4.0, "wrld"),
+        ];
+        let retimed = retime_edited_text("hello\n\n  world \n", &timed);
+        let cues: Vec<(f64, &str)> = retimed.iter().map(|s| (s.start, s.text.as_str())).collect();
+        assert_eq!(cues, vec![(1.0, "hello"), (3.0, "world")]);
+        
+        // 줄 수가 달라지면 기존 타이밍을 맞출 수 없으므로 추정 타이밍 사용
+        let estimated = retime_edited_text("one\ntwo\nthree", &timed);
+        assert_eq!(estimated.len(), 3);
+        assert_eq!(estimated[2].start, 10.0);
+    }
+    
+    async fn history_with_results(history_service: &HistoryService, results: &[(&str, &str)]) -> TranscriptionHistory {
+        let history = history_service.create_history_entry(
+            "talk.wav".to_string(),
+            PathBuf::from("talk.wav"),
+            "base".to_string(),
+            Default::default(),
+        ).await.unwrap();
+        let files_dir = history_service.get_history_directory(&history.id).join("files");
+        let mut files = Vec::new();
+        for (format, content) in results {
+            let path = files_dir.join(format!("result.{}", format));
+            tokio::fs::write(&path, content).await.unwrap();
+            files.push((path, format.to_string()));
+        }
+        history_service.register_existing_results(&history.id, files).await.unwrap()
+    }
+    
+    #[tokio::test]
+    async fn regeneration_source_is_the_most_recently_edited_result() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let history = history_with_results(&service.history_service, &[("txt", ""), ("srt", ""), ("vtt", "")]).await;
+        // vtt 파일은 디스크에 없으므로 후보에서 빠짐
+        tokio::fs::remove_file(&history.results[2].file_path).await.unwrap();
+        assert_eq!(select_regeneration_source(&history.results).await.unwrap().format, "srt");
+        
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tokio::fs::write(&history.results[0].file_path, "edited").await.unwrap();
+        assert_eq!(select_regeneration_source(&history.results).await.unwrap().format, "txt");
+        assert!(select_regeneration_source(&history.results[2..]).await.is_none());
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn regenerated_srt_and_vtt_agree_after_text_edit() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nhelo\n\n2\n00:00:03,000 --> 00:00:04,500\nwrld\n";
+        let history = history_with_results(&service.history_service, &[("txt", "helo\nwrld\n"), ("srt", srt)]).await;
+        
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tokio::fs::write(&history.results[0].file_path, "hello\nworld\n").await.unwrap();
+        let history = service.regenerate_exports(&history.id, &["srt".to_string(), "vtt".to_string()]).await.unwrap();
+        
+        let mut cues = Vec::new();
+        for format in ["srt", "vtt"] {
+            let result = history.results.iter().find(|r| r.format == format).unwrap();
+            let content = tokio::fs::read_to_string(&result.file_path).await.unwrap();
+            assert_eq!(result.file_size, content.len() as u64);
+            let segments: Vec<(f64, f64, String)> = parse_subtitle_segments(&content).into_iter()
+                .map(|s| (s.start, s.end, s.text))
+                .collect();
+            cues.push(segments);
+        }
+        assert_eq!(cues[0], vec![(1.0, 2.0, "hello".to_string()), (3.0, 4.5, "world".to_string())]);
+        assert_eq!(cues[0], cues[1]);
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn phase_weights_reserve_a_share_for_preprocessing() {
+        let weights = PhaseWeights::new(true);
+        assert!((weights.overall(TranscriptionPhase::Preprocessing, 1.0) - PREPROCESSING_PROGRESS_SHARE).abs() < 1e-6);
+        assert!((weights.overall(TranscriptionPhase::Transcribing, 0.5) - 0.575).abs() < 1e-6);
+        assert_eq!(weights.overall(TranscriptionPhase::Finalizing, 0.0), 1.0);
+        
+        // 변환 단계가 없으면 whisper 변환이 전체 구간을 사용하고, 범위 밖 값은 잘림
+        let weights = PhaseWeights::new(false);
+        assert_eq!(weights.overall(TranscriptionPhase::Transcribing, 0.5), 0.5);
+        assert_eq!(weights.overall(TranscriptionPhase::Transcribing, 1.5), 1.0);
+        assert_eq!(weights.overall(TranscriptionPhase::Transcribing, -1.0), 0.0);
+    }
+    
+    #[test]
+    fn queued_and_model_loading_phases_map_to_progress_range() {
+        let weights = PhaseWeights::new(true);
+        assert_eq!(weights.overall(TranscriptionPhase::Queued, 1.0), 0.0);
+        // 모델 로딩은 변환 구간을 이어받아 whisper 변환과 같은 구간을 사용
+        assert_eq!(
+            weights.overall(TranscriptionPhase::LoadingModel, 0.0),
+            weights.overall(TranscriptionPhase::Preprocessing, 1.0)
+        );
+        
+        let json = serde_json::to_string(&TranscriptionPhase::LoadingModel).unwrap();
+        assert_eq!(json, "\"LoadingModel\"");
+        assert!(matches!(serde_json::from_str(&json).unwrap(), TranscriptionPhase::LoadingModel));
+    }
+    
+    #[test]
+    fn result_files_take_the_source_mtime() {
+        let dir = test_data_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("talk.wav");
+        let result = dir.join("result.srt");
+        std::fs::write(&source, "").unwrap();
+        std::fs::write(&result, "").unwrap();
+        let source_mtime = filetime::FileTime::from_unix_time(1_600_000_000, 0);
+        filetime::set_file_mtime(&source, source_mtime).unwrap();
+        
+        // 없는 결과 파일이 섞여 있어도 나머지 파일은 처리됨
+        copy_source_mtime(&source, &[(dir.join("missing.txt"), "txt".to_string()), (result.clone(), "srt".to_string())]);
+        let result_mtime = filetime::FileTime::from_last_modification_time(&std::fs::metadata(&result).unwrap());
+        assert_eq!(result_mtime, source_mtime);
+        
+        std::fs::remove_dir_all(&dir).ok();
+    }
+    
+    #[test]
+    fn resources_report_running_only_for_live_processes() {
+        let sampler: ResourceSampler = std::sync::Arc::new(std::sync::Mutex::new(sysinfo::System::new()));
+        
+        let own = sample_transcription_resources(&sampler, "live", Some(std::process::id()));
+        assert!(own.running);
+        assert!(own.rss_bytes.is_some_and(|rss| rss > 0));
+        assert!(own.cpu_percent.is_some());
+        
+        // 아직 PID가 없거나 이미 종료된 프로세스는 사용량 없이 보고
+        let pending = sample_transcription_resources(&sampler, "pending", None);
+        assert!(!pending.running && pending.rss_bytes.is_none() && pending.cpu_percent.is_none());
+        assert!(!sample_transcription_resources(&sampler, "gone", Some(u32::MAX)).running);
+    }
+    
+    #[test]
+    fn default_output_formats_apply_only_without_explicit_formats() {
+        let mut options = std::collections::HashMap::new();
+        apply_default_output_formats(&mut options, &["txt".to_string(), "vtt".to_string()]);
+        let mut keys: Vec<&str> = options.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["output-txt", "output-vtt"]);
+        
+        let mut options = std::collections::HashMap::new();
+        apply_default_output_formats(&mut options, &[]);
+        assert!(options.contains_key("output-srt"));
+        
+        // 사용자가 형식을 지정했으면 기본 형식을 더하지 않음
+        let mut options: std::collections::HashMap<String, String> = [("output-json".to_string(), String::new())].into_iter().collect();
+        apply_default_output_formats(&mut options, &["txt".to_string()]);
+        assert_eq!(options.len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn failed_verification_is_repairable_only_for_official_models() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        
+        let official = service.verify_model_with_sample("base").await;
+        assert!(!official.passed && official.repairable);
+        assert!(official.error.unwrap().contains("Model not found"));
+        assert!(!service.verify_model_with_sample("my-finetune").await.repairable);
+        
+        // 모델이 있어도 샘플 오디오가 없으면 검증할 수 없음
+        tokio::fs::create_dir_all(&service.models_path).await.unwrap();
+        tokio::fs::write(service.models_path.join("ggml-base.bin"), b"ggml").await.unwrap();
+        let verification = service.verify_model_with_sample("base").await;
+        assert!(verification.error.unwrap().contains("Sample audio not found"));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn translator_reads_stdin_and_languages_from_env() {
+        let command = render_translator_command("printf '%s>%s:' {source_language} {target_language}; cat", "en", "ko");
+        assert_eq!(command.env, vec![
+            ("WHISPER_GUI_SOURCE_LANGUAGE", "en".to_string()),
+            ("WHISPER_GUI_TARGET_LANGUAGE", "ko".to_string()),
+        ]);
+        assert_eq!(run_translator(&command, "hello\n").await.unwrap(), "en>ko:hello\n");
+        
+        let failing = render_translator_command("echo broken >&2; exit 3", "en", "ko");
+        assert!(run_translator(&failing, "").await.unwrap_err().to_string().contains("broken"));
+    }
+    
+    #[tokio::test]
+    async fn enqueue_transcription_marks_history_queued() {
+        let data_dir = test_data_dir();
+        let service = WhisperService::with_data_dir(data_dir.clone());
+        assert!(service.enqueue_transcription(&test_config("talk.wav")).await.is_err());
+        
+        tokio::fs::create_dir_all(&service.models_path).await.unwrap();
+        tokio::fs::write(service.models_path.join("ggml-base.bin"), b"ggml").await.unwrap();
+        let history_id = service.enqueue_transcription(&test_config("talk.wav")).await.unwrap();
+        
+        let history = service.history_service.get_history(&history_id).await.unwrap();
+        assert!(matches!(history.status, TranscriptionStatus::Queued));
+        let queue = service.get_queue();
+        assert_eq!(queue.len(), 1);
+        assert_eq!((queue[0].history_id.as_str(), queue[0].position), (history_id.as_str(), 1));
+        
+        tokio::fs::remove_dir_all(&data_dir).await.ok();
+    }
+    
+    #[test]
+    fn only_wav_input_skips_transcoding() {
+        assert!(!requires_transcoding("talk.wav"));
+        assert!(!requires_transcoding("TALK.WAV"));
+        for input in ["song.mp3", "clip.mp4", "screen.mkv", "voice.opus", "no_extension"] {
+            assert!(requires_transcoding(input), "{} should be transcoded", input);
+        }
+    }
+    
+    #[test]
+    fn transcode_args_produce_16khz_mono_wav() {
+        let output = PathBuf::from("/tmp/whisper-gui-1.wav");
+        let args = transcode_args("song.mp3", &[], &output);
+        assert_eq!(args, vec![
+            "-nostdin", "-hide_banner", "-loglevel", "error", "-y", "-i", "song.mp3",
+            "-vn", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le", "/tmp/whisper-gui-1.wav",
+        ]);
+        
+        // 트랙 선택 인자는 입력 바로 뒤, 출력 형식 인자 앞에 들어감
+        let track_args = vec!["-map".to_string(), "0:a:1".to_string()];
+        let args = transcode_args("screen.mkv", &track_args, &output);
+        let input_at = args.iter().position(|arg| arg == "screen.mkv").unwrap();
+        assert_eq!(&args[input_at + 1..input_at + 3], track_args.as_slice());
+    }
+    
+    #[test]
+    fn m4a_voice_memos_are_transcoded_to_wav() {
+        assert!(requires_transcoding("Voice Memo.m4a"));
+        assert!(requires_transcoding("memo.M4A"));
+        
+        let args = transcode_args("memo.m4a", &[], &PathBuf::from("memo.wav"));
+        let input_at = args.iter().position(|arg| arg == "-i").unwrap();
+        assert_eq!(args[input_at + 1], "memo.m4a");
+        assert!(args.windows(2).any(|pair| pair == ["-c:a", "pcm_s16le"]));
+        assert_eq!(args.last().map(String::as_str), Some("memo.wav"));
+    }
+    
+    #[test]
+    fn processing_line_reports_input_duration() {
+        let line = "main: processing 'audio.wav' (176000 samples, 11.0 sec), 4 threads, 1 processors, lang = en, task = transcribe ...";
+        assert_eq!(parse_processing_duration(line), Some(11.0));
+        assert_eq!(parse_processing_duration("whisper_model_load: processing"), None);
+    }
+    
+    #[test]
+    fn timestamp_progress_uses_total_duration() {
+        let line = "[00:00:30.000 --> 00:00:35.000]  hello";
+        let progress = parse_whisper_output_line(line, Some(120.0)).unwrap();
+        assert_eq!((progress.progress, progress.current_time), (0.25, Some(30.0)));
+        assert_eq!(parse_whisper_output_line(line, Some(10.0)).unwrap().progress, 1.0);
+        
+        // 길이를 모르면 기본 길이를 기준으로 계산
+        let fallback = parse_whisper_output_line(line, None).unwrap();
+        assert_eq!(fallback.progress, (30.0 / FALLBACK_PROGRESS_DURATION_SECS).min(1.0));
+    }
+    
+    #[test]
+    fn output_lines_map_to_transcription_phases() {
+        let phase = |line: &str| parse_whisper_output_line(line, Some(60.0)).map(|progress| progress.phase);
+        assert_eq!(
+            phase("whisper_init_from_file_with_params_no_state: loading model from 'models/ggml-base.bin'"),
+            Some(TranscriptionPhase::LoadingModel)
+        );
+        assert_eq!(phase("[00:00:00.000 --> 00:00:04.000]   And so my fellow Americans"), Some(TranscriptionPhase::Transcribing));
+        assert_eq!(phase("whisper_print_timings:     load time =   120.00 ms"), Some(TranscriptionPhase::Finalizing));
+        assert_eq!(phase("system_info: n_threads = 4 / 8"), None);
+    }
+}
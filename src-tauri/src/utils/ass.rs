@@ -0,0 +1,180 @@
+use crate::models::WordTimedLine;
+use crate::utils::subtitle::SpeakerSegment;
+
+/// 화자 정보가 없을 때 사용하는 스타일 이름
+pub const DEFAULT_STYLE: &str = "Default";
+
+/// 글자색 (&HAABBGGRR). 첫 번째 흰색은 기본 스타일용이고 나머지를 화자에게 순서대로 배정
+const SPEAKER_COLOURS: [&str; 6] = [
+    "&H00FFFFFF",
+    "&H0000FFFF",
+    "&H00FFFF00",
+    "&H0080FF80",
+    "&H00FF80FF",
+    "&H0000A5FF",
+];
+
+/// 스타일 이름과 글자색으로 [Script Info], [V4+ Styles], [Events] 헤더를 만듭니다
+pub fn ass_header(styles: &[(String, &str)]) -> String {
+    let mut header = String::from("[Script Info]
+ScriptType: v4.00+
+PlayResX: 1920
+PlayResY: 1080
+WrapStyle: 0
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+");
+    for (name, colour) in styles {
+        header.push_str(&format!(
+            "Style: {},Arial,64,{},&H000000FF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,3,1,2,40,40,60,1\n",
+            sanitize_style_name(name), colour
+        ));
+    }
+    header.push_str("
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+");
+    header
+}
+
+/// 기본 스타일 하나만 가진 ASS 헤더를 만듭니다
+pub fn default_ass_header() -> String {
+    ass_header(&[(DEFAULT_STYLE.to_string(), SPEAKER_COLOURS[0])])
+}
+
+/// 스타일 이름에는 쉼표를 쓸 수 없으므로 밑줄로 바꿉니다
+fn sanitize_style_name(name: &str) -> String {
+    name.replace(',', "_")
+}
+
+/// 초를 ASS 타임스탬프로 변환합니다 ("0:01:23.45", 센티초 단위)
+pub fn format_ass_timestamp(seconds: f64) -> String {
+    let total_centis = (seconds.max(0.0) * 100.0).round() as u64;
+    let (hours, minutes) = (total_centis / 360_000, (total_centis % 360_000) / 6000);
+    let (secs, centis) = ((total_centis % 6000) / 100, total_centis % 100);
+    format!("{}:{:02}:{:02}.{:02}", hours, minutes, secs, centis)
+}
+
+/// ASS 텍스트에서 오버라이드 블록으로 해석될 중괄호를 이스케이프하고 줄바꿈을 \N으로 바꿉니다
+pub fn escape_ass_text(text: &str) -> String {
+    text.replace('{', "\\{").replace('}', "\\}").replace('\n', "\\N")
+}
+
+/// 단어별 `\k` 태그가 붙은 Dialogue 라인을 만듭니다
+///
+/// 단어 사이의 무음은 텍스트 없는 `\k` 태그로 넣어 하이라이트가 실제 발화에 맞춰 진행되게 합니다.
+pub fn karaoke_dialogue(line: &WordTimedLine) -> String {
+    let mut text = String::new();
+    let mut cursor = line.start;
+    
+    for (i, word) in line.words.iter().enumerate() {
+        let gap = ((word.start - cursor) * 100.0).round() as i64;
+        if gap > 0 {
+            text.push_str(&format!("{{\\k{}}}", gap));
+        }
+        
+        let duration = ((word.end - word.start.max(cursor)) * 100.0).round().max(1.0) as i64;
+        let separator = if i + 1 < line.words.len() { " " } else { "" };
+        text.push_str(&format!("{{\\k{}}}{}{}", duration, escape_ass_text(&word.text), separator));
+        cursor = word.end.max(cursor);
+    }
+    
+    dialogue_line(line.start, line.end, DEFAULT_STYLE, "", &text)
+}
+
+/// Dialogue 이벤트 한 줄을 만듭니다 (`text`는 이미 이스케이프된 내용)
+pub fn dialogue_line(start: f64, end: f64, style: &str, name: &str, text: &str) -> String {
+    format!(
+        "Dialogue: 0,{},{},{},{},0,0,0,,{}",
+        format_ass_timestamp(start),
+        format_ass_timestamp(end),
+        sanitize_style_name(style),
+        sanitize_style_name(name),
+        text
+    )
+}
+
+/// 화자별로 색이 다른 스타일을 배정한 ASS 자막을 만듭니다 (화자 정보가 없으면 기본 스타일 하나)
+pub fn speaker_segments_to_ass(segments: &[SpeakerSegment]) -> String {
+    let mut speakers: Vec<&str> = Vec::new();
+    for speaker in segments.iter().filter_map(|s| s.speaker.as_deref()) {
+        if !speakers.contains(&speaker) {
+            speakers.push(speaker);
+        }
+    }
+    
+    let mut styles = vec![(DEFAULT_STYLE.to_string(), SPEAKER_COLOURS[0])];
+    styles.extend(speakers.iter().enumerate().map(|(i, speaker)| {
+        (speaker.to_string(), SPEAKER_COLOURS[1 + i % (SPEAKER_COLOURS.len() - 1)])
+    }));
+    
+    let mut content = ass_header(if speakers.is_empty() { &styles[..1] } else { &styles });
+    for segment in segments {
+        let style = segment.speaker.as_deref().unwrap_or(DEFAULT_STYLE);
+        let name = segment.speaker.as_deref().unwrap_or("");
+        content.push_str(&dialogue_line(segment.start, segment.end, style, name, &escape_ass_text(&segment.text)));
+        content.push('\n');
+    }
+    content
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimedWord;
+    
+    fn speaker_segment(start: f64, speaker: Option<&str>, text: &str) -> SpeakerSegment {
+        SpeakerSegment { start, end: start + 1.0, speaker: speaker.map(str::to_string), text: text.to_string() }
+    }
+    
+    #[test]
+    fn ass_timestamps_use_centiseconds() {
+        assert_eq!(format_ass_timestamp(3723.456), "1:02:03.46");
+        assert_eq!(format_ass_timestamp(-1.0), "0:00:00.00");
+    }
+    
+    #[test]
+    fn dialogue_line_sanitizes_style_and_name() {
+        assert_eq!(
+            dialogue_line(1.0, 2.5, "Host, Main", "Host, Main", &escape_ass_text("{hi}\nthere")),
+            "Dialogue: 0,0:00:01.00,0:00:02.50,Host_ Main,Host_ Main,0,0,0,,\\{hi\\}\\Nthere"
+        );
+    }
+    
+    #[test]
+    fn each_speaker_gets_its_own_coloured_style() {
+        let segments = vec![
+            speaker_segment(0.0, Some("Alice"), "hello"),
+            speaker_segment(1.0, Some("Bob"), "hi"),
+            speaker_segment(2.0, Some("Alice"), "bye"),
+        ];
+        let ass = speaker_segments_to_ass(&segments);
+        assert!(ass.contains(&format!("Style: Alice,Arial,64,{},", SPEAKER_COLOURS[1])));
+        assert!(ass.contains(&format!("Style: Bob,Arial,64,{},", SPEAKER_COLOURS[2])));
+        assert_eq!(ass.matches("Style: Alice,").count(), 1);
+        assert!(ass.contains("Dialogue: 0,0:00:02.00,0:00:03.00,Alice,Alice,0,0,0,,bye"));
+        
+        // 화자 정보가 없으면 기본 스타일 하나만 사용
+        let ass = speaker_segments_to_ass(&[speaker_segment(0.0, None, "plain")]);
+        assert_eq!(ass.matches("\nStyle: ").count(), 1);
+        assert!(ass.contains("Dialogue: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,plain"));
+    }
+    
+    #[test]
+    fn karaoke_dialogue_inserts_gaps_between_words() {
+        let line = WordTimedLine {
+            start: 1.0,
+            end: 2.5,
+            words: vec![
+                TimedWord { start: 1.0, end: 1.4, text: "{hi}".to_string() },
+                TimedWord { start: 1.9, end: 2.5, text: "there".to_string() },
+            ],
+        };
+        assert_eq!(
+            karaoke_dialogue(&line),
+            "Dialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,{\\k40}\\{hi\\} {\\k50}{\\k60}there"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::path::Path;
+use crate::models::{AudioAnalysis, AudioIssue, AudioQuality};
+use super::{probe_media_duration, MediaTools};
+
+/// 이 값보다 작은 소리는 무음으로 간주 (silencedetect noise)
+const SILENCE_NOISE_DB: &str = "-50dB";
+/// 무음으로 집계할 최소 구간 길이 (초)
+const SILENCE_MIN_DURATION: f64 = 0.5;
+/// 전체 중 무음 비율이 이 값을 넘으면 "대부분 무음"
+const MOSTLY_SILENT_RATIO: f64 = 0.6;
+/// 0dB에 닿은 샘플 비율이 이 값을 넘으면 "심한 클리핑"
+const HEAVY_CLIPPING_RATIO: f64 = 0.001;
+/// 평균 음량이 이 값보다 낮으면 "너무 조용함"
+const TOO_QUIET_MEAN_DB: f64 = -40.0;
+
+/// volumedetect 필터 출력에서 얻은 값
+#[derive(Debug, Default, PartialEq)]
+pub struct VolumeStats {
+    pub n_samples: u64,
+    pub mean_volume_db: Option<f64>,
+    pub max_volume_db: Option<f64>,
+    pub clipped_samples: u64,       // histogram_0db 구간의 샘플 수
+}
+
+/// ffmpeg의 volumedetect/silencedetect 필터로 입력 오디오의 품질을 분석합니다
+pub async fn analyze_audio(tools: &MediaTools, path: &Path) -> anyhow::Result<AudioAnalysis> {
+    if !path.exists() {
+        return Err(anyhow::anyhow!("File not found: {}", path.display()));
+    }
+
+    let filters = format!("volumedetect,silencedetect=noise={}:d={}", SILENCE_NOISE_DB, SILENCE_MIN_DURATION);
+    let output = tokio::process::Command::new(&tools.ffmpeg)
+        .args(["-hide_banner", "-nostats", "-i"])
+        .arg(path)
+        .args(["-vn", "-sn", "-dn", "-af", &filters, "-f", "null", "-"])
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => anyhow::anyhow!("ffmpeg not found. Install ffmpeg or set its path in settings to analyze audio"),
+            _ => anyhow::anyhow!("Failed to run ffmpeg: {}", e),
+        })?;
+
+    // 필터 결과와 입력 정보는 모두 stderr로 출력됨
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(anyhow::anyhow!("ffmpeg failed to analyze audio: {}", last_line.trim()));
+    }
+
+    let volume = parse_volumedetect(&stderr);
+    if volume.n_samples == 0 {
+        return Err(anyhow::anyhow!("No audio stream found in {}", path.display()));
+    }
+
+    let duration_seconds = match parse_input_duration(&stderr) {
+        Some(duration) => Some(duration),
+        None => probe_media_duration(tools, path).await,
+    };
+    let silence_seconds = parse_silence_seconds(&stderr, duration_seconds);
+
+    Ok(build_analysis(path, volume, duration_seconds, silence_seconds))
+}
+
+fn build_analysis(
+    path: &Path,
+    volume: VolumeStats,
+    duration_seconds: Option<f64>,
+    silence_seconds: f64,
+) -> AudioAnalysis {
+    let clipped_ratio = volume.clipped_samples as f64 / volume.n_samples.max(1) as f64;
+    let silence_ratio = match duration_seconds {
+        Some(duration) if duration > 0.0 => (silence_seconds / duration).min(1.0),
+        _ => 0.0,
+    };
+
+    let mut issues = Vec::new();
+    if silence_ratio > MOSTLY_SILENT_RATIO {
+        issues.push(AudioIssue::MostlySilent);
+    }
+    if clipped_ratio > HEAVY_CLIPPING_RATIO {
+        issues.push(AudioIssue::HeavilyClipped);
+    }
+    if volume.mean_volume_db.is_some_and(|mean| mean < TOO_QUIET_MEAN_DB) {
+        issues.push(AudioIssue::TooQuiet);
+    }
+
+    let quality = if issues.iter().any(|i| matches!(i, AudioIssue::MostlySilent | AudioIssue::HeavilyClipped)) {
+        AudioQuality::Poor
+    } else if !issues.is_empty() {
+        AudioQuality::Fair
+    } else {
+        AudioQuality::Good
+    };
+
+    AudioAnalysis {
+        file_path: path.to_string_lossy().to_string(),
+        duration_seconds,
+        mean_volume_db: volume.mean_volume_db,
+        max_volume_db: volume.max_volume_db,
+        clipped_samples: volume.clipped_samples,
+        clipped_ratio,
+        silence_seconds,
+        silence_ratio,
+        quality,
+        issues,
+    }
+}
+
+/// "[Parsed_volumedetect_0 @ 0x...] mean_volume: -20.5 dB" 형식의 줄들을 읽습니다
+pub fn parse_volumedetect(output: &str) -> VolumeStats {
+    let mut stats = VolumeStats::default();
+
+    for line in output.lines().filter(|l| l.contains("volumedetect")) {
+        let (key, value) = match line.split_once("] ").and_then(|(_, rest)| rest.split_once(':')) {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        let number = value.trim_end_matches("dB").trim();
+
+        match key {
+            "n_samples" => stats.n_samples = number.parse().unwrap_or(0),
+            "mean_volume" => stats.mean_volume_db = number.parse().ok(),
+            "max_volume" => stats.max_volume_db = number.parse().ok(),
+            "histogram_0db" => stats.clipped_samples = number.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    stats
+}
+
+/// silencedetect 결과의 무음 구간 길이 합계 (끝나지 않은 마지막 구간은 입력 끝까지로 계산)
+pub fn parse_silence_seconds(output: &str, duration_seconds: Option<f64>) -> f64 {
+    let mut total = 0.0;
+    let mut open_start: Option<f64> = None;
+
+    for line in output.lines().filter(|l| l.contains("silencedetect")) {
+        if let Some(value) = field_after(line, "silence_start:") {
+            open_start = value.parse().ok();
+        } else if let Some(value) = field_after(line, "silence_duration:") {
+            total += value.parse::<f64>().unwrap_or(0.0);
+            open_start = None;
+        }
+    }
+
+    if let (Some(start), Some(duration)) = (open_start, duration_seconds) {
+        total += (duration - start).max(0.0);
+    }
+
+    total
+}
+
+/// ffmpeg 입력 정보의 "Duration: 00:01:02.34, start: ..." 값을 초 단위로 읽습니다
+pub fn parse_input_duration(output: &str) -> Option<f64> {
+    let value = output.lines().find_map(|l| field_after(l, "Duration:"))?;
+    let mut seconds = 0.0;
+    for part in value.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(seconds)
+}
+
+fn field_after<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = &line[line.find(key)? + key.len()..];
+    rest.split(|c: char| c == ',' || c == '|').next().map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FFMPEG_OUTPUT: &str = "\
+  Duration: 00:01:40.00, start: 0.000000, bitrate: 256 kb/s
+[silencedetect @ 0x1] silence_start: 10
+[silencedetect @ 0x1] silence_end: 40 | silence_duration: 30
+[silencedetect @ 0x1] silence_start: 80
+[Parsed_volumedetect_1 @ 0x2] n_samples: 1000000
+[Parsed_volumedetect_1 @ 0x2] mean_volume: -20.5 dB
+[Parsed_volumedetect_1 @ 0x2] max_volume: 0.0 dB
+[Parsed_volumedetect_1 @ 0x2] histogram_0db: 5000
+";
+
+    #[test]
+    fn parses_ffmpeg_filter_output() {
+        assert_eq!(parse_input_duration(FFMPEG_OUTPUT), Some(100.0));
+        // 끝나지 않은 마지막 무음 구간은 입력 끝까지로 계산
+        assert_eq!(parse_silence_seconds(FFMPEG_OUTPUT, Some(100.0)), 50.0);
+        assert_eq!(parse_silence_seconds(FFMPEG_OUTPUT, None), 30.0);
+        assert_eq!(parse_volumedetect(FFMPEG_OUTPUT), VolumeStats {
+            n_samples: 1_000_000,
+            mean_volume_db: Some(-20.5),
+            max_volume_db: Some(0.0),
+            clipped_samples: 5000,
+        });
+        assert_eq!(parse_input_duration("Duration: N/A, bitrate: N/A"), None);
+    }
+
+    #[test]
+    fn rates_quality_from_silence_clipping_and_volume() {
+        let path = Path::new("talk.wav");
+        let quiet = VolumeStats { n_samples: 1000, mean_volume_db: Some(-45.0), ..Default::default() };
+        let analysis = build_analysis(path, quiet, Some(100.0), 10.0);
+        assert_eq!(analysis.issues, vec![AudioIssue::TooQuiet]);
+        assert_eq!(analysis.quality, AudioQuality::Fair);
+
+        let clipped = VolumeStats { n_samples: 1000, clipped_samples: 10, ..Default::default() };
+        let analysis = build_analysis(path, clipped, Some(100.0), 70.0);
+        assert_eq!(analysis.issues, vec![AudioIssue::MostlySilent, AudioIssue::HeavilyClipped]);
+        assert_eq!(analysis.quality, AudioQuality::Poor);
+
+        let analysis = build_analysis(path, VolumeStats::default(), None, 10.0);
+        assert_eq!((analysis.silence_ratio, analysis.quality), (0.0, AudioQuality::Good));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::models::{ExportFormatInfo, MediaCategory};
+
+/// 내보내기 형식 정의 (형식 관련 정보의 단일 출처)
+pub struct ExportFormatSpec {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    pub requires_timestamps: bool,
+    /// whisper.cpp가 직접 생성하는 경우 해당 출력 플래그 (예: "output-srt")
+    pub whisper_option: Option<&'static str>,
+    /// whisper 옵션 목록의 기본 설명
+    pub option_description: &'static str,
+    /// 앱이 srt/vtt 결과에서 직접 변환할 수 있는지 여부
+    pub derivable: bool,
+}
+
+/// 결과 목록 정렬 순서이기도 합니다
+pub const EXPORT_FORMATS: &[ExportFormatSpec] = &[
+    ExportFormatSpec {
+        id: "txt",
+        display_name: "Plain Text",
+        requires_timestamps: false,
+        whisper_option: Some("output-txt"),
+        option_description: "텍스트 파일 출력 생성",
+        derivable: true,
+    },
+    ExportFormatSpec {
+        id: "srt",
+        display_name: "SubRip (SRT)",
+        requires_timestamps: true,
+        whisper_option: Some("output-srt"),
+        option_description: "SRT 자막 파일 출력 생성",
+        derivable: true,
+    },
+    ExportFormatSpec {
+        id: "vtt",
+        display_name: "WebVTT",
+        requires_timestamps: true,
+        whisper_option: Some("output-vtt"),
+        option_description: "WebVTT 자막 파일 출력 생성",
+        derivable: true,
+    },
+    ExportFormatSpec {
+        id: "csv",
+        display_name: "CSV",
+        requires_timestamps: true,
+        whisper_option: Some("output-csv"),
+        option_description: "CSV 파일 출력 생성",
+        derivable: false,
+    },
+    ExportFormatSpec {
+        id: "json",
+        display_name: "JSON",
+        requires_timestamps: true,
+        whisper_option: Some("output-json"),
+        option_description: "JSON 파일 출력 생성",
+        derivable: false,
+    },
+    ExportFormatSpec {
+        id: "lrc",
+        display_name: "LRC Lyrics",
+        requires_timestamps: true,
+        whisper_option: Some("output-lrc"),
+        option_description: "LRC 가사 파일 출력 생성",
+        derivable: false,
+    },
+    ExportFormatSpec {
+        id: "fcpxml",
+        display_name: "Final Cut Pro XML",
+        requires_timestamps: true,
+        whisper_option: None,
+        option_description: "",
+        derivable: true,
+    },
+];
+
+/// whisper.cpp가 직접 생성하는 형식들 (출력 플래그, 형식 ID)
+pub fn whisper_output_formats() -> impl Iterator<Item = (&'static str, &'static str)> {
+    EXPORT_FORMATS.iter().filter_map(|f| f.whisper_option.map(|option| (option, f.id)))
+}
+
+/// 결과 목록 정렬 순서 (정의되지 않은 형식은 뒤로)
+pub fn export_format_rank(format: &str) -> usize {
+    EXPORT_FORMATS.iter()
+        .position(|f| f.id == format)
+        .unwrap_or(EXPORT_FORMATS.len())
+}
+
+/// 미디어 종류별로 미리 선택할 출력 형식 (권장 사항일 뿐 강제하지 않음)
+pub fn suggested_formats(category: MediaCategory) -> &'static [&'static str] {
+    match category {
+        MediaCategory::Video => &["srt", "vtt"],
+        MediaCategory::Audio => &["txt", "srt"],
+        MediaCategory::Music => &["lrc"],
+        MediaCategory::Unknown => &["txt"],
+    }
+}
+
+pub fn list_export_formats() -> Vec<ExportFormatInfo> {
+    EXPORT_FORMATS.iter()
+        .map(|f| ExportFormatInfo {
+            id: f.id.to_string(),
+            display_name: f.display_name.to_string(),
+            requires_timestamps: f.requires_timestamps,
+            produced_by_whisper: f.whisper_option.is_some(),
+            derivable: f.derivable,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggested_formats_are_known_export_formats() {
+        for category in [MediaCategory::Video, MediaCategory::Audio, MediaCategory::Music, MediaCategory::Unknown] {
+            let suggested = suggested_formats(category);
+            assert!(!suggested.is_empty());
+            assert!(suggested.iter().all(|id| EXPORT_FORMATS.iter().any(|f| f.id == *id)));
+        }
+        assert_eq!(suggested_formats(MediaCategory::Music), &["lrc"]);
+    }
+}
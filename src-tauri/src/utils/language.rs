@@ -0,0 +1,45 @@
+use crate::models::TextScript;
+
+/// 문자가 속한 문자 체계를 반환합니다 (구두점/숫자/공백은 None)
+fn classify_char(c: char) -> Option<TextScript> {
+    match c as u32 {
+        0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F => Some(TextScript::Latin),
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Some(TextScript::Hangul),
+        0x3040..=0x30FF | 0x31F0..=0x31FF => Some(TextScript::Kana),
+        0x4E00..=0x9FFF | 0x3400..=0x4DBF => Some(TextScript::Han),
+        0x0400..=0x04FF => Some(TextScript::Cyrillic),
+        0x0600..=0x06FF | 0x0750..=0x077F => Some(TextScript::Arabic),
+        _ => None,
+    }
+}
+
+/// 텍스트에서 가장 많이 등장하는 문자 체계를 반환합니다
+pub fn detect_dominant_script(text: &str) -> Option<TextScript> {
+    let mut counts: Vec<(TextScript, usize)> = Vec::new();
+    
+    for script in text.chars().filter_map(classify_char) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    
+    counts.into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(script, _)| script)
+}
+
+/// 언어 코드에 대해 기대되는 문자 체계들을 반환합니다 (알 수 없는 언어는 빈 목록)
+pub fn expected_scripts_for_language(language: &str) -> Vec<TextScript> {
+    match language {
+        "ko" => vec![TextScript::Hangul],
+        // 일본어는 가나와 한자가 섞여 있으므로 둘 다 허용
+        "ja" => vec![TextScript::Kana, TextScript::Han],
+        "zh" | "yue" => vec![TextScript::Han],
+        "ru" | "uk" | "be" | "bg" | "sr" | "mk" | "kk" => vec![TextScript::Cyrillic],
+        "ar" | "fa" | "ur" => vec![TextScript::Arabic],
+        "en" | "fr" | "de" | "es" | "it" | "pt" | "nl" | "sv" | "da" | "no" | "fi"
+        | "pl" | "cs" | "sk" | "hu" | "ro" | "tr" | "vi" | "id" | "ms" | "ca" => vec![TextScript::Latin],
+        _ => Vec::new(),
+    }
+}
@@ -1,4 +1,19 @@
-use std::path::Path;
+pub mod ass;
+pub mod audio_analysis;
+pub mod export_formats;
+pub mod language;
+pub mod model_header;
+pub mod model_provenance;
+pub mod resource_limits;
+pub mod subtitle;
+pub mod whisper_json;
+
+use std::path::{Path, PathBuf};
+use crate::models::{AppSettings, AudioTrackPreference, MediaCategory, TranscriptionHistory};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// 파일 끝에서부터 읽을 때 한 번에 읽는 크기
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
 
 pub fn ensure_directory_exists(path: &Path) -> std::io::Result<()> {
     if !path.exists() {
@@ -7,12 +22,411 @@ pub fn ensure_directory_exists(path: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
-pub fn is_audio_file(file_path: &str) -> bool {
-    let audio_extensions = ["mp3", "wav", "m4a", "flac", "aac", "ogg"];
-    
+/// ffmpeg로 변환해 사용할 수 있는 오디오 확장자 (소문자)
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "m4a", "flac", "aac", "ogg", "oga", "opus", "wma", "aiff", "aif", "amr", "mka"];
+
+/// 오디오 트랙을 추출해 사용할 수 있는 동영상 컨테이너 확장자 (소문자)
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "avi", "mov", "mkv", "webm", "m4v", "wmv", "flv", "mpg", "mpeg", "3gp", "ts"];
+
+/// 확장자가 목록에 있는지 대소문자 구분 없이 확인합니다 (확장자가 없으면 false)
+fn has_extension(file_path: &str, extensions: &[&str]) -> bool {
     Path::new(file_path)
         .extension()
         .and_then(|ext| ext.to_str())
-        .map(|ext| audio_extensions.contains(&ext.to_lowercase().as_str()))
+        .map(|ext| extensions.contains(&ext.to_lowercase().as_str()))
         .unwrap_or(false)
-}
\ No newline at end of file
+}
+
+pub fn is_audio_file(file_path: &str) -> bool {
+    has_extension(file_path, AUDIO_EXTENSIONS)
+}
+
+pub fn is_video_file(file_path: &str) -> bool {
+    has_extension(file_path, VIDEO_EXTENSIONS)
+}
+
+/// 변환 입력으로 받을 수 있는 파일인지 확인합니다 (오디오 또는 동영상)
+pub fn is_media_file(file_path: &str) -> bool {
+    is_audio_file(file_path) || is_video_file(file_path)
+}
+
+/// 확장자와 ffprobe 메타데이터 태그로 미디어 종류를 추정합니다
+///
+/// 오디오 파일에 음악 장르나 앨범/아티스트 태그가 있으면 음악으로 봅니다.
+/// ffprobe를 사용할 수 없으면 확장자만으로 판단합니다.
+pub async fn detect_media_category(tools: &MediaTools, file_path: &str) -> MediaCategory {
+    if is_video_file(file_path) {
+        return MediaCategory::Video;
+    }
+    if !is_audio_file(file_path) {
+        return MediaCategory::Unknown;
+    }
+    
+    match probe_media_tags(tools, Path::new(file_path)).await {
+        Some(tags) if looks_like_music(&tags) => MediaCategory::Music,
+        _ => MediaCategory::Audio,
+    }
+}
+
+/// ffprobe로 컨테이너 메타데이터 태그를 읽습니다 (키는 소문자)
+async fn probe_media_tags(tools: &MediaTools, path: &Path) -> Option<std::collections::HashMap<String, String>> {
+    let output = tokio::process::Command::new(&tools.ffprobe)
+        .args(["-v", "error", "-show_entries", "format_tags", "-of", "default=noprint_wrappers=1"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
+    }
+    
+    let tags = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("TAG:").and_then(|tag| tag.split_once('=')))
+        .map(|(key, value)| (key.to_lowercase(), value.trim().to_string()))
+        .collect();
+    Some(tags)
+}
+
+fn looks_like_music(tags: &std::collections::HashMap<String, String>) -> bool {
+    let speech_genres = ["podcast", "speech", "audiobook", "spoken"];
+    
+    if let Some(genre) = tags.get("genre") {
+        let genre = genre.to_lowercase();
+        return !speech_genres.iter().any(|g| genre.contains(g));
+    }
+    
+    tags.contains_key("album") && tags.contains_key("artist")
+}
+
+/// 파일 전체를 읽지 않고 끝에서부터 마지막 N줄을 읽습니다
+///
+/// 줄 구분자(`\n`)는 UTF-8 멀티바이트 시퀀스 안에 나타나지 않으므로 바이트 단위로 나눈 뒤
+/// 줄마다 디코딩하면 문자 경계가 깨지지 않습니다. 반환값은 (원래 순서의 줄들, 앞에 더 남은 내용 여부)입니다.
+pub async fn read_last_lines(path: &Path, count: usize) -> std::io::Result<(Vec<String>, bool)> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let file_len = file.metadata().await?.len();
+    
+    if count == 0 {
+        return Ok((Vec::new(), file_len > 0));
+    }
+    
+    let mut position = file_len;
+    let mut buffer: Vec<u8> = Vec::new();
+    
+    loop {
+        // 파일 끝의 개행은 마지막 줄의 종료이므로 구분자 개수에서 제외
+        let content_len = if buffer.ends_with(b"\n") { buffer.len() - 1 } else { buffer.len() };
+        let newline_count = buffer[..content_len].iter().filter(|b| **b == b'\n').count();
+        
+        if newline_count >= count || position == 0 {
+            break;
+        }
+        
+        let read_size = std::cmp::min(TAIL_CHUNK_SIZE, position);
+        position -= read_size;
+        
+        file.seek(std::io::SeekFrom::Start(position)).await?;
+        let mut chunk = vec![0u8; read_size as usize];
+        file.read_exact(&mut chunk).await?;
+        
+        chunk.extend_from_slice(&buffer);
+        buffer = chunk;
+    }
+    
+    let content = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+    let lines: Vec<&[u8]> = content.split(|b| *b == b'\n').collect();
+    let skip = lines.len().saturating_sub(count);
+    
+    let tail = lines[skip..].iter()
+        .map(|line| String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).to_string())
+        .collect();
+    
+    Ok((tail, skip > 0 || position > 0))
+}
+
+/// 파일 이름 템플릿의 자리표시자를 히스토리 값으로 채웁니다 (확장자 제외)
+///
+/// `{name}`(원본 파일 이름, 확장자 제외), `{id}`, `{date}`(YYYY-MM-DD), `{model}`을 지원하며
+/// 경로 구분자처럼 파일 이름에 쓸 수 없는 문자는 `_`로 바꿉니다.
+pub fn render_filename_template(template: &str, history: &TranscriptionHistory) -> String {
+    let name = Path::new(&history.original_file_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| history.original_file_name.clone());
+    let date: String = history.created_at.chars().take(10).collect();
+    
+    let rendered = template
+        .replace("{name}", &name)
+        .replace("{id}", &history.id)
+        .replace("{date}", &date)
+        .replace("{model}", &history.model_used);
+    
+    let sanitized: String = rendered.chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control() { '_' } else { c })
+        .collect();
+    let sanitized = sanitized.trim().trim_matches('.').to_string();
+    
+    if sanitized.is_empty() { history.id.clone() } else { sanitized }
+}
+
+/// 사용할 ffmpeg/ffprobe 실행 파일 (서비스가 설정에서 읽어 두고 호출할 때 넘김)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaTools {
+    pub ffmpeg: PathBuf,
+    pub ffprobe: PathBuf,
+}
+
+impl MediaTools {
+    /// 설정에 경로가 없으면 PATH에서 찾습니다
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            ffmpeg: settings.ffmpeg_path.clone().unwrap_or_else(|| PathBuf::from("ffmpeg")),
+            ffprobe: settings.ffprobe_path.clone().unwrap_or_else(|| PathBuf::from("ffprobe")),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// ffprobe로 미디어 길이(초)를 구합니다 (ffprobe가 없거나 실패하면 None)
+pub async fn probe_media_duration(tools: &MediaTools, path: &Path) -> Option<f64> {
+    let output = tokio::process::Command::new(&tools.ffprobe)
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
+    }
+    
+    String::from_utf8_lossy(&output.stdout).trim().parse::<f64>().ok()
+}
+
+/// ffprobe로 입력 파일의 오디오 트랙 수를 구합니다 (ffprobe가 없거나 실패하면 None)
+pub async fn probe_audio_stream_count(tools: &MediaTools, path: &Path) -> Option<usize> {
+    let output = tokio::process::Command::new(&tools.ffprobe)
+        .args(["-v", "error", "-select_streams", "a", "-show_entries", "stream=index", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
+    }
+    
+    Some(String::from_utf8_lossy(&output.stdout).lines().filter(|l| !l.trim().is_empty()).count())
+}
+
+/// 오디오 트랙 선호를 ffmpeg 트랙 선택 인자로 변환합니다
+///
+/// 트랙이 하나 이하면 선호와 관계없이 첫 번째 트랙을 사용하고 fallback을 true로 반환합니다.
+pub fn audio_track_args(preference: AudioTrackPreference, audio_stream_count: usize) -> (Vec<String>, bool) {
+    if audio_stream_count < 2 {
+        return (vec!["-map".to_string(), "0:a:0".to_string()], true);
+    }
+    
+    let args = match preference {
+        AudioTrackPreference::System => vec!["-map".to_string(), "0:a:0".to_string()],
+        AudioTrackPreference::Mic => vec!["-map".to_string(), "0:a:1".to_string()],
+        AudioTrackPreference::Mix => {
+            let inputs: String = (0..audio_stream_count).map(|i| format!("[0:a:{}]", i)).collect();
+            vec![
+                "-filter_complex".to_string(),
+                format!("{}amix=inputs={}:duration=longest[mixed]", inputs, audio_stream_count),
+                "-map".to_string(),
+                "[mixed]".to_string(),
+            ]
+        }
+    };
+    
+    (args, false)
+}
+
+/// 디렉토리를 재귀적으로 복사합니다 (동기, 복사한 바이트 수 반환)
+pub fn copy_dir_recursive(source: &Path, target: &Path) -> std::io::Result<u64> {
+    std::fs::create_dir_all(target)?;
+    let mut copied = 0;
+    
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target_path = target.join(entry.file_name());
+        
+        if file_type.is_dir() {
+            copied += copy_dir_recursive(&entry.path(), &target_path)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(std::fs::read_link(entry.path())?, &target_path)?;
+            #[cfg(not(unix))]
+            { copied += std::fs::copy(entry.path(), &target_path)?; }
+        } else {
+            copied += std::fs::copy(entry.path(), &target_path)?;
+        }
+    }
+    
+    Ok(copied)
+}
+
+/// 바이트 수를 사람이 읽기 쉬운 단위로 변환합니다 (예: "2.9 GB")
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn media_extensions_ignore_case() {
+        assert!(is_audio_file("/music/Track.MP3"));
+        assert!(is_video_file("C:\\Videos\\Clip.MkV"));
+        assert!(is_media_file("recording.OPUS"));
+        assert!(!is_media_file("notes.TXT"));
+    }
+    
+    #[test]
+    fn files_without_extension_are_not_media() {
+        assert!(!has_extension("README", AUDIO_EXTENSIONS));
+        assert!(!is_media_file("/tmp/mp3"));
+        assert!(!is_media_file("archive."));
+        // 점으로 시작하는 숨김 파일은 확장자가 아니라 이름으로 취급됨
+        assert!(!is_media_file(".wav"));
+    }
+    
+    #[tokio::test]
+    async fn read_last_lines_spans_chunks_and_keeps_utf8() {
+        let path = std::env::temp_dir().join(format!("whisper-gui-test-{}.txt", uuid::Uuid::new_v4()));
+        // 청크 경계를 넘도록 긴 앞부분을 두고 마지막 줄들은 멀티바이트/CRLF로 구성
+        let mut content = "x".repeat(TAIL_CHUNK_SIZE as usize * 2);
+        content.push_str("\n안녕하세요\r\n두 번째 줄\n마지막\n");
+        tokio::fs::write(&path, &content).await.unwrap();
+        
+        let (lines, truncated) = read_last_lines(&path, 3).await.unwrap();
+        assert_eq!(lines, vec!["안녕하세요", "두 번째 줄", "마지막"]);
+        assert!(truncated);
+        
+        let (lines, truncated) = read_last_lines(&path, 10).await.unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(!truncated);
+        
+        tokio::fs::remove_file(&path).await.ok();
+    }
+    
+    #[tokio::test]
+    async fn read_last_lines_with_zero_count_reports_remaining_content() {
+        let path = std::env::temp_dir().join(format!("whisper-gui-test-{}.txt", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, "only line").await.unwrap();
+        
+        assert_eq!(read_last_lines(&path, 0).await.unwrap(), (Vec::new(), true));
+        assert_eq!(read_last_lines(&path, 1).await.unwrap(), (vec!["only line".to_string()], false));
+        
+        tokio::fs::remove_file(&path).await.ok();
+    }
+    
+    #[test]
+    fn music_needs_tags_and_a_non_speech_genre() {
+        let tags = |pairs: &[(&str, &str)]| -> std::collections::HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        assert!(looks_like_music(&tags(&[("album", "A"), ("artist", "B")])));
+        assert!(!looks_like_music(&tags(&[("artist", "B")])));
+        assert!(looks_like_music(&tags(&[("genre", "Jazz")])));
+        // 장르가 음성 콘텐츠면 앨범/아티스트 태그가 있어도 음악이 아님
+        assert!(!looks_like_music(&tags(&[("genre", "Podcast"), ("album", "A"), ("artist", "B")])));
+    }
+    
+    #[tokio::test]
+    async fn media_category_falls_back_to_audio_without_ffprobe() {
+        let tools = MediaTools {
+            ffmpeg: PathBuf::from("/nonexistent/ffmpeg"),
+            ffprobe: PathBuf::from("/nonexistent/ffprobe"),
+        };
+        assert_eq!(detect_media_category(&tools, "clip.mp4").await, MediaCategory::Video);
+        assert_eq!(detect_media_category(&tools, "talk.mp3").await, MediaCategory::Audio);
+        assert_eq!(detect_media_category(&tools, "notes.txt").await, MediaCategory::Unknown);
+    }
+    
+    #[test]
+    fn media_tools_prefer_configured_paths() {
+        let mut settings = AppSettings::default();
+        assert_eq!(MediaTools::from_settings(&settings), MediaTools {
+            ffmpeg: PathBuf::from("ffmpeg"),
+            ffprobe: PathBuf::from("ffprobe"),
+        });
+        
+        settings.ffmpeg_path = Some(PathBuf::from("/opt/ffmpeg/bin/ffmpeg"));
+        settings.ffprobe_path = Some(PathBuf::from("/opt/ffmpeg/bin/ffprobe"));
+        let tools = MediaTools::from_settings(&settings);
+        assert_eq!(tools.ffmpeg, PathBuf::from("/opt/ffmpeg/bin/ffmpeg"));
+        assert_eq!(tools.ffprobe, PathBuf::from("/opt/ffmpeg/bin/ffprobe"));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn probing_runs_the_configured_ffprobe() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        // PATH의 ffprobe와 구분되도록 고정된 길이를 출력하는 스크립트를 설정 경로로 사용
+        let dir = std::env::temp_dir().join(format!("whisper-gui-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ffprobe = dir.join("custom-ffprobe");
+        std::fs::write(&ffprobe, "#!/bin/sh\necho 42.5\n").unwrap();
+        std::fs::set_permissions(&ffprobe, std::fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let tools = MediaTools::from_settings(&AppSettings {
+            ffprobe_path: Some(ffprobe.clone()),
+            ..Default::default()
+        });
+        assert!(is_executable(&tools.ffprobe));
+        assert_eq!(probe_media_duration(&tools, Path::new("any.mp3")).await, Some(42.5));
+        
+        std::fs::remove_dir_all(&dir).ok();
+    }
+    
+    #[test]
+    fn audio_track_args_pick_or_mix_tracks() {
+        let to_strs = |args: &[String]| args.iter().map(String::as_str).collect::<Vec<_>>().join(" ");
+        
+        let (args, fallback) = audio_track_args(AudioTrackPreference::Mic, 2);
+        assert_eq!((to_strs(&args), fallback), ("-map 0:a:1".to_string(), false));
+        let (args, _) = audio_track_args(AudioTrackPreference::Mix, 3);
+        assert_eq!(to_strs(&args), "-filter_complex [0:a:0][0:a:1][0:a:2]amix=inputs=3:duration=longest[mixed] -map [mixed]");
+        
+        // 트랙이 하나뿐이면 선호와 관계없이 첫 트랙을 쓰고 fallback으로 알림
+        let (args, fallback) = audio_track_args(AudioTrackPreference::Mix, 1);
+        assert_eq!((to_strs(&args), fallback), ("-map 0:a:0".to_string(), true));
+    }
+    
+    #[test]
+    fn file_sizes_use_binary_units() {
+        assert_eq!(format_file_size(512), "512 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(3_100_000_000), "2.9 GB");
+        assert_eq!(format_file_size(u64::MAX), "16777216.0 TB");
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use crate::models::{LoadedModelInfo, ModelFileFormat, ModelHeader};
+
+/// whisper.cpp ggml 모델 매직 ("ggml"을 little-endian u32로 기록)
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+/// gguf 파일 매직 ("GGUF" 바이트)
+const GGUF_MAGIC: u32 = 0x4655_4747;
+
+/// whisper.cpp가 양자화 버전을 ftype에 곱해 저장할 때 쓰는 값
+const GGML_QNT_VERSION_FACTOR: i32 = 1000;
+
+/// 손상된 파일에서 메모리를 과하게 쓰지 않도록 제한
+const GGUF_MAX_KV_COUNT: u64 = 4096;
+const GGUF_MAX_STRING_LEN: u64 = 1024 * 1024;
+
+/// 메타데이터에 문자열로 남길 gguf 배열의 최대 원소 수 (토크나이저 배열 등은 길이만 기록)
+const GGUF_MAX_INLINE_ARRAY: u64 = 16;
+/// 배열 안의 배열을 따라갈 최대 깊이 (손상된 파일에서 재귀가 스택을 넘지 않도록)
+const GGUF_MAX_ARRAY_DEPTH: usize = 8;
+
+/// 모델 파일의 ggml/gguf 헤더를 읽습니다
+pub fn read_model_header(path: &Path) -> anyhow::Result<ModelHeader> {
+    let file_size = std::fs::metadata(path)?.len();
+    let file = std::fs::File::open(path)?;
+    let mut header = parse_model_header(&mut std::io::BufReader::new(file))?;
+    header.file_path = path.to_string_lossy().to_string();
+    header.file_size = file_size;
+    Ok(header)
+}
+
+/// 매직 값으로 형식을 판별해 헤더를 파싱합니다 (file_path/file_size는 비어 있음)
+pub fn parse_model_header<R: Read>(reader: &mut R) -> anyhow::Result<ModelHeader> {
+    let magic = read_u32(reader).map_err(|_| anyhow::anyhow!("File is too small to be a model"))?;
+
+    match magic {
+        GGML_MAGIC => parse_ggml_header(reader),
+        GGUF_MAGIC => parse_gguf_header(reader),
+        _ => Err(anyhow::anyhow!("Not a ggml/gguf model (magic 0x{:08x})", magic)),
+    }
+}
+
+/// 구형 whisper.cpp ggml 레이아웃: 매직 뒤에 i32 하이퍼파라미터 11개
+fn parse_ggml_header<R: Read>(reader: &mut R) -> anyhow::Result<ModelHeader> {
+    let mut hparams = [0i32; 11];
+    for value in hparams.iter_mut() {
+        *value = read_i32(reader).map_err(|_| anyhow::anyhow!("Truncated ggml header"))?;
+    }
+    let [n_vocab, n_audio_ctx, n_audio_state, n_audio_head, n_audio_layer, n_text_ctx, n_text_state, n_text_head, n_text_layer, n_mels, ftype] = hparams;
+
+    let mut header = empty_header(ModelFileFormat::Ggml);
+    header.n_vocab = Some(n_vocab);
+    header.n_audio_ctx = Some(n_audio_ctx);
+    header.n_audio_state = Some(n_audio_state);
+    header.n_audio_head = Some(n_audio_head);
+    header.n_audio_layer = Some(n_audio_layer);
+    header.n_text_ctx = Some(n_text_ctx);
+    header.n_text_state = Some(n_text_state);
+    header.n_text_head = Some(n_text_head);
+    header.n_text_layer = Some(n_text_layer);
+    header.n_mels = Some(n_mels);
+    header.ftype = Some(ggml_ftype_name(ftype % GGML_QNT_VERSION_FACTOR));
+    fill_derived_fields(&mut header);
+    Ok(header)
+}
+
+/// gguf 레이아웃: 버전, 텐서 수, 키-값 메타데이터 순서
+fn parse_gguf_header<R: Read>(reader: &mut R) -> anyhow::Result<ModelHeader> {
+    let version = read_u32(reader)?;
+    // v1은 개수와 문자열 길이가 u32, v2부터 u64
+    let wide = version >= 2;
+    let _tensor_count = read_count(reader, wide)?;
+    let kv_count = read_count(reader, wide)?;
+    if kv_count > GGUF_MAX_KV_COUNT {
+        return Err(anyhow::anyhow!("Unreasonable gguf metadata count: {}", kv_count));
+    }
+
+    let mut metadata = HashMap::new();
+    for _ in 0..kv_count {
+        let key = read_gguf_string(reader, wide)?;
+        let value_type = read_u32(reader)?;
+        let value = read_gguf_value(reader, value_type, wide, 0)?;
+        metadata.insert(key, value);
+    }
+
+    let architecture = metadata.get("general.architecture").cloned().unwrap_or_else(|| "whisper".to_string());
+    let int_field = |name: &str| -> Option<i32> {
+        metadata.get(&format!("{}.{}", architecture, name))
+            .and_then(|value| value.parse().ok())
+    };
+
+    let mut header = empty_header(ModelFileFormat::Gguf);
+    header.gguf_version = Some(version);
+    header.n_vocab = int_field("vocab_size").or_else(|| int_field("n_vocab"));
+    header.n_audio_ctx = int_field("encoder.context_length").or_else(|| int_field("n_audio_ctx"));
+    header.n_audio_state = int_field("encoder.embedding_length").or_else(|| int_field("n_audio_state"));
+    header.n_audio_head = int_field("encoder.attention.head_count").or_else(|| int_field("n_audio_head"));
+    header.n_audio_layer = int_field("encoder.block_count").or_else(|| int_field("n_audio_layer"));
+    header.n_text_ctx = int_field("decoder.context_length").or_else(|| int_field("n_text_ctx"));
+    header.n_text_state = int_field("decoder.embedding_length").or_else(|| int_field("n_text_state"));
+    header.n_text_head = int_field("decoder.attention.head_count").or_else(|| int_field("n_text_head"));
+    header.n_text_layer = int_field("decoder.block_count").or_else(|| int_field("n_text_layer"));
+    header.n_mels = int_field("n_mels").or_else(|| int_field("encoder.n_mels"));
+    header.ftype = metadata.get("general.file_type")
+        .and_then(|value| value.parse::<i32>().ok())
+        .map(ggml_ftype_name);
+    header.architecture = Some(architecture);
+    header.metadata = metadata;
+    fill_derived_fields(&mut header);
+    Ok(header)
+}
+
+fn empty_header(format: ModelFileFormat) -> ModelHeader {
+    ModelHeader {
+        file_path: String::new(),
+        file_size: 0,
+        format,
+        gguf_version: None,
+        architecture: None,
+        n_vocab: None,
+        n_audio_ctx: None,
+        n_audio_state: None,
+        n_audio_head: None,
+        n_audio_layer: None,
+        n_text_ctx: None,
+        n_text_state: None,
+        n_text_head: None,
+        n_text_layer: None,
+        n_mels: None,
+        ftype: None,
+        model_type: None,
+        multilingual: None,
+        metadata: HashMap::new(),
+    }
+}
+
+/// 인코더 레이어 수로 크기 등급을, 어휘 크기로 다국어 여부를 추정합니다 (whisper.cpp와 같은 기준)
+fn fill_derived_fields(header: &mut ModelHeader) {
+    header.model_type = header.n_audio_layer.and_then(|layers| match layers {
+        4 => Some("tiny"),
+        6 => Some("base"),
+        12 => Some("small"),
+        24 => Some("medium"),
+        32 => Some("large"),
+        _ => None,
+    }).map(str::to_string);
+    // 영어 전용 모델은 51864, 다국어 모델은 51865 이상 (large-v3는 51866)
+    header.multilingual = header.n_vocab.map(|n_vocab| n_vocab >= 51865);
+}
+
+fn ggml_ftype_name(ftype: i32) -> String {
+    match ftype {
+        0 => "f32".to_string(),
+        1 => "f16".to_string(),
+        2 => "q4_0".to_string(),
+        3 => "q4_1".to_string(),
+        7 => "q8_0".to_string(),
+        8 => "q5_0".to_string(),
+        9 => "q5_1".to_string(),
+        other => format!("unknown({})", other),
+    }
+}
+
+/// gguf 값 하나를 읽어 문자열로 반환합니다 (긴 배열은 "[type; len]"으로 요약, `depth`는 배열 중첩 깊이)
+fn read_gguf_value<R: Read>(reader: &mut R, value_type: u32, wide: bool, depth: usize) -> anyhow::Result<String> {
+    Ok(match value_type {
+        0 => read_array::<1, R>(reader)?[0].to_string(),
+        1 => (read_array::<1, R>(reader)?[0] as i8).to_string(),
+        2 => u16::from_le_bytes(read_array(reader)?).to_string(),
+        3 => i16::from_le_bytes(read_array(reader)?).to_string(),
+        4 => read_u32(reader)?.to_string(),
+        5 => read_i32(reader)?.to_string(),
+        6 => f32::from_le_bytes(read_array(reader)?).to_string(),
+        7 => (read_array::<1, R>(reader)?[0] != 0).to_string(),
+        8 => read_gguf_string(reader, wide)?,
+        9 => {
+            if depth >= GGUF_MAX_ARRAY_DEPTH {
+                return Err(anyhow::anyhow!("gguf arrays nested deeper than {} levels", GGUF_MAX_ARRAY_DEPTH));
+            }
+            let element_type = read_u32(reader)?;
+            let len = read_count(reader, wide)?;
+            let mut elements = Vec::new();
+            for i in 0..len {
+                let element = read_gguf_value(reader, element_type, wide, depth + 1)?;
+                if i < GGUF_MAX_INLINE_ARRAY {
+                    elements.push(element);
+                }
+            }
+            if len > GGUF_MAX_INLINE_ARRAY {
+                format!("[array of {} items]", len)
+            } else {
+                format!("[{}]", elements.join(", "))
+            }
+        }
+        10 => u64::from_le_bytes(read_array(reader)?).to_string(),
+        11 => i64::from_le_bytes(read_array(reader)?).to_string(),
+        12 => f64::from_le_bytes(read_array(reader)?).to_string(),
+        other => return Err(anyhow::anyhow!("Unknown gguf value type: {}", other)),
+    })
+}
+
+fn read_gguf_string<R: Read>(reader: &mut R, wide: bool) -> anyhow::Result<String> {
+    let len = read_count(reader, wide)?;
+    if len > GGUF_MAX_STRING_LEN {
+        return Err(anyhow::anyhow!("Unreasonable gguf string length: {}", len));
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn read_count<R: Read>(reader: &mut R, wide: bool) -> std::io::Result<u64> {
+    if wide {
+        Ok(u64::from_le_bytes(read_array(reader)?))
+    } else {
+        Ok(read_u32(reader)? as u64)
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> std::io::Result<i32> {
+    Ok(i32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> std::io::Result<[u8; N]> {
+    let mut buffer = [0u8; N];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// whisper 프로세스 로그의 `whisper_model_load: key = value` 줄들에서 실제로 읽은 모델 정보를 뽑습니다
+///
+/// 모델 로드 줄이 하나도 없으면 None을 반환합니다.
+pub fn parse_model_load_log(log: &str) -> Option<LoadedModelInfo> {
+    let mut info = LoadedModelInfo {
+        model_type: None,
+        n_vocab: None,
+        n_audio_layer: None,
+        model_size_mb: None,
+    };
+    let mut found = false;
+    
+    for line in log.lines() {
+        let (key, value) = match line.trim().strip_prefix("whisper_model_load:").and_then(|rest| rest.split_once('=')) {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        found = true;
+        
+        match key {
+            // "1 (tiny)" / "5 (large v3)"
+            "type" => {
+                info.model_type = value.split_once('(')
+                    .and_then(|(_, name)| name.split_once(')'))
+                    .map(|(name, _)| name.trim().to_string());
+            }
+            "n_vocab" => info.n_vocab = value.parse().ok(),
+            "n_audio_layer" => info.n_audio_layer = value.parse().ok(),
+            "model size" => {
+                info.model_size_mb = value.split_whitespace().next().and_then(|size| size.parse().ok());
+            }
+            _ => {}
+        }
+    }
+    
+    if found { Some(info) } else { None }
+}
+
+/// 모델 이름의 크기 등급 ("large-v3-turbo" → "large", "tiny.en" → "tiny", "large v3" → "large")
+pub fn model_family(name: &str) -> &str {
+    name.split(|c: char| c == '-' || c == '.' || c == ' ' || c == '_')
+        .next()
+        .unwrap_or(name)
+}
+
+/// 요청한 모델과 실제로 읽은 모델이 다르면 차이를 설명하는 메시지를 반환합니다
+///
+/// 크기 등급과 영어 전용 여부(어휘 크기)만 비교하며, 알려진 등급이 아닌 사용자 모델 이름은 비교하지 않습니다.
+pub fn describe_model_mismatch(requested: &str, loaded: &LoadedModelInfo) -> Option<String> {
+    const KNOWN_FAMILIES: &[&str] = &["tiny", "base", "small", "medium", "large"];
+    
+    let requested_family = model_family(requested);
+    if !KNOWN_FAMILIES.contains(&requested_family) {
+        return None;
+    }
+    
+    if let Some(loaded_type) = loaded.model_type.as_deref() {
+        let loaded_family = model_family(loaded_type);
+        if KNOWN_FAMILIES.contains(&loaded_family) && loaded_family != requested_family {
+            return Some(format!("Requested model '{}' but whisper loaded a {} model", requested, loaded_type));
+        }
+    }
+    
+    if let Some(n_vocab) = loaded.n_vocab {
+        let requested_english_only = requested.contains(".en");
+        let loaded_english_only = n_vocab < 51865;
+        if requested_english_only != loaded_english_only {
+            return Some(format!(
+                "Requested model '{}' but whisper loaded a {} model (n_vocab = {})",
+                requested,
+                if loaded_english_only { "English-only" } else { "multilingual" },
+                n_vocab
+            ));
+        }
+    }
+    
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// gguf v3 헤더 (텐서 0개, 주어진 키-값만 포함)를 만듭니다
+    fn gguf_bytes(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&GGUF_MAGIC.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (key, typed_value) in entries {
+            bytes.extend_from_slice(&gguf_string(key));
+            bytes.extend_from_slice(typed_value);
+        }
+        bytes
+    }
+    
+    fn gguf_string(value: &str) -> Vec<u8> {
+        let mut bytes = (value.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(value.as_bytes());
+        bytes
+    }
+    
+    fn gguf_u32(value: u32) -> Vec<u8> {
+        let mut bytes = 4u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&value.to_le_bytes());
+        bytes
+    }
+    
+    fn gguf_str_value(value: &str) -> Vec<u8> {
+        let mut bytes = 8u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&gguf_string(value));
+        bytes
+    }
+    
+    /// 바깥 배열 안에 배열이 `depth`단 더 중첩된 값 (가장 안쪽은 u32 하나짜리 배열)
+    fn gguf_nested_array(depth: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..depth {
+            bytes.extend_from_slice(&9u32.to_le_bytes());
+            bytes.extend_from_slice(&1u64.to_le_bytes());
+        }
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        bytes.extend_from_slice(&7u32.to_le_bytes());
+        // 맨 앞의 값 타입은 키 뒤에 한 번만 오므로 배열 타입(9)으로 시작
+        let mut typed = 9u32.to_le_bytes().to_vec();
+        typed.extend_from_slice(&bytes);
+        typed
+    }
+    
+    #[test]
+    fn parses_ggml_header() {
+        let mut bytes = GGML_MAGIC.to_le_bytes().to_vec();
+        for value in [51865i32, 1500, 512, 8, 6, 448, 512, 8, 6, 80, 1] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        
+        let header = parse_model_header(&mut bytes.as_slice()).unwrap();
+        assert!(matches!(header.format, ModelFileFormat::Ggml));
+        assert_eq!(header.model_type.as_deref(), Some("base"));
+        assert_eq!(header.multilingual, Some(true));
+        assert_eq!(header.ftype.as_deref(), Some("f16"));
+    }
+    
+    #[test]
+    fn parses_gguf_metadata() {
+        let bytes = gguf_bytes(&[
+            ("general.architecture", gguf_str_value("whisper")),
+            ("whisper.encoder.block_count", gguf_u32(12)),
+            ("whisper.vocab_size", gguf_u32(51864)),
+            ("whisper.mel_bins", gguf_nested_array(0)),
+        ]);
+        
+        let header = parse_model_header(&mut bytes.as_slice()).unwrap();
+        assert!(matches!(header.format, ModelFileFormat::Gguf));
+        assert_eq!(header.gguf_version, Some(3));
+        assert_eq!(header.model_type.as_deref(), Some("small"));
+        assert_eq!(header.multilingual, Some(false));
+        assert_eq!(header.metadata.get("whisper.mel_bins").map(String::as_str), Some("[7]"));
+    }
+    
+    #[test]
+    fn rejects_deeply_nested_gguf_arrays() {
+        let within_limit = gguf_bytes(&[("nested", gguf_nested_array(GGUF_MAX_ARRAY_DEPTH - 1))]);
+        assert!(parse_model_header(&mut within_limit.as_slice()).is_ok());
+        
+        let too_deep = gguf_bytes(&[("nested", gguf_nested_array(GGUF_MAX_ARRAY_DEPTH))]);
+        assert!(parse_model_header(&mut too_deep.as_slice()).is_err());
+    }
+    
+    #[test]
+    fn rejects_unknown_magic_and_truncated_headers() {
+        assert!(parse_model_header(&mut [0u8, 1, 2, 3].as_slice()).is_err());
+        assert!(parse_model_header(&mut [0u8, 1].as_slice()).is_err());
+        
+        let truncated = GGML_MAGIC.to_le_bytes().to_vec();
+        assert!(parse_model_header(&mut truncated.as_slice()).is_err());
+    }
+    
+    const LOAD_LOG: &str = "\
+whisper_init_from_file_with_params_no_state: loading model from 'ggml-base.bin'
+whisper_model_load: n_vocab       = 51865
+whisper_model_load: n_audio_layer = 6
+whisper_model_load: type          = 2 (base)
+whisper_model_load: model size    =  147.37 MB
+";
+    
+    #[test]
+    fn parse_model_load_log_reads_loaded_model() {
+        let info = parse_model_load_log(LOAD_LOG).unwrap();
+        assert_eq!(info.model_type.as_deref(), Some("base"));
+        assert_eq!((info.n_vocab, info.n_audio_layer), (Some(51865), Some(6)));
+        assert!((info.model_size_mb.unwrap() - 147.37).abs() < 1e-3);
+        assert!(parse_model_load_log("main: processing 'talk.wav'").is_none());
+    }
+    
+    #[test]
+    fn model_mismatch_compares_family_and_english_only_vocab() {
+        let loaded = parse_model_load_log(LOAD_LOG).unwrap();
+        assert_eq!(model_family("large-v3-turbo"), "large");
+        assert_eq!(describe_model_mismatch("base-q5_1", &loaded), None);
+        assert!(describe_model_mismatch("small", &loaded).unwrap().contains("loaded a base model"));
+        assert!(describe_model_mismatch("base.en", &loaded).unwrap().contains("multilingual"));
+        // 알려진 등급이 아닌 사용자 모델은 비교하지 않음
+        assert_eq!(describe_model_mismatch("my-finetune", &loaded), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::models::ModelProvenance;
+
+/// ggml 변환 모델이 배포되는 저장소
+const GGML_REPOSITORY_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp";
+
+/// 양자화 변형 접미사 (출처와 라이선스는 원본 모델과 같음)
+const QUANTIZATION_SUFFIXES: &[&str] = &["-q5_0", "-q5_1", "-q8_0"];
+
+/// 공식 ggml 모델의 출처 정의
+pub struct ModelProvenanceSpec {
+    pub base_model: &'static str,
+    pub original_author: &'static str,
+    pub source_url: &'static str,
+    pub license: &'static str,
+    pub license_url: &'static str,
+}
+
+const OPENAI_SOURCE_URL: &str = "https://github.com/openai/whisper";
+const OPENAI_LICENSE_URL: &str = "https://github.com/openai/whisper/blob/main/LICENSE";
+
+const fn openai_model(base_model: &'static str) -> ModelProvenanceSpec {
+    ModelProvenanceSpec {
+        base_model,
+        original_author: "OpenAI",
+        source_url: OPENAI_SOURCE_URL,
+        license: "MIT",
+        license_url: OPENAI_LICENSE_URL,
+    }
+}
+
+pub const MODEL_PROVENANCE: &[ModelProvenanceSpec] = &[
+    openai_model("tiny"),
+    openai_model("tiny.en"),
+    openai_model("base"),
+    openai_model("base.en"),
+    openai_model("small"),
+    openai_model("small.en"),
+    openai_model("medium"),
+    openai_model("medium.en"),
+    openai_model("large-v1"),
+    openai_model("large-v2"),
+    openai_model("large-v3"),
+    openai_model("large-v3-turbo"),
+    ModelProvenanceSpec {
+        base_model: "small.en-tdrz",
+        original_author: "Akash Mahajan (tinydiarize, OpenAI small.en 기반)",
+        source_url: "https://github.com/akashmjn/tinydiarize",
+        license: "MIT",
+        license_url: "https://github.com/akashmjn/tinydiarize/blob/main/LICENSE",
+    },
+];
+
+/// 양자화 접미사를 제거한 원본 모델 이름
+fn base_model_name(model_name: &str) -> &str {
+    QUANTIZATION_SUFFIXES.iter()
+        .find_map(|suffix| model_name.strip_suffix(suffix))
+        .unwrap_or(model_name)
+}
+
+pub fn find_model_provenance(model_name: &str) -> Option<ModelProvenance> {
+    let base_model = base_model_name(model_name);
+    let spec = MODEL_PROVENANCE.iter().find(|spec| spec.base_model == base_model)?;
+
+    Some(ModelProvenance {
+        model_name: model_name.to_string(),
+        base_model: spec.base_model.to_string(),
+        quantized: base_model != model_name,
+        original_author: spec.original_author.to_string(),
+        source_url: spec.source_url.to_string(),
+        license: spec.license.to_string(),
+        license_url: spec.license_url.to_string(),
+        repository_url: GGML_REPOSITORY_URL.to_string(),
+        download_url: format!("{}/resolve/main/ggml-{}.bin", GGML_REPOSITORY_URL, model_name),
+    })
+}
@@ -0,0 +1,105 @@
+use crate::models::{BatchLimitCheck, ResourceLimits};
+
+/// whisper 변환 하나가 쓰는 파일 디스크립터 수 (stdout/stderr 파이프, 로그, 모델, 입력, 결과 파일 등)
+const FDS_PER_TRANSCRIPTION: u64 = 8;
+
+/// 앱 자체(WebView, 인덱스 파일 등)를 위해 남겨 둘 디스크립터 수
+const RESERVED_FDS: u64 = 64;
+
+/// 현재 프로세스의 열린 파일/프로세스 수 제한을 읽습니다
+#[cfg(unix)]
+pub fn read_resource_limits() -> ResourceLimits {
+    let (open_files_soft, open_files_hard) = read_rlimit(libc::RLIMIT_NOFILE);
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    let (max_processes, _) = read_rlimit(libc::RLIMIT_NPROC);
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    let max_processes = None;
+    
+    ResourceLimits {
+        open_files_soft,
+        open_files_hard,
+        open_files_in_use: count_open_files(),
+        max_processes,
+    }
+}
+
+/// Windows는 프로세스별 핸들 제한이 사실상 없어 값을 알 수 없음으로 표시
+#[cfg(not(unix))]
+pub fn read_resource_limits() -> ResourceLimits {
+    ResourceLimits {
+        open_files_soft: None,
+        open_files_hard: None,
+        open_files_in_use: None,
+        max_processes: None,
+    }
+}
+
+/// getrlimit의 resource 인자 타입 (glibc만 별도 타입을 씀)
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// (soft, hard) 제한을 읽습니다 (무제한이면 None)
+#[cfg(unix)]
+fn read_rlimit(resource: RlimitResource) -> (Option<u64>, Option<u64>) {
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // SAFETY: 유효한 rlimit 구조체에 현재 프로세스의 제한 값만 읽어 옴
+    if unsafe { libc::getrlimit(resource, &mut limit) } != 0 {
+        return (None, None);
+    }
+    
+    let finite = |value: libc::rlim_t| if value == libc::RLIM_INFINITY { None } else { Some(value as u64) };
+    (finite(limit.rlim_cur), finite(limit.rlim_max))
+}
+
+/// 현재 열려 있는 디스크립터 수 (/proc/self/fd 또는 /dev/fd 항목 수)
+#[cfg(unix)]
+fn count_open_files() -> Option<u64> {
+    ["/proc/self/fd", "/dev/fd"].iter()
+        .find_map(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| entries.count() as u64)
+}
+
+/// 배치 크기가 현재 제한 안에서 안전하게 동시에 실행할 수 있는 수를 넘는지 확인합니다
+///
+/// 동시 실행 수는 남은 디스크립터를 변환 하나당 사용량으로 나눠 계산하고, 제한을 알 수 없으면 CPU 수를 사용합니다.
+pub fn check_batch_limits(batch_size: usize) -> BatchLimitCheck {
+    let limits = read_resource_limits();
+    let cpu_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    
+    let fd_concurrency = limits.open_files_soft.map(|soft| {
+        let available = soft.saturating_sub(limits.open_files_in_use.unwrap_or(0) + RESERVED_FDS);
+        (available / FDS_PER_TRANSCRIPTION).max(1) as usize
+    });
+    let safe_concurrency = fd_concurrency.unwrap_or(cpu_count).min(cpu_count.max(1) * 4).max(1);
+    
+    let mut warnings = Vec::new();
+    if let Some(fd_concurrency) = fd_concurrency {
+        if batch_size > fd_concurrency {
+            warnings.push(format!(
+                "Batch of {} may exceed the open file limit ({} soft, {} in use); run at most {} at a time or raise `ulimit -n`",
+                batch_size,
+                limits.open_files_soft.unwrap_or(0),
+                limits.open_files_in_use.unwrap_or(0),
+                fd_concurrency
+            ));
+        }
+    }
+    if let Some(max_processes) = limits.max_processes {
+        if batch_size as u64 >= max_processes {
+            warnings.push(format!(
+                "Batch of {} may exceed the process limit ({}); run fewer files at a time or raise `ulimit -u`",
+                batch_size, max_processes
+            ));
+        }
+    }
+    
+    BatchLimitCheck {
+        batch_size,
+        safe_concurrency,
+        within_limits: warnings.is_empty(),
+        warnings,
+        limits,
+    }
+}
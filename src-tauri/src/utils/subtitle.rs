@@ -0,0 +1,899 @@
+use crate::models::{
+    SegmentSpeakingRate, SpeakingPace, SpeakingRate, SplitStrategy, SubtitleCoverage, SubtitleLayout, SubtitleSegment, TimeRange,
+    TimedWord, WordTimedLine,
+};
+
+/// SRT/VTT 타임스탬프를 초 단위로 변환합니다 ("00:01:23,456" / "01:23.456")
+pub fn parse_subtitle_timestamp(timestamp: &str) -> Option<f64> {
+    let normalized = timestamp.trim().replace(',', ".");
+    let parts: Vec<&str> = normalized.split(':').collect();
+    
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>().ok()?, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        [m, s] => (0.0, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        _ => return None,
+    };
+    
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// "start --> end" 형식의 타이밍 라인을 파싱합니다 (VTT 큐 설정은 무시)
+fn parse_timing_line(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_subtitle_timestamp(start)?, parse_subtitle_timestamp(end)?))
+}
+
+/// SRT/VTT 내용을 세그먼트 목록으로 파싱합니다
+///
+/// 두 형식 모두 "타이밍 라인 + 텍스트 라인들" 블록 구조이므로 같은 파서를 사용합니다.
+/// 큐 번호, WEBVTT 헤더, NOTE 블록 등 타이밍 라인 앞의 내용은 무시됩니다.
+pub fn parse_subtitle_segments(content: &str) -> Vec<SubtitleSegment> {
+    let mut segments = Vec::new();
+    let mut current: Option<SubtitleSegment> = None;
+    
+    for line in content.lines() {
+        let trimmed = line.trim();
+        
+        if let Some((start, end)) = parse_timing_line(trimmed) {
+            if let Some(segment) = current.take() {
+                segments.push(segment);
+            }
+            current = Some(SubtitleSegment { start, end, text: String::new() });
+            continue;
+        }
+        
+        if trimmed.is_empty() {
+            if let Some(segment) = current.take() {
+                segments.push(segment);
+            }
+            continue;
+        }
+        
+        if let Some(segment) = current.as_mut() {
+            if !segment.text.is_empty() {
+                segment.text.push('\n');
+            }
+            segment.text.push_str(trimmed);
+        }
+    }
+    
+    if let Some(segment) = current.take() {
+        segments.push(segment);
+    }
+    
+    segments
+}
+
+/// 번역할 텍스트 라인만 (라인 번호, 내용)으로 꺼냅니다
+///
+/// SRT/VTT는 타이밍 라인 뒤의 큐 텍스트만, 타이밍 라인이 없는 내용(txt)은 비어 있지 않은 모든 라인이 대상입니다.
+/// 큐 번호, 타이밍 라인, WEBVTT 헤더는 꺼내지 않으므로 다시 넣어도 타이밍이 그대로 유지됩니다.
+pub fn extract_translatable_lines(content: &str) -> Vec<(usize, String)> {
+    let has_timing = content.lines().any(|line| parse_timing_line(line.trim()).is_some());
+    let mut lines = Vec::new();
+    let mut in_cue = false;
+    
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            in_cue = false;
+            continue;
+        }
+        if parse_timing_line(trimmed).is_some() {
+            in_cue = true;
+            continue;
+        }
+        if in_cue || !has_timing {
+            lines.push((index, trimmed.to_string()));
+        }
+    }
+    
+    lines
+}
+
+/// extract_translatable_lines로 꺼낸 라인 번호 위치에 번역문을 다시 넣습니다 (나머지 라인은 그대로)
+pub fn reinject_translated_lines(content: &str, translated: &[(usize, String)]) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    for (index, text) in translated {
+        if let Some(line) = lines.get_mut(*index) {
+            *line = text;
+        }
+    }
+    
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// 세그먼트들의 텍스트만 이어붙여 반환합니다
+pub fn segments_to_plain_text(segments: &[SubtitleSegment]) -> String {
+    segments.iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 초를 SRT 타임스탬프로 변환합니다 ("00:01:23,456")
+pub fn format_srt_timestamp(seconds: f64) -> String {
+    format_vtt_timestamp(seconds).replacen('.', ",", 1)
+}
+
+/// SRT 큐 하나를 만듭니다 (index는 1부터)
+pub fn format_srt_cue(index: usize, segment: &SubtitleSegment) -> String {
+    format!(
+        "{}\n{} --> {}\n{}\n\n",
+        index,
+        format_srt_timestamp(segment.start),
+        format_srt_timestamp(segment.end),
+        segment.text
+    )
+}
+
+/// VTT 큐 하나를 만듭니다
+pub fn format_vtt_cue(segment: &SubtitleSegment) -> String {
+    format!(
+        "{} --> {}\n{}\n\n",
+        format_vtt_timestamp(segment.start),
+        format_vtt_timestamp(segment.end),
+        segment.text
+    )
+}
+
+/// VTT 파일 헤더
+pub const VTT_HEADER: &str = "WEBVTT\n\n";
+
+/// 세그먼트 목록을 SRT 형식으로 변환합니다
+pub fn segments_to_srt(segments: &[SubtitleSegment]) -> String {
+    segments.iter()
+        .enumerate()
+        .map(|(i, segment)| format_srt_cue(i + 1, segment))
+        .collect()
+}
+
+/// 세그먼트 목록을 VTT 형식으로 변환합니다
+pub fn segments_to_vtt(segments: &[SubtitleSegment]) -> String {
+    let mut vtt_content = String::from(VTT_HEADER);
+    for segment in segments {
+        vtt_content.push_str(&format_vtt_cue(segment));
+    }
+    vtt_content
+}
+
+/// 각 파트의 크기 한계를 판정하는 방식에 따라 세그먼트를 여러 파트로 나눕니다
+///
+/// `cue_size`는 (파트 내 1부터 시작하는 번호, 세그먼트)로 렌더링된 큐의 바이트 수를,
+/// `header_size`는 파일 헤더의 바이트 수를 돌려줘야 합니다 (MaxBytes 전략에서만 사용).
+/// 한 큐가 한계를 넘더라도 빈 파트는 만들지 않으므로 해당 큐는 단독 파트가 됩니다.
+pub fn split_segments(
+    segments: &[SubtitleSegment],
+    strategy: &SplitStrategy,
+    header_size: usize,
+    cue_size: impl Fn(usize, &SubtitleSegment) -> usize,
+) -> Vec<Vec<SubtitleSegment>> {
+    let mut parts: Vec<Vec<SubtitleSegment>> = Vec::new();
+    let mut current: Vec<SubtitleSegment> = Vec::new();
+    let mut current_bytes = header_size;
+    
+    for segment in segments {
+        let next_cue_bytes = cue_size(current.len() + 1, segment);
+        
+        let exceeds = !current.is_empty() && match strategy {
+            SplitStrategy::MaxDuration(max_seconds) => segment.end - current[0].start > *max_seconds,
+            SplitStrategy::MaxCues(max_cues) => current.len() >= *max_cues,
+            SplitStrategy::MaxBytes(max_bytes) => (current_bytes + next_cue_bytes) as u64 > *max_bytes,
+        };
+        
+        if exceeds {
+            parts.push(std::mem::take(&mut current));
+            current_bytes = header_size + cue_size(1, segment);
+        } else {
+            current_bytes += next_cue_bytes;
+        }
+        current.push(segment.clone());
+    }
+    
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    
+    parts
+}
+
+/// 큐 텍스트를 줄 길이에 맞게 다시 줄바꿈하고, 줄 수 제한을 넘는 큐는 연속된 큐로 나눕니다
+///
+/// 나뉜 큐는 원래 큐의 시간을 글자 수 비율로 나눠 갖습니다.
+/// `max_chars_per_line`이 없으면 세그먼트를 그대로 반환합니다.
+pub fn reflow_segments(segments: &[SubtitleSegment], layout: &SubtitleLayout) -> Vec<SubtitleSegment> {
+    let max_chars = match layout.max_chars_per_line {
+        Some(max_chars) if max_chars > 0 => max_chars,
+        _ => return segments.to_vec(),
+    };
+    let max_lines = layout.max_lines.filter(|n| *n > 0).unwrap_or(usize::MAX);
+    
+    let mut reflowed = Vec::new();
+    for segment in segments {
+        let lines = wrap_text(&segment.text, max_chars);
+        if lines.is_empty() {
+            reflowed.push(segment.clone());
+            continue;
+        }
+        
+        let groups: Vec<&[String]> = lines.chunks(max_lines).collect();
+        let total_chars: usize = lines.iter().map(|line| line.chars().count()).sum();
+        let duration = segment.end - segment.start;
+        
+        let mut cursor = segment.start;
+        let mut consumed_chars = 0;
+        for (i, group) in groups.iter().enumerate() {
+            consumed_chars += group.iter().map(|line| line.chars().count()).sum::<usize>();
+            // 마지막 큐는 반올림 오차 없이 원래 종료 시간에 맞춤
+            let end = if i + 1 == groups.len() {
+                segment.end
+            } else {
+                segment.start + duration * consumed_chars as f64 / total_chars.max(1) as f64
+            };
+            reflowed.push(SubtitleSegment { start: cursor, end, text: group.join("\n") });
+            cursor = end;
+        }
+    }
+    
+    reflowed
+}
+
+/// 단어 단위로 줄을 채웁니다 (한 단어가 줄 길이보다 길면 글자 단위로 자름)
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > max_chars {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        
+        let word: String = word.into_iter().collect();
+        let needed = if current.is_empty() { word.chars().count() } else { current.chars().count() + 1 + word.chars().count() };
+        if needed > max_chars && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    
+    lines
+}
+
+/// 첫 세그먼트 시작이 0초가 되도록 타이밍을 옮깁니다
+pub fn rebase_segments(segments: &[SubtitleSegment]) -> Vec<SubtitleSegment> {
+    let offset = segments.first().map_or(0.0, |s| s.start);
+    segments.iter()
+        .map(|s| SubtitleSegment { start: s.start - offset, end: s.end - offset, text: s.text.clone() })
+        .collect()
+}
+
+/// 초를 VTT 타임스탬프로 변환합니다 ("00:01:23.456")
+pub fn format_vtt_timestamp(seconds: f64) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hours, minutes) = (total_millis / 3_600_000, (total_millis % 3_600_000) / 60_000);
+    let (secs, millis) = ((total_millis % 60_000) / 1000, total_millis % 1000);
+    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+}
+
+/// 중복 판정용으로 텍스트를 정규화합니다 (대소문자/문장부호/공백 차이 무시)
+fn normalize_for_comparison(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_ascii_punctuation())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// 연속된 동일/유사 세그먼트를 하나로 합칩니다 (whisper 반복 환각 정리)
+///
+/// 정규화한 텍스트의 Levenshtein 유사도가 `similarity_threshold` 이상이면 같은 문장으로 보고
+/// 앞 세그먼트의 종료 시간을 늘립니다. 병합된 세그먼트 수를 함께 반환합니다.
+pub fn merge_duplicate_segments(
+    segments: &[SubtitleSegment],
+    similarity_threshold: f64,
+) -> (Vec<SubtitleSegment>, usize) {
+    let mut merged: Vec<SubtitleSegment> = Vec::new();
+    let mut merged_count = 0;
+    
+    for segment in segments {
+        if let Some(previous) = merged.last_mut() {
+            let (a, b) = (normalize_for_comparison(&previous.text), normalize_for_comparison(&segment.text));
+            if !a.is_empty() && strsim::normalized_levenshtein(&a, &b) >= similarity_threshold {
+                previous.end = previous.end.max(segment.end);
+                merged_count += 1;
+                continue;
+            }
+        }
+        merged.push(segment.clone());
+    }
+    
+    (merged, merged_count)
+}
+
+/// 문장 끝 문장부호로 끝나는지 확인합니다 (병합 경계로 사용)
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end().ends_with(|c: char| matches!(c, '.' | '?' | '!' | '。' | '？' | '！'))
+}
+
+/// 짧은 세그먼트를 간격이 좁은 이웃 세그먼트와 이어 붙여 문장 단위 큐로 만듭니다
+///
+/// 앞 큐가 `min_duration_seconds`보다 짧고(`merge_into_previous`이면 다음 세그먼트가 짧은 경우도 포함),
+/// 앞 큐의 문장이 끝나지 않았고, 간격이 `max_gap_seconds` 이하이며,
+/// 합친 텍스트가 `max_merged_chars`(글자 수)를 넘지 않을 때만 합칩니다.
+/// 합친 큐는 첫 세그먼트의 시작부터 마지막 세그먼트의 끝까지를 덮습니다.
+pub fn merge_short_segments(
+    segments: &[SubtitleSegment],
+    min_duration_seconds: f64,
+    max_gap_seconds: f64,
+    max_merged_chars: Option<usize>,
+    merge_into_previous: bool,
+) -> Vec<SubtitleSegment> {
+    let mut merged: Vec<SubtitleSegment> = Vec::new();
+    
+    for segment in segments {
+        if let Some(previous) = merged.last_mut() {
+            let is_short = previous.end - previous.start < min_duration_seconds
+                || (merge_into_previous && segment.end - segment.start < min_duration_seconds);
+            let gap = segment.start - previous.end;
+            let text = format!("{} {}", previous.text.trim_end(), segment.text.trim_start());
+            let fits = max_merged_chars.map_or(true, |max_chars| text.chars().count() <= max_chars);
+            if is_short && gap <= max_gap_seconds && fits && !ends_sentence(&previous.text) {
+                previous.text = text;
+                previous.end = previous.end.max(segment.end);
+                continue;
+            }
+        }
+        merged.push(segment.clone());
+    }
+    
+    merged
+}
+
+/// 오디오 길이 대비 자막 큐가 덮는 비율과 가장 긴 공백 구간을 계산합니다
+pub fn analyze_coverage(
+    segments: &[SubtitleSegment],
+    audio_duration_seconds: f64,
+    duration_estimated: bool,
+) -> SubtitleCoverage {
+    let mut intervals: Vec<(f64, f64)> = segments.iter()
+        .map(|segment| (segment.start.max(0.0), segment.end.min(audio_duration_seconds)))
+        .filter(|(start, end)| end > start)
+        .collect();
+    intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    
+    let mut covered_seconds = 0.0;
+    let mut largest_gap: Option<TimeRange> = None;
+    let mut cursor = 0.0;
+    
+    let mut consider_gap = |start: f64, end: f64| {
+        if end > start && largest_gap.as_ref().map_or(true, |gap| end - start > gap.end - gap.start) {
+            largest_gap = Some(TimeRange { start, end });
+        }
+    };
+    
+    for (start, end) in intervals {
+        if start > cursor {
+            consider_gap(cursor, start);
+        }
+        if end > cursor {
+            covered_seconds += end - start.max(cursor);
+            cursor = end;
+        }
+    }
+    consider_gap(cursor, audio_duration_seconds);
+    
+    let minutes = audio_duration_seconds / 60.0;
+    
+    SubtitleCoverage {
+        audio_duration_seconds,
+        duration_estimated,
+        covered_seconds,
+        coverage_ratio: if audio_duration_seconds > 0.0 { covered_seconds / audio_duration_seconds } else { 0.0 },
+        largest_gap,
+        cue_count: segments.len(),
+        cues_per_minute: if minutes > 0.0 { segments.len() as f64 / minutes } else { 0.0 },
+    }
+}
+
+/// 이 속도(분당 단어 수)를 넘는 세그먼트는 빠른 것으로 표시
+const FAST_SPEECH_WPM: f64 = 220.0;
+
+/// 이 속도(분당 단어 수)보다 느린 세그먼트는 느린 것으로 표시
+const SLOW_SPEECH_WPM: f64 = 60.0;
+
+/// 이보다 짧은 세그먼트는 속도가 크게 튀므로 빠름/느림을 판단하지 않음
+const MIN_PACE_SEGMENT_SECONDS: f64 = 1.0;
+
+/// 세그먼트별/전체 분당 단어 수를 계산합니다
+///
+/// 단어는 공백으로 나누므로 띄어쓰기가 없는 언어(중국어, 일본어)에서는 낮게 계산됩니다.
+pub fn compute_speaking_rate(segments: &[SubtitleSegment]) -> SpeakingRate {
+    let rates: Vec<SegmentSpeakingRate> = segments.iter()
+        .map(|segment| {
+            let duration = (segment.end - segment.start).max(0.0);
+            let word_count = segment.text.split_whitespace().count();
+            let words_per_minute = if duration > 0.0 { word_count as f64 * 60.0 / duration } else { 0.0 };
+            
+            let pace = if duration < MIN_PACE_SEGMENT_SECONDS {
+                SpeakingPace::Normal
+            } else if words_per_minute > FAST_SPEECH_WPM {
+                SpeakingPace::Fast
+            } else if words_per_minute < SLOW_SPEECH_WPM {
+                SpeakingPace::Slow
+            } else {
+                SpeakingPace::Normal
+            };
+            
+            SegmentSpeakingRate { start: segment.start, end: segment.end, word_count, words_per_minute, pace }
+        })
+        .collect();
+    
+    let total_words = rates.iter().map(|r| r.word_count).sum();
+    let speaking_seconds: f64 = rates.iter().map(|r| (r.end - r.start).max(0.0)).sum();
+    
+    SpeakingRate {
+        overall_words_per_minute: if speaking_seconds > 0.0 { total_words as f64 * 60.0 / speaking_seconds } else { 0.0 },
+        total_words,
+        speaking_seconds,
+        fast_count: rates.iter().filter(|r| r.pace == SpeakingPace::Fast).count(),
+        slow_count: rates.iter().filter(|r| r.pace == SpeakingPace::Slow).count(),
+        segments: rates,
+    }
+}
+
+/// 한 줄에 넣을 최대 단어 수 (단어 단위 자막을 줄로 묶을 때)
+const MAX_WORDS_PER_LINE: usize = 8;
+
+/// 이 시간(초) 이상 쉬면 새 줄을 시작
+const LINE_BREAK_GAP_SECONDS: f64 = 1.0;
+
+/// 단어 단위 큐(--max-len 1 출력)를 문장 부호/무음/단어 수 기준으로 줄 단위로 묶습니다
+pub fn group_words_into_lines(segments: &[SubtitleSegment]) -> Vec<WordTimedLine> {
+    let mut lines: Vec<WordTimedLine> = Vec::new();
+    let mut current: Vec<TimedWord> = Vec::new();
+    
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        
+        if let Some(last) = current.last() {
+            let sentence_end = last.text.ends_with(|c| matches!(c, '.' | '?' | '!' | '。' | '？' | '！'));
+            if sentence_end
+                || segment.start - last.end >= LINE_BREAK_GAP_SECONDS
+                || current.len() >= MAX_WORDS_PER_LINE
+            {
+                lines.push(words_to_line(std::mem::take(&mut current)));
+            }
+        }
+        
+        current.push(TimedWord { start: segment.start, end: segment.end, text: text.to_string() });
+    }
+    
+    if !current.is_empty() {
+        lines.push(words_to_line(current));
+    }
+    
+    lines
+}
+
+fn words_to_line(words: Vec<TimedWord>) -> WordTimedLine {
+    WordTimedLine {
+        start: words.first().map_or(0.0, |w| w.start),
+        end: words.last().map_or(0.0, |w| w.end),
+        words,
+    }
+}
+
+/// 단어 타이밍을 max_len 글자 이하의 단어 묶음으로 나눠 묶음마다 SRT 큐 하나를 만듭니다
+///
+/// 묶음은 세그먼트(줄)를 넘지 않고, max_len보다 긴 단어는 혼자 한 묶음이 됩니다.
+pub fn convert_words_to_srt(lines: &[WordTimedLine], max_len: usize) -> String {
+    let mut groups: Vec<SubtitleSegment> = Vec::new();
+    
+    for line in lines {
+        let mut current: Option<SubtitleSegment> = None;
+        for word in &line.words {
+            match current.as_mut() {
+                Some(group) if group.text.chars().count() + 1 + word.text.chars().count() <= max_len => {
+                    group.text.push(' ');
+                    group.text.push_str(&word.text);
+                    group.end = word.end;
+                }
+                _ => {
+                    groups.extend(current.take());
+                    current = Some(SubtitleSegment { start: word.start, end: word.end, text: word.text.clone() });
+                }
+            }
+        }
+        groups.extend(current);
+    }
+    
+    segments_to_srt(&groups)
+}
+
+/// 챕터 제목으로 사용할 첫 단어 개수
+const CHAPTER_TITLE_WORDS: usize = 6;
+
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub start: f64,
+    pub title: String,
+}
+
+/// 무음 간격이 `min_gap_seconds` 이상인 지점에서 세그먼트를 챕터로 나눕니다
+///
+/// YouTube 챕터 규칙에 맞춰 첫 챕터는 항상 0초에서 시작합니다.
+pub fn group_into_chapters(segments: &[SubtitleSegment], min_gap_seconds: f64) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = Vec::new();
+    let mut previous_end: Option<f64> = None;
+    
+    for segment in segments {
+        let starts_chapter = match previous_end {
+            None => true,
+            Some(end) => segment.start - end >= min_gap_seconds,
+        };
+        
+        if starts_chapter {
+            chapters.push(Chapter {
+                start: if chapters.is_empty() { 0.0 } else { segment.start },
+                title: provisional_title(&segment.text),
+            });
+        }
+        
+        previous_end = Some(segment.end);
+    }
+    
+    chapters
+}
+
+/// 텍스트의 첫 몇 단어로 임시 제목을 만듭니다
+fn provisional_title(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let title = words.iter().take(CHAPTER_TITLE_WORDS).cloned().collect::<Vec<_>>().join(" ");
+    
+    if words.len() > CHAPTER_TITLE_WORDS {
+        format!("{}…", title)
+    } else {
+        title
+    }
+}
+
+/// YouTube 챕터 형식 타임스탬프 ("0:00", "12:34", "1:02:03")
+pub fn format_chapter_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (hours, minutes, secs) = (total / 3600, (total % 3600) / 60, total % 60);
+    
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{}:{:02}", minutes, secs)
+    }
+}
+
+/// 챕터 목록을 YouTube 설명란 형식으로 변환합니다
+pub fn chapters_to_text(chapters: &[Chapter]) -> String {
+    chapters.iter()
+        .map(|chapter| format!("{} {}", format_chapter_timestamp(chapter.start), chapter.title))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 교정용 타임코드 ("00:01:05" 형식, 시간 자리도 항상 표시)
+fn format_proofreading_timecode(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
+
+/// 교정용 문서를 만듭니다: interval_seconds마다 새 문단을 시작하고 그 문단 첫 세그먼트의 시작 시간을 [hh:mm:ss]로 붙임
+///
+/// markdown이면 제목과 굵은 타임코드를 사용하고, 아니면 일반 텍스트로 만듭니다.
+pub fn segments_to_proofreading_doc(title: &str, segments: &[SubtitleSegment], interval_seconds: f64, markdown: bool) -> String {
+    let mut paragraphs: Vec<(f64, Vec<&str>)> = Vec::new();
+    let mut next_marker = 0.0;
+    
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if paragraphs.is_empty() || segment.start >= next_marker {
+            paragraphs.push((segment.start, Vec::new()));
+            // 긴 무음 뒤에도 간격이 밀리지 않도록 현재 위치 기준으로 다음 표시 시점을 잡음
+            next_marker = ((segment.start / interval_seconds).floor() + 1.0) * interval_seconds;
+        }
+        if let Some((_, texts)) = paragraphs.last_mut() {
+            texts.push(text);
+        }
+    }
+    
+    let body = paragraphs.iter()
+        .map(|(start, texts)| {
+            let timecode = format_proofreading_timecode(*start);
+            let text = texts.join(" ").replace('\n', " ");
+            if markdown {
+                format!("**[{}]** {}", timecode, text)
+            } else {
+                format!("[{}] {}", timecode, text)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    
+    if markdown {
+        format!("# {}\n\n{}\n", title, body)
+    } else {
+        format!("{}\n\n{}\n", title, body)
+    }
+}
+
+/// tinydiarize(tdrz) 모델이 화자가 바뀌는 세그먼트 끝에 붙이는 토큰
+const SPEAKER_TURN_TOKEN: &str = "[SPEAKER_TURN]";
+
+#[derive(Debug, Clone)]
+pub struct SpeakerSegment {
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// 세그먼트 텍스트 앞의 화자 표기를 분리합니다
+///
+/// whisper `--diarize`의 "(speaker 0)"과 외부 화자 분리 도구의 "[SPEAKER_00]" / "SPEAKER_00:" 형식을 인식합니다.
+fn split_speaker_label(label: &regex::Regex, text: &str) -> (Option<String>, String) {
+    match label.captures(text) {
+        Some(captures) => {
+            let speaker = captures.iter().skip(1).flatten().next().map(|m| m.as_str().to_string());
+            (speaker, text[captures.get(0).unwrap().end()..].to_string())
+        }
+        None => (None, text.to_string()),
+    }
+}
+
+/// 세그먼트마다 화자를 붙입니다
+///
+/// 명시적인 화자 표기가 있으면 그대로 쓰고, tdrz의 `[SPEAKER_TURN]` 토큰만 있는 경우에는
+/// 화자가 누구인지 알 수 없으므로 턴마다 "Speaker 1"과 "Speaker 2"를 번갈아 배정합니다.
+pub fn label_speakers(segments: &[SubtitleSegment]) -> Vec<SpeakerSegment> {
+    let label = regex::Regex::new(r"^\s*(?:\((speaker [^)]+)\)|\[(SPEAKER_\w+)\]:?|(SPEAKER_\w+):)\s*").unwrap();
+    let has_turns = segments.iter().any(|s| s.text.contains(SPEAKER_TURN_TOKEN));
+    let mut turn_index = 0;
+    
+    segments.iter()
+        .map(|segment| {
+            let (explicit, text) = split_speaker_label(&label, &segment.text);
+            let turned = text.contains(SPEAKER_TURN_TOKEN);
+            let text = text.replace(SPEAKER_TURN_TOKEN, "").trim().to_string();
+            
+            let speaker = explicit.or_else(|| has_turns.then(|| format!("Speaker {}", turn_index % 2 + 1)));
+            if turned {
+                turn_index += 1;
+            }
+            
+            SpeakerSegment { start: segment.start, end: segment.end, speaker, text }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn segment(start: f64, end: f64, text: &str) -> SubtitleSegment {
+        SubtitleSegment { start, end, text: text.to_string() }
+    }
+    
+    #[test]
+    fn merge_short_segments_respects_char_cap() {
+        let segments = vec![
+            segment(0.0, 0.2, "one"),
+            segment(0.2, 0.4, "two"),
+            segment(0.4, 0.6, "three"),
+            segment(0.6, 0.8, "four"),
+        ];
+        
+        let merged = merge_short_segments(&segments, 1.0, 0.5, Some(9), true);
+        assert!(merged.iter().all(|s| s.text.chars().count() <= 9));
+        // "one two" 다음에 "three"를 붙이면 13자가 되므로 새 큐에서 다시 시작
+        let texts: Vec<&str> = merged.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["one two", "three", "four"]);
+    }
+    
+    #[test]
+    fn merge_short_segments_stops_at_cap_and_keeps_timing() {
+        let segments = vec![
+            segment(0.0, 0.3, "가나다"),
+            segment(0.3, 0.6, "라마바"),
+            segment(0.6, 0.9, "사아자"),
+        ];
+        
+        let merged = merge_short_segments(&segments, 1.0, 0.5, Some(7), true);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].text, "가나다 라마바");
+        assert_eq!((merged[0].start, merged[0].end), (0.0, 0.6));
+        assert_eq!((merged[1].start, merged[1].end), (0.6, 0.9));
+    }
+    
+    #[test]
+    fn merge_short_segments_previous_only_keeps_long_cue_before_short_one() {
+        let segments = vec![
+            segment(0.0, 3.0, "a long cue"),
+            segment(3.0, 3.2, "short"),
+        ];
+        
+        assert_eq!(merge_short_segments(&segments, 1.0, 0.5, None, false).len(), 2);
+        assert_eq!(merge_short_segments(&segments, 1.0, 0.5, None, true).len(), 1);
+    }
+    
+    #[test]
+    fn merge_duplicate_segments_collapses_exact_and_near_repeats() {
+        let segments = vec![
+            segment(0.0, 1.0, "intro"),
+            segment(1.0, 2.0, "Hello there."),
+            segment(2.0, 3.0, "hello there"),
+            segment(3.0, 4.0, "hello their"),
+            segment(4.0, 5.0, "goodbye"),
+        ];
+        
+        // 대소문자/문장부호만 다른 반복과 한두 글자만 다른 반복이 앞 큐로 합쳐지고 이웃은 유지됨
+        let (merged, merged_count) = merge_duplicate_segments(&segments, 0.8);
+        let cues: Vec<(f64, f64, &str)> = merged.iter().map(|s| (s.start, s.end, s.text.as_str())).collect();
+        assert_eq!(cues, vec![(0.0, 1.0, "intro"), (1.0, 4.0, "Hello there."), (4.0, 5.0, "goodbye")]);
+        assert_eq!(merged_count, 2);
+        
+        // 임계값을 높이면 정확한 반복만 합침
+        let (merged, merged_count) = merge_duplicate_segments(&segments, 0.9);
+        let texts: Vec<&str> = merged.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["intro", "Hello there.", "hello their", "goodbye"]);
+        assert_eq!(merged_count, 1);
+    }
+    
+    fn part_lengths(parts: &[Vec<SubtitleSegment>]) -> Vec<usize> {
+        parts.iter().map(Vec::len).collect()
+    }
+    
+    #[test]
+    fn split_segments_by_cues_and_duration() {
+        let segments: Vec<SubtitleSegment> = (0..5)
+            .map(|i| segment(i as f64 * 10.0, i as f64 * 10.0 + 8.0, "line"))
+            .collect();
+        let srt_size = |index: usize, s: &SubtitleSegment| format_srt_cue(index, s).len();
+        
+        assert_eq!(part_lengths(&split_segments(&segments, &SplitStrategy::MaxCues(2), 0, srt_size)), vec![2, 2, 1]);
+        // 파트 첫 큐 시작부터 28초 안에 끝나는 큐까지만 한 파트에 들어감
+        assert_eq!(part_lengths(&split_segments(&segments, &SplitStrategy::MaxDuration(28.0), 0, srt_size)), vec![3, 2]);
+    }
+    
+    #[test]
+    fn split_segments_by_bytes_counts_header_and_keeps_oversized_cue() {
+        let segments = vec![segment(0.0, 1.0, "a"), segment(1.0, 2.0, "b"), segment(2.0, 3.0, "b")];
+        let vtt_size = |_: usize, s: &SubtitleSegment| format_vtt_cue(s).len();
+        let cue_bytes = format_vtt_cue(&segments[0]).len() as u64;
+        let header = VTT_HEADER.len();
+        
+        let limit = SplitStrategy::MaxBytes(header as u64 + cue_bytes * 2);
+        assert_eq!(part_lengths(&split_segments(&segments, &limit, header, vtt_size)), vec![2, 1]);
+        // 한계보다 큰 큐도 빈 파트에는 들어가야 무한히 나누지 않음
+        let tiny = SplitStrategy::MaxBytes(1);
+        assert_eq!(part_lengths(&split_segments(&segments, &tiny, header, vtt_size)), vec![1, 1, 1]);
+    }
+    
+    #[test]
+    fn rebase_segments_starts_part_at_zero() {
+        let rebased = rebase_segments(&[segment(90.0, 92.5, "a"), segment(93.0, 95.0, "b")]);
+        assert_eq!(format_srt_cue(1, &rebased[0]), "1\n00:00:00,000 --> 00:00:02,500\na\n\n");
+        assert_eq!(format_vtt_cue(&rebased[1]), "00:00:03.000 --> 00:00:05.000\nb\n\n");
+        assert!(rebase_segments(&[]).is_empty());
+    }
+    
+    #[test]
+    fn label_speakers_reads_explicit_labels() {
+        let labelled = label_speakers(&[
+            segment(0.0, 1.0, "(speaker 0) hello"),
+            segment(1.0, 2.0, "[SPEAKER_01]: hi"),
+            segment(2.0, 3.0, "SPEAKER_02: hey"),
+            segment(3.0, 4.0, "no label"),
+        ]);
+        let pairs: Vec<(Option<&str>, &str)> = labelled.iter().map(|s| (s.speaker.as_deref(), s.text.as_str())).collect();
+        assert_eq!(pairs, vec![
+            (Some("speaker 0"), "hello"),
+            (Some("SPEAKER_01"), "hi"),
+            (Some("SPEAKER_02"), "hey"),
+            (None, "no label"),
+        ]);
+    }
+    
+    #[test]
+    fn label_speakers_alternates_on_turn_tokens() {
+        let labelled = label_speakers(&[
+            segment(0.0, 1.0, &format!("question {}", SPEAKER_TURN_TOKEN)),
+            segment(1.0, 2.0, "answer"),
+            segment(2.0, 3.0, &format!("more {}", SPEAKER_TURN_TOKEN)),
+            segment(3.0, 4.0, "follow-up"),
+        ]);
+        let speakers: Vec<&str> = labelled.iter().map(|s| s.speaker.as_deref().unwrap()).collect();
+        assert_eq!(speakers, vec!["Speaker 1", "Speaker 2", "Speaker 2", "Speaker 1"]);
+        assert_eq!(labelled[0].text, "question");
+    }
+    
+    #[test]
+    fn wrap_text_breaks_on_words_and_splits_long_words() {
+        assert_eq!(wrap_text("aaaa bb cc dddddddddd", 5), vec!["aaaa", "bb cc", "ddddd", "ddddd"]);
+        assert_eq!(wrap_text("가나다라마바", 4), vec!["가나다라", "마바"]);
+        assert!(wrap_text("   ", 5).is_empty());
+    }
+    
+    #[test]
+    fn reflow_splits_cues_over_line_limit_by_char_share() {
+        let segments = vec![segment(0.0, 10.0, "one two three four")];
+        let layout = |max_lines| SubtitleLayout { max_chars_per_line: Some(9), max_lines };
+        
+        let reflowed = reflow_segments(&segments, &layout(Some(2)));
+        let cues: Vec<(f64, f64, &str)> = reflowed.iter().map(|s| (s.start, s.end, s.text.as_str())).collect();
+        assert_eq!(cues, vec![(0.0, 7.5, "one two\nthree"), (7.5, 10.0, "four")]);
+        
+        // 줄 수 제한이 없으면 큐를 나누지 않고 줄바꿈만 함
+        let reflowed = reflow_segments(&segments, &layout(None));
+        assert_eq!(reflowed.len(), 1);
+        assert_eq!(reflowed[0].text, "one two\nthree\nfour");
+        
+        let unchanged = reflow_segments(&segments, &SubtitleLayout::default());
+        assert_eq!(unchanged[0].text, "one two three four");
+    }
+    
+    #[test]
+    fn translatable_lines_skip_numbers_and_timings() {
+        let srt = "1\n00:00:00,000 --> 00:00:01,000\nhello\nworld\n\n2\n00:00:01,000 --> 00:00:02,000\nbye\n";
+        let lines = extract_translatable_lines(srt);
+        assert_eq!(lines, vec![(2, "hello".to_string()), (3, "world".to_string()), (7, "bye".to_string())]);
+        
+        let translated: Vec<(usize, String)> = lines.into_iter().map(|(i, text)| (i, text.to_uppercase())).collect();
+        assert_eq!(
+            reinject_translated_lines(srt, &translated),
+            "1\n00:00:00,000 --> 00:00:01,000\nHELLO\nWORLD\n\n2\n00:00:01,000 --> 00:00:02,000\nBYE\n"
+        );
+        
+        // 타이밍이 없는 텍스트는 비어 있지 않은 모든 줄이 대상
+        assert_eq!(extract_translatable_lines("one\n\n two "), vec![(0, "one".to_string()), (2, "two".to_string())]);
+    }
+    
+    #[test]
+    fn speaking_rate_flags_pace_outside_normal_range() {
+        let rate = compute_speaking_rate(&[
+            segment(0.0, 2.0, "one two three"),
+            segment(2.0, 3.0, &["word"; 10].join(" ")),
+            segment(3.0, 7.0, "slow"),
+            // 1초보다 짧은 세그먼트는 속도를 판단하지 않음
+            segment(7.0, 7.5, &["word"; 10].join(" ")),
+        ]);
+        let paces: Vec<SpeakingPace> = rate.segments.iter().map(|s| s.pace).collect();
+        assert_eq!(paces, vec![SpeakingPace::Normal, SpeakingPace::Fast, SpeakingPace::Slow, SpeakingPace::Normal]);
+        assert_eq!((rate.fast_count, rate.slow_count, rate.total_words), (1, 1, 24));
+        assert_eq!(rate.speaking_seconds, 7.5);
+        assert!((rate.overall_words_per_minute - 192.0).abs() < 1e-9);
+        
+        assert_eq!(compute_speaking_rate(&[]).overall_words_per_minute, 0.0);
+    }
+}
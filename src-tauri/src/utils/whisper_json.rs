@@ -0,0 +1,146 @@
+use serde_json::Value;
+use crate::models::{ScoredSegment, TimedWord, WordTimedLine};
+
+/// whisper.cpp JSON 출력(--output-json / --output-json-full)을 세그먼트 목록으로 파싱합니다
+///
+/// 세그먼트 신뢰도는 특수 토큰("[_BEG_]" 등)을 제외한 토큰 확률(`p`)의 평균입니다.
+/// 토큰 정보는 --output-json-full로 생성한 경우에만 포함됩니다.
+pub fn parse_whisper_json_segments(content: &str) -> anyhow::Result<Vec<ScoredSegment>> {
+    let root: Value = serde_json::from_str(content)?;
+    let transcription = root.get("transcription")
+        .and_then(|t| t.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid whisper JSON: missing transcription array"))?;
+    
+    let segments = transcription.iter()
+        .filter_map(|item| {
+            // offsets는 밀리초 단위
+            let offsets = item.get("offsets")?;
+            let start = offsets.get("from")?.as_f64()? / 1000.0;
+            let end = offsets.get("to")?.as_f64()? / 1000.0;
+            let text = item.get("text")?.as_str()?.trim().to_string();
+            
+            Some(ScoredSegment { start, end, text, confidence: segment_confidence(item) })
+        })
+        .collect();
+    
+    Ok(segments)
+}
+
+/// --output-json-full 출력의 토큰 타이밍으로 세그먼트별 단어 목록을 만듭니다
+///
+/// 공백으로 시작하지 않는 토큰은 앞 단어의 일부(서브워드)로 보고 이어붙입니다.
+/// 토큰 정보가 하나도 없으면 None을 반환합니다.
+pub fn parse_whisper_json_words(content: &str) -> anyhow::Result<Option<Vec<WordTimedLine>>> {
+    let root: Value = serde_json::from_str(content)?;
+    let transcription = root.get("transcription")
+        .and_then(|t| t.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid whisper JSON: missing transcription array"))?;
+    
+    let mut has_tokens = false;
+    let mut lines = Vec::new();
+    
+    for item in transcription {
+        let tokens = match item.get("tokens").and_then(|t| t.as_array()) {
+            Some(tokens) => tokens,
+            None => continue,
+        };
+        has_tokens = true;
+        
+        let mut words: Vec<TimedWord> = Vec::new();
+        for token in tokens {
+            let text = token.get("text").and_then(|t| t.as_str()).unwrap_or("");
+            if text.is_empty() || (text.starts_with("[_") && text.ends_with(']')) {
+                continue;
+            }
+            let (start, end) = match token.get("offsets")
+                .and_then(|o| Some((o.get("from")?.as_f64()?, o.get("to")?.as_f64()?)))
+            {
+                Some((from, to)) => (from / 1000.0, to / 1000.0),
+                None => continue,
+            };
+            
+            match words.last_mut() {
+                Some(word) if !text.starts_with(' ') => {
+                    word.text.push_str(text);
+                    word.end = end;
+                }
+                _ => words.push(TimedWord { start, end, text: text.trim().to_string() }),
+            }
+        }
+        
+        words.retain(|word| !word.text.is_empty());
+        if let (Some(first), Some(last)) = (words.first(), words.last()) {
+            lines.push(WordTimedLine { start: first.start, end: last.end, words: words.clone() });
+        }
+    }
+    
+    Ok(if has_tokens { Some(lines) } else { None })
+}
+
+/// 신뢰도가 있는 세그먼트들의 평균 신뢰도 (하나도 없으면 None)
+pub fn average_confidence(segments: &[ScoredSegment]) -> Option<f32> {
+    let confidences: Vec<f64> = segments.iter().filter_map(|s| s.confidence).collect();
+    if confidences.is_empty() {
+        return None;
+    }
+    
+    Some((confidences.iter().sum::<f64>() / confidences.len() as f64) as f32)
+}
+
+fn segment_confidence(item: &Value) -> Option<f64> {
+    let probabilities: Vec<f64> = item.get("tokens")?
+        .as_array()?
+        .iter()
+        .filter(|token| {
+            let text = token.get("text").and_then(|t| t.as_str()).unwrap_or("");
+            !(text.starts_with("[_") && text.ends_with(']'))
+        })
+        .filter_map(|token| token.get("p").and_then(|p| p.as_f64()))
+        .collect();
+    
+    if probabilities.is_empty() {
+        return None;
+    }
+    
+    Some(probabilities.iter().sum::<f64>() / probabilities.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const FULL_JSON: &str = r#"{"transcription": [
+        {"offsets": {"from": 0, "to": 1500}, "text": " Hello world", "tokens": [
+            {"text": "[_BEG_]", "p": 0.1, "offsets": {"from": 0, "to": 0}},
+            {"text": " Hel", "p": 0.9, "offsets": {"from": 0, "to": 400}},
+            {"text": "lo", "p": 0.7, "offsets": {"from": 400, "to": 700}},
+            {"text": " world", "p": 0.8, "offsets": {"from": 800, "to": 1500}}
+        ]},
+        {"offsets": {"from": 1500, "to": 3000}, "text": " No tokens"}
+    ]}"#;
+    
+    #[test]
+    fn segment_confidence_skips_special_tokens() {
+        let segments = parse_whisper_json_segments(FULL_JSON).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[0].start, segments[0].end, segments[0].text.as_str()), (0.0, 1.5, "Hello world"));
+        assert!((segments[0].confidence.unwrap() - 0.8).abs() < 1e-9);
+        assert!(segments[1].confidence.is_none());
+        
+        // 신뢰도가 없는 세그먼트는 평균에서 제외
+        assert!((average_confidence(&segments).unwrap() - 0.8).abs() < 1e-6);
+        assert_eq!(average_confidence(&segments[1..]), None);
+        assert!(parse_whisper_json_segments(r#"{"result": {}}"#).is_err());
+    }
+    
+    #[test]
+    fn words_join_subword_tokens() {
+        let lines = parse_whisper_json_words(FULL_JSON).unwrap().unwrap();
+        assert_eq!(lines.len(), 1);
+        let words: Vec<(&str, f64, f64)> = lines[0].words.iter().map(|w| (w.text.as_str(), w.start, w.end)).collect();
+        assert_eq!(words, vec![("Hello", 0.0, 0.7), ("world", 0.8, 1.5)]);
+        
+        let without_tokens = r#"{"transcription": [{"offsets": {"from": 0, "to": 1000}, "text": "hi"}]}"#;
+        assert!(parse_whisper_json_words(without_tokens).unwrap().is_none());
+    }
+}
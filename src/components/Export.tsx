@@ -26,6 +26,13 @@ const exportFormats: ExportFormat[] = [
     description: '자막 파일 (비디오 편집용)',
     icon: '🎬'
   },
+  {
+    id: 'ass',
+    name: 'Advanced SubStation (ASS)',
+    extension: 'ass',
+    description: '화자별 색상 자막 파일',
+    icon: '🎨'
+  },
   {
     id: 'fcpxml',
     name: 'Final Cut Pro XML',
@@ -86,6 +93,9 @@ export const Export: React.FC = React.memo(() => {
         case 'srt':
           await whisperApi.exportToSrt(transcriptionText, savePath);
           break;
+        case 'ass':
+          await whisperApi.exportToAss(transcriptionText, savePath);
+          break;
         case 'fcpxml':
           await whisperApi.exportToFcpxml(transcriptionText, savePath);
           break;
@@ -318,6 +318,7 @@ export const Management: React.FC = React.memo(() => {
                       <span className="text-gray-600">
                         {progress?.status === 'Starting' && t('download.starting')}
                         {progress?.status === 'Downloading' && t('download.downloading')}
+                        {progress?.status === 'Verifying' && t('download.verifying')}
                         {progress?.status === 'Completed' && t('common.completed')}
                         {progress?.status === 'Failed' && t('common.failed')}
                         {!progress && t('download.downloading')}
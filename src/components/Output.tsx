@@ -168,6 +168,20 @@ export const Output: React.FC = React.memo(() => {
     }
   };
 
+  // 깨진 문자가 있는 결과를 줄 길이 제한 없이 다시 실행
+  const rerunWithoutLineLimit = async (historyId: string) => {
+    try {
+      const check = await whisperApi.checkEncodingIssues(historyId);
+      if (check.rerun_config) {
+        await whisperApi.startTranscriptionWithOptions(check.rerun_config);
+        loadHistory(0, true);
+      }
+    } catch (error) {
+      console.error('Failed to re-run transcription:', error);
+      // TODO: 에러 토스트 표시
+    }
+  };
+
   // 더 보기
   const loadMore = () => {
     if (historyData.has_more && !loading) {
@@ -201,8 +215,10 @@ export const Output: React.FC = React.memo(() => {
   const getStatusBadge = (status: string) => {
     const statusMap = {
       'Completed': { color: 'bg-green-100 text-green-800', text: t('output.completed') },
+      'Queued': { color: 'bg-purple-100 text-purple-800', text: t('output.queued') },
       'Running': { color: 'bg-blue-100 text-blue-800', text: t('output.running') },
       'Failed': { color: 'bg-red-100 text-red-800', text: t('output.failed') },
+      'Cancelled': { color: 'bg-yellow-100 text-yellow-800', text: t('output.cancelled') },
       'Idle': { color: 'bg-gray-100 text-gray-800', text: t('output.idle') }
     };
     const statusInfo = statusMap[status as keyof typeof statusMap] || statusMap.Idle;
@@ -218,8 +234,10 @@ export const Output: React.FC = React.memo(() => {
   const getStatusText = (status: string) => {
     const statusMap = {
       'Completed': t('output.completed'),
+      'Queued': t('output.queued'),
       'Running': t('output.running'),
       'Failed': t('output.failed'),
+      'Cancelled': t('output.cancelled'),
       'Idle': t('output.idle')
     };
     return statusMap[status as keyof typeof statusMap] || status;
@@ -293,8 +311,10 @@ export const Output: React.FC = React.memo(() => {
           >
             <option value="">{t('output.allStatuses')}</option>
             <option value="Completed">{t('output.completed')}</option>
+            <option value="Queued">{t('output.queued')}</option>
             <option value="Running">{t('output.running')}</option>
             <option value="Failed">{t('output.failed')}</option>
+            <option value="Cancelled">{t('output.cancelled')}</option>
           </select>
         </div>
 
@@ -390,6 +410,7 @@ export const Output: React.FC = React.memo(() => {
               onDownloadFile={downloadFile}
               onDeleteHistory={deleteHistory}
               onSaveNotes={saveNotes}
+              onRerunWithoutLineLimit={rerunWithoutLineLimit}
               editingNotes={editingNotes}
               setEditingNotes={setEditingNotes}
               noteText={noteText}
@@ -435,6 +456,7 @@ interface HistoryItemProps {
   onDownloadFile: (historyId: string, format: string) => void;
   onDeleteHistory: (historyId: string) => void;
   onSaveNotes: (historyId: string) => void;
+  onRerunWithoutLineLimit: (historyId: string) => void;
   editingNotes: string | null;
   setEditingNotes: (id: string | null) => void;
   noteText: string;
@@ -452,6 +474,7 @@ const HistoryItem: React.FC<HistoryItemProps> = React.memo(({
   onDownloadFile,
   onDeleteHistory,
   onSaveNotes,
+  onRerunWithoutLineLimit,
   editingNotes,
   setEditingNotes,
   noteText,
@@ -526,6 +549,7 @@ const HistoryItem: React.FC<HistoryItemProps> = React.memo(({
               >
                 <ArrowDownTrayIcon className="h-3 w-3 mr-1" />
                 {result.format.toUpperCase()} ({formatFileSize(result.file_size)})
+                {result.partial && ` · ${t('output.partial')}`}
               </button>
             ))}
           </div>
@@ -543,7 +567,10 @@ const HistoryItem: React.FC<HistoryItemProps> = React.memo(({
                 {item.results.map((result, index) => (
                   <div key={index} className="flex items-center justify-between p-3 bg-white rounded border">
                     <div>
-                      <div className="font-medium">{result.format.toUpperCase()}</div>
+                      <div className="font-medium">
+                        {result.format.toUpperCase()}
+                        {result.partial && <span className="ml-2 text-xs text-yellow-700">{t('output.partial')}</span>}
+                      </div>
                       <div className="text-sm text-gray-500">
                         {formatFileSize(result.file_size)} • {formatDate(result.created_at)}
                       </div>
@@ -618,6 +645,26 @@ const HistoryItem: React.FC<HistoryItemProps> = React.memo(({
               </div>
             </div>
           )}
+
+          {/* 품질 경고 */}
+          {item.warnings?.map((warning) => (
+            <div key={warning.code} className="mt-4 p-3 bg-yellow-50 border border-yellow-200 rounded-md">
+              <div className="flex items-center">
+                <div className="text-yellow-500 mr-2">⚠</div>
+                <div className="text-yellow-800 text-sm" title={warning.message}>
+                  {t(`output.warnings.${warning.code}`, { defaultValue: warning.message })}
+                </div>
+                {warning.code === 'possible_encoding_issue' && (
+                  <button
+                    onClick={() => onRerunWithoutLineLimit(item.id)}
+                    className="ml-auto px-3 py-1 bg-yellow-100 text-yellow-800 rounded-md hover:bg-yellow-200 text-sm"
+                  >
+                    {t('output.rerunWithoutLineLimit')}
+                  </button>
+                )}
+              </div>
+            </div>
+          ))}
         </div>
       )}
     </div>
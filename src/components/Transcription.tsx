@@ -2,13 +2,14 @@ import React, { useState, useEffect, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import { open } from '@tauri-apps/api/dialog';
 import { listen } from '@tauri-apps/api/event';
-import { whisperApi, ProgressInfo, WhisperOptions, WhisperConfig } from '../services/api';
+import { whisperApi, ProgressInfo, TranscriptionPhase, WhisperOptions, WhisperConfig } from '../services/api';
 import { OptionsForm } from './OptionsForm';
 
 interface TranscriptionState {
   currentFile: string | null;
   status: 'idle' | 'running' | 'completed' | 'failed';
   progress: number;
+  phase: TranscriptionPhase | null;
   logs: string[];
   result: string | null;
 }
@@ -19,6 +20,7 @@ export const Transcription: React.FC = React.memo(() => {
     currentFile: null,
     status: 'idle',
     progress: 0,
+    phase: null,
     logs: [],
     result: null,
   });
@@ -74,7 +76,10 @@ export const Transcription: React.FC = React.memo(() => {
         filters: [
           {
             name: 'Audio Files',
-            extensions: ['mp3', 'wav', 'm4a', 'flac', 'aac', 'ogg', 'mp4', 'avi', 'mov', 'mkv']
+            extensions: [
+              'mp3', 'wav', 'm4a', 'flac', 'aac', 'ogg', 'oga', 'opus', 'wma', 'aiff', 'aif', 'amr', 'mka',
+              'mp4', 'avi', 'mov', 'mkv', 'webm', 'm4v', 'wmv', 'flv', 'mpg', 'mpeg', '3gp', 'ts'
+            ]
           }
         ]
       });
@@ -96,6 +101,7 @@ export const Transcription: React.FC = React.memo(() => {
       ...prev, 
       status: 'running', 
       progress: 0, 
+      phase: null,
       logs: [],
       result: null 
     }));
@@ -126,6 +132,7 @@ export const Transcription: React.FC = React.memo(() => {
       currentFile: null,
       status: 'idle',
       progress: 0,
+      phase: null,
       logs: [],
       result: null,
     });
@@ -139,6 +146,7 @@ export const Transcription: React.FC = React.memo(() => {
         setState(prev => ({ 
           ...prev, 
           progress: event.payload.progress,
+          phase: event.payload.phase ?? prev.phase,
           logs: [...prev.logs, `📊 ${event.payload.message}`]
         }));
       });
@@ -271,7 +279,10 @@ export const Transcription: React.FC = React.memo(() => {
           {state.status === 'running' && (
             <div className="space-y-3">
               <div className="flex items-center justify-between">
-                <span className="text-sm text-gray-600">{t('transcription.transcriptionProgress')}</span>
+                <span className="text-sm text-gray-600">
+                  {t('transcription.transcriptionProgress')}
+                  {state.phase && ` · ${t(`transcription.phases.${state.phase}`)}`}
+                </span>
                 <span className="text-sm font-medium">{Math.round(state.progress * 100)}%</span>
               </div>
               <div className="w-full bg-gray-200 rounded-full h-2">
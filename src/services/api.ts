@@ -1,9 +1,19 @@
 import { invoke } from '@tauri-apps/api/tauri';
 
+export type TranscriptionPhase = 'Queued' | 'Preprocessing' | 'LoadingModel' | 'Transcribing' | 'Finalizing';
+
 export interface ProgressInfo {
-  progress: number;
+  progress: number;             // 전처리를 포함한 전체 진행률 (0.0 ~ 1.0)
   current_time?: number;
   message: string;
+  phase?: TranscriptionPhase;
+}
+
+export interface TranscriptionSegmentEvent {
+  history_id: string;
+  start: number;            // 오디오 구간 시작 (초)
+  end: number;              // 오디오 구간 끝 (초)
+  text: string;
 }
 
 export interface WhisperOption {
@@ -23,6 +33,20 @@ export interface WhisperConfig {
   model: string;
   input_file: string;
   options: Record<string, string>;
+  adaptive_threads?: boolean;   // 진행이 비정상적으로 느리면 스레드 수를 줄여 재시도
+  export_formats?: string[];    // 완료 시 자막 결과에서 추가로 파생할 형식들 (txt, srt, vtt, fcpxml)
+  preserve_source_mtime?: boolean; // 결과 파일의 수정 시간을 원본 미디어와 같게 맞춤
+  process_priority?: ProcessPriority; // whisper 프로세스의 OS 우선순위
+  audio_track?: AudioTrackPreference; // 여러 오디오 트랙 중 변환에 사용할 트랙
+}
+
+// 화면 녹화 등에서 사용할 오디오 트랙 (Mic: 두 번째 트랙, System: 첫 번째 트랙, Mix: 전체 합성)
+export type AudioTrackPreference = 'Mic' | 'System' | 'Mix';
+
+export interface AudioTrackChoice {
+  preference: AudioTrackPreference;
+  audio_stream_count: number;
+  fallback: boolean;              // 트랙이 하나뿐이라 유일한 트랙을 사용함
 }
 
 export interface DownloadProgress {
@@ -32,14 +56,26 @@ export interface DownloadProgress {
   total_bytes?: number;
   download_speed?: string;
   eta?: string;
-  status: 'Starting' | 'Downloading' | 'Completed' | 'Failed' | 'Cancelled';
+  status: 'Starting' | 'Downloading' | 'Verifying' | 'Completed' | 'Failed' | 'Cancelled';
 }
 
+export type TranscriptionStatus = 'Idle' | 'Queued' | 'Running' | 'Completed' | 'Failed' | 'Cancelled';
+
 export interface TranscriptionResult {
   file_path: string;
   format: string;         // "txt", "srt", "vtt", "csv", "json", "lrc"
   file_size: number;      // 파일 크기 (bytes)
   created_at: string;     // ISO 8601 timestamp
+  partial: boolean;       // 취소되어 일부만 담긴 파일인지 여부
+  language?: string;      // 번역 결과의 대상 언어 (원본 결과는 없음)
+  variant?: string;       // 원본에서 파생한 결과의 종류 (예: "dedup")
+}
+
+export interface LoadedModelInfo {
+  model_type?: string;    // 예: "tiny", "large v3"
+  n_vocab?: number;       // 51864면 영어 전용 모델
+  n_audio_layer?: number;
+  model_size_mb?: number;
 }
 
 export interface TranscriptionHistory {
@@ -49,13 +85,54 @@ export interface TranscriptionHistory {
   model_used: string;                 // 사용된 모델
   options_used: Record<string, string>; // 사용된 옵션들
   results: TranscriptionResult[];     // 생성된 결과 파일들
-  status: 'Idle' | 'Running' | 'Completed' | 'Failed'; // 변환 상태
+  status: TranscriptionStatus;        // 변환 상태
   created_at: string;                 // 변환 시작 시간 (ISO 8601)
   completed_at?: string;              // 변환 완료 시간 (ISO 8601)
   duration_seconds?: number;          // 변환 소요 시간 (초)
   tags: string[];                     // 사용자 태그들
   notes?: string;                     // 사용자 메모
   error_message?: string;             // 실패 시 에러 메시지
+  warnings: HistoryWarning[];         // 결과 품질 관련 경고들
+  thread_attempts: ThreadAttempt[];   // adaptive_threads 시도 기록 (마지막이 최종)
+  custom_fields: Record<string, string>; // 사용자 정의 메타데이터
+  processed_until_seconds?: number;      // 이어서 변환으로 처리된 마지막 오디오 위치 (초)
+  segment_timings: SegmentTiming[];      // 세그먼트별 처리 시간 (측정된 경우에만)
+  input_file_size?: number;              // 변환 시작 시점의 원본 파일 크기 (bytes)
+  applied_language_rule?: string;        // 적용된 언어 규칙 (자동 감지된 언어 코드)
+  process_priority?: ProcessPriority;    // 변환 시 사용한 프로세스 우선순위
+  avg_confidence?: number;               // JSON 결과의 세그먼트 신뢰도 평균
+  whisper_commit?: string;               // 변환 시작 시점의 whisper.cpp 커밋 해시
+  audio_track?: AudioTrackChoice;        // 오디오 트랙을 선택해 변환한 경우의 선택 결과
+  preview?: string;                      // 목록 표시용 결과 텍스트 앞부분
+  loaded_model?: LoadedModelInfo;        // whisper가 실제로 읽은 모델 정보 (whisper_model_load 로그 기준)
+  export_formats?: string[];             // 변환 시 요청한 추가 파생 형식 (재실행 시 복원)
+  preserve_source_mtime?: boolean;       // 결과 파일 수정 시간을 원본에 맞추도록 요청했는지 여부
+}
+
+export interface ThreadAttempt {
+  threads: number;
+  aborted_as_slow: boolean;   // 느린 진행으로 중단되었는지 여부
+}
+
+export interface HistoryWarning {
+  code: string;       // 번역용 코드 (예: "language_mismatch")
+  message: string;    // 상세 설명
+}
+
+export type TextScript = 'Latin' | 'Hangul' | 'Kana' | 'Han' | 'Cyrillic' | 'Arabic';
+
+export interface LanguageCheck {
+  requested_language: string;
+  expected_scripts: TextScript[];
+  detected_script?: TextScript;
+  mismatch: boolean;
+}
+
+export interface EncodingCheck {
+  replacement_chars: number;     // 결과 파일들에서 찾은 U+FFFD 수
+  affected_formats: string[];
+  possible_issue: boolean;
+  rerun_config?: WhisperConfig;  // 문제가 있으면 줄 길이 제한 없이 다시 실행할 설정
 }
 
 export interface HistoryQuery {
@@ -65,9 +142,11 @@ export interface HistoryQuery {
   model_filter?: string;    // 모델별 필터
   format_filter?: string;   // 형식별 필터
   tag_filter?: string;      // 태그별 필터
-  status_filter?: 'Idle' | 'Running' | 'Completed' | 'Failed'; // 상태별 필터
+  status_filter?: TranscriptionStatus; // 상태별 필터
   date_from?: string;       // 시작 날짜 (ISO 8601)
   date_to?: string;         // 종료 날짜 (ISO 8601)
+  custom_field_filter?: [string, string]; // 사용자 정의 필드 (키, 값) 일치 필터
+  needs_review_filter?: number;  // 평균 신뢰도가 이 값보다 낮은 항목만 (신뢰도가 없는 항목은 제외)
 }
 
 export interface HistoryListResponse {
@@ -76,13 +155,424 @@ export interface HistoryListResponse {
   has_more: boolean;
 }
 
+export interface ValueChange {
+  from: string;
+  to: string;
+}
+
+export interface HistoryOptionsDiff {
+  model_changed?: ValueChange;              // 모델 변경 (a → b)
+  added: Record<string, string>;            // b에만 있는 옵션
+  removed: Record<string, string>;          // a에만 있는 옵션
+  changed: Record<string, ValueChange>;     // 값이 다른 옵션
+  language_changed?: ValueChange;           // 언어 변경 (미지정 시 auto)
+  task_changed?: ValueChange;               // transcribe ↔ translate
+}
+
+export interface AppInfo {
+  app_version: string;          // CARGO_PKG_VERSION
+  target_triple: string;        // 빌드 타겟
+  build_profile: string;        // debug / release
+  whisper_cpp_repo: string;     // whisper.cpp 저장소 URL
+  whisper_cpp_ref?: string;     // 고정된 whisper.cpp ref (없으면 기본 브랜치)
+}
+
+export interface QueueEntry {
+  history_id: string;
+  position: number;             // 1부터 시작 (실행 중인 변환이 앞쪽)
+  status: TranscriptionStatus;  // Running 또는 Queued
+}
+
+export interface QueueStatus {
+  paused: boolean;      // true면 대기 중인 변환이 시작되지 않음 (queue-status 이벤트로도 전달)
+  queued: number;
+  running: number;      // 입력을 준비 중인 변환 포함
+}
+
+export interface BatchProgress {
+  completed: number;      // 끝난 파일 수 (실패/취소 포함)
+  total: number;
+  current_file?: string;  // 실행 중인 파일 이름
+}
+
+export interface ScheduledTranscription {
+  id: string;
+  config: WhisperConfig;
+  run_at: string;       // 대기열에 넣을 시각 (RFC 3339)
+  created_at: string;
+}
+
+export interface TailResult {
+  lines: string[];      // 마지막 줄부터 역순
+  has_more: boolean;    // 앞쪽에 더 많은 내용이 있는지 여부
+}
+
+export type VttPosition = 'Top' | 'Middle' | 'Bottom';
+export type VttAlign = 'Start' | 'Center' | 'End' | 'Left' | 'Right';
+
+export interface SubtitleLayout {
+  max_chars_per_line?: number; // 줄바꿈 기준 글자 수 (없으면 줄바꿈/분할하지 않음)
+  max_lines?: number;          // 큐당 최대 줄 수 (넘으면 다음 큐로 분할)
+}
+
+export interface VttStyleOptions {
+  position?: VttPosition;   // 세로 위치 (line 설정)
+  align?: VttAlign;         // 텍스트 정렬 (align 설정)
+  class?: string;           // 큐 텍스트를 감쌀 CSS 클래스
+}
+
+export interface DeduplicationReport {
+  format: string;
+  output_path: string;      // 정리한 자막 파일 (원본은 그대로 둠)
+  original_count: number;   // 정리 전 세그먼트 수
+  remaining_count: number;  // 정리 후 세그먼트 수
+  merged_count: number;     // 앞 세그먼트에 병합된 중복 수
+}
+
+export interface SegmentMergeReport {
+  format: string;
+  output_path: string;      // 병합 결과 파일 경로
+  original_count: number;   // 병합 전 세그먼트 수
+  merged_count: number;     // 병합 후 세그먼트 수
+}
+
+export interface TimeRange {
+  start: number;
+  end: number;
+}
+
+export interface SubtitleCoverage {
+  audio_duration_seconds: number;
+  duration_estimated: boolean;  // 마지막 큐 종료 시간으로 길이를 추정했는지 여부
+  covered_seconds: number;
+  coverage_ratio: number;       // 0.0 ~ 1.0
+  largest_gap?: TimeRange;      // 자막이 없는 가장 긴 구간
+  cue_count: number;
+  cues_per_minute: number;
+}
+
+export type SpeakingPace = 'Normal' | 'Fast' | 'Slow';
+
+export interface SegmentSpeakingRate {
+  start: number;
+  end: number;
+  word_count: number;
+  words_per_minute: number;
+  pace: SpeakingPace;       // Fast: 인식 오류 가능성, Slow: 무음이 음성으로 잡혔을 가능성
+}
+
+export interface SpeakingRate {
+  overall_words_per_minute: number; // 큐가 있는 시간 기준 (큐 사이 공백 제외)
+  total_words: number;
+  speaking_seconds: number;
+  fast_count: number;
+  slow_count: number;
+  segments: SegmentSpeakingRate[];
+}
+
+export interface ConfidenceExportReport {
+  output_path: string;
+  kept_count: number;
+  dropped_count: number;    // 임계값 미만으로 제외된 세그먼트 수
+}
+
+export type SplitStrategy =
+  | { MaxDuration: number }   // 파트당 최대 길이 (초)
+  | { MaxCues: number }       // 파트당 최대 큐 개수
+  | { MaxBytes: number };     // 파트당 최대 파일 크기 (bytes)
+
+export interface SplitOptions {
+  strategy: SplitStrategy;
+  output_dir: string;
+  rebase_timings?: boolean;   // true면 각 파트가 0초부터 시작
+}
+
+export interface ExportFormatInfo {
+  id: string;                   // "txt", "srt", ...
+  display_name: string;
+  requires_timestamps: boolean;
+  produced_by_whisper: boolean; // whisper.cpp가 직접 생성하는지 여부
+  derivable: boolean;           // 앱이 자막 결과에서 변환할 수 있는지 여부
+}
+
+export interface SegmentTiming {
+  start: number;              // 오디오 구간 시작 (초)
+  end: number;                // 오디오 구간 끝 (초)
+  processing_seconds: number; // 직전 세그먼트 이후 출력까지 걸린 시간
+}
+
+export interface TranscriptSearchOptions {
+  case_sensitive?: boolean;
+  regex?: boolean;          // 검색어를 정규식으로 해석
+  context_lines?: number;   // 일치한 줄 앞뒤로 포함할 줄 수
+  max_results?: number;     // 결과 최대 개수 (기본 200)
+}
+
+export interface TranscriptSearchHit {
+  history_id: string;
+  original_file_name: string;
+  format: string;
+  line_index: number;
+  line: string;
+  context_before: string[];
+  context_after: string[];
+  timestamp?: number;       // srt/vtt에서 찾은 경우 큐 시작 시간 (초)
+}
+
+export interface TranscriptSearchResponse {
+  hits: TranscriptSearchHit[];
+  truncated: boolean;       // 결과 최대 개수에 도달했는지 여부
+}
+
+export interface TranscriptionResources {
+  history_id: string;
+  pid?: number;
+  running: boolean;         // 프로세스가 이미 끝났으면 false
+  rss_bytes?: number;       // 상주 메모리
+  cpu_percent?: number;     // 직전 측정 이후 CPU 사용률 (여러 코어 사용 시 100% 초과 가능)
+}
+
+export interface TranscriptionEta {
+  history_id: string;
+  progress: number;             // 0.0 ~ 1.0
+  elapsed_seconds: number;
+  remaining_seconds?: number;   // 진행 속도를 아직 추정할 수 없으면 없음
+}
+
+export interface DownloadScriptStatus {
+  path: string;
+  exists: boolean;
+  readable: boolean;
+  model_count: number;      // 스크립트에서 파싱한 모델 수
+  error?: string;           // 읽기/파싱 실패 사유
+}
+
+export interface DuplicateHistoryGroup {
+  original_file_path: string;
+  input_file_size?: number;   // 크기를 알 수 없으면 경로만으로 묶음
+  history_ids: string[];      // 최신순
+}
+
+export interface ModelValidation {
+  model_name: string;
+  valid: boolean;
+}
+
+// download-verified 이벤트 페이로드
+export interface ModelVerification {
+  model_name: string;
+  passed: boolean;
+  error?: string;
+  repairable: boolean;      // 실패했고 다시 받을 수 있는 공식 모델 (repairModel 제안용)
+}
+
+export type QuantizeStatus = 'Building' | 'Quantizing' | 'Validating' | 'Completed' | 'Failed';
+
+export interface QuantizeProgress {
+  source_model: string;
+  target_model: string;   // 예: "large-v3-q5_0"
+  status: QuantizeStatus;
+  message?: string;       // quantize 출력 줄 또는 에러 메시지
+}
+
+export interface ModelChecksum {
+  model_name: string;
+  expected?: string;      // 기대 SHA256 (알 수 없는 모델이면 없음)
+  actual: string;         // 내려받은 파일의 SHA256
+  matches?: boolean;      // 기대값을 모르면 없음
+}
+
+export type ModelRepairStatus = 'Repairing' | 'Repaired' | 'Failed' | 'Unrepairable';
+
+export interface ModelRepairProgress {
+  model_name: string;
+  status: ModelRepairStatus;
+  error?: string;
+}
+
+export interface ModelRepairSummary {
+  repaired: string[];
+  failed: ModelRepairProgress[];  // 실패 사유 포함
+  unrepairable: string[];         // 알려진 다운로드 URL이 없는 모델
+}
+
+export type DownloadTool = 'Wget' | 'Curl';
+
+export interface DownloadToolInfo {
+  tool: DownloadTool;
+  available: boolean;
+  version?: string;         // 예: "1.21.4"
+  supports_resume: boolean; // 중단된 다운로드 이어받기 가능 여부
+  supports_proxy: boolean;  // http_proxy/https_proxy 환경 변수 지원 여부
+}
+
+export interface DownloadCapabilities {
+  tools: DownloadToolInfo[];
+  preferred?: DownloadTool; // 다운로드에 실제로 사용하는 도구
+}
+
+export interface ModelDownloadSize {
+  model_name: string;
+  bytes: number;
+  display: string;          // 사람이 읽기 쉬운 크기 (예: "2.9 GB")
+  estimated: boolean;       // 서버에 연결할 수 없어 예상 크기를 사용했는지 여부
+}
+
+export type MediaCategory = 'Video' | 'Audio' | 'Music' | 'Unknown';
+
+export interface OutputFormatSuggestion {
+  category: MediaCategory;
+  formats: string[];        // UI에서 미리 선택할 형식 ID
+}
+
+export type AudioQuality = 'Good' | 'Fair' | 'Poor';
+
+export type AudioIssue = 'MostlySilent' | 'HeavilyClipped' | 'TooQuiet';
+
+export interface AudioAnalysis {
+  file_path: string;
+  duration_seconds?: number;
+  mean_volume_db?: number;
+  max_volume_db?: number;
+  clipped_samples: number;  // 0dB에 닿은 샘플 수
+  clipped_ratio: number;    // 전체 샘플 중 클리핑 비율 (0.0 ~ 1.0)
+  silence_seconds: number;
+  silence_ratio: number;    // 전체 길이 중 무음 비율 (0.0 ~ 1.0)
+  quality: AudioQuality;
+  issues: AudioIssue[];
+}
+
+export interface ModelProvenance {
+  model_name: string;
+  base_model: string;       // 양자화 변형이면 원본 모델 이름
+  quantized: boolean;
+  original_author: string;  // 원본 가중치 제공자
+  source_url: string;
+  license: string;          // SPDX 식별자 (예: "MIT")
+  license_url: string;
+  repository_url: string;   // ggml 변환 모델 저장소
+  download_url: string;
+}
+
+export type ModelFileFormat = 'Ggml' | 'Gguf';
+
+export interface ModelHeader {
+  file_path: string;
+  file_size: number;
+  format: ModelFileFormat;
+  gguf_version?: number;
+  architecture?: string;
+  n_vocab?: number;
+  n_audio_ctx?: number;
+  n_audio_state?: number;
+  n_audio_head?: number;
+  n_audio_layer?: number;
+  n_text_ctx?: number;
+  n_text_state?: number;
+  n_text_head?: number;
+  n_text_layer?: number;
+  n_mels?: number;
+  ftype?: string;           // 가중치 자료형 (예: "f16", "q5_0")
+  model_type?: string;      // 인코더 레이어 수로 추정한 크기 등급
+  multilingual?: boolean;
+  metadata: Record<string, string>; // gguf 키-값 메타데이터 (ggml은 비어 있음)
+}
+
+export type SummaryFormat = 'Markdown' | 'Html';
+
+export type ProcessPriority = 'Low' | 'Normal' | 'High';
+
+export interface ResultQuality {
+  history_id: string;
+  format: string;
+  is_empty: boolean;            // 파일 크기가 0
+  is_whitespace_only: boolean;  // 내용이 공백/줄바꿈뿐
+  suspiciously_short: boolean;  // 오디오 길이에 비해 텍스트가 지나치게 적음
+  repetition_ratio: number;     // 앞에서 이미 나온 줄과 같은 줄의 비율 (0.0 ~ 1.0)
+  line_count: number;           // 텍스트가 있는 줄(자막은 큐) 수
+  audio_duration_seconds?: number; // 판단에 사용한 오디오 길이
+}
+
+export interface ResourceLimits {
+  open_files_soft?: number;   // 열린 파일 수 soft 제한 (무제한이거나 알 수 없으면 없음)
+  open_files_hard?: number;
+  open_files_in_use?: number; // 현재 열려 있는 디스크립터 수
+  max_processes?: number;     // 사용자당 프로세스 수 제한
+}
+
+export interface BatchLimitCheck {
+  batch_size: number;
+  safe_concurrency: number;   // 현재 제한에서 동시에 실행해도 안전한 변환 수
+  within_limits: boolean;
+  warnings: string[];
+  limits: ResourceLimits;
+}
+
+export interface BatchExportResult {
+  history_id: string;
+  output_path?: string;     // 실패하면 없음
+  derived: boolean;         // 저장된 결과가 없어 다른 결과에서 변환했는지 여부
+  error?: string;
+}
+
+export interface DeliveryPackage {
+  output_path: string;      // 만들어진 zip 경로
+  included_formats: string[];
+  derived_formats: string[]; // 저장된 결과가 없어 다른 결과에서 변환한 형식들
+}
+
+export interface AppSettings {
+  data_dir: string;     // 모델/결과/저장소/인덱스 루트 디렉토리
+  language_rules: Record<string, Record<string, string>>; // 자동 감지된 언어 → 덮어쓸 옵션
+  ffmpeg_path?: string;     // 지정하지 않으면 PATH의 ffmpeg 사용
+  ffprobe_path?: string;    // 지정하지 않으면 PATH의 ffprobe 사용
+  export_filename_template: string; // 일괄 내보내기 파일 이름 ({name}, {id}, {date}, {model}; 확장자 제외)
+  post_hook_command?: string; // 변환 완료 후 실행할 셸 명령 (주의: 임의 명령 실행, 기본값 꺼짐)
+  default_output_formats: string[]; // 출력 형식을 지정하지 않았을 때 사용할 형식 (비어 있으면 srt)
+  verify_after_download: boolean; // 모델 다운로드 직후 샘플 오디오로 변환 확인
+  translator_command?: string; // 결과 번역에 사용할 셸 명령 (stdin 텍스트 → stdout 번역, 줄 수 유지)
+  custom_binary_path?: string; // 직접 빌드한 whisper-cli 경로 (유효하면 기본 탐색 대신 사용)
+  max_concurrent: number;   // 대기열에서 동시에 실행할 변환 수
+  build_backend?: BuildBackend; // whisper.cpp 빌드 가속 백엔드 (없으면 업스트림 기본값)
+}
+
+export type BuildBackend = 'Cpu' | 'Metal' | 'Cuda' | 'OpenBlas';
+
+export interface GpuBackendDetection {
+  available: BuildBackend[];  // 이 시스템에서 빌드 가능한 백엔드
+  suggested: BuildBackend;    // 기본으로 제안하는 백엔드
+  nvidia_gpus: string[];      // nvidia-smi로 찾은 GPU 이름
+  cuda_toolkit: boolean;      // nvcc 사용 가능 여부
+}
+
+export interface InstallationStatus {
+  installed: boolean;
+  binary_path?: string;           // 실제로 사용할 whisper 실행 파일
+  custom_binary_path?: string;    // 설정된 사용자 지정 바이너리
+  using_custom_binary: boolean;   // 사용자 지정 바이너리가 --help 확인을 통과해 사용 중인지 여부
+  custom_binary_error?: string;   // 사용자 지정 바이너리를 쓸 수 없는 이유 (기본 탐색으로 대체됨)
+  installed_version?: string;     // 마지막 설치 시 기록된 whisper.cpp 버전
+}
+
 export const whisperApi = {
   async checkInstallation(): Promise<boolean> {
     return invoke('check_whisper_installation');
   },
 
-  async setupWhisper(): Promise<string> {
-    return invoke('setup_whisper');
+  async getInstallationStatus(): Promise<InstallationStatus> {
+    return invoke('get_installation_status');
+  },
+
+  async setupWhisper(version?: string, backend?: BuildBackend): Promise<string> {
+    return invoke('setup_whisper', { version, backend });
+  },
+
+  async detectGpuBackends(): Promise<GpuBackendDetection> {
+    return invoke('detect_gpu_backends');
+  },
+
+  async getInstalledWhisperVersion(): Promise<string | null> {
+    return invoke('get_installed_whisper_version');
   },
 
   async listAvailableModels(): Promise<string[]> {
@@ -109,8 +599,29 @@ export const whisperApi = {
     return invoke('read_transcription_result', { filePath });
   },
 
-  async exportToSrt(transcription: string, outputPath: string): Promise<string> {
-    return invoke('export_to_srt', { transcription, outputPath });
+  async exportToStyledVtt(
+    transcription: string,
+    outputPath: string,
+    style?: VttStyleOptions,
+    layout?: SubtitleLayout
+  ): Promise<string> {
+    return invoke('export_to_styled_vtt', { transcription, outputPath, style, layout });
+  },
+
+  async exportToAss(transcription: string, outputPath: string): Promise<string> {
+    return invoke('export_to_ass', { transcription, outputPath });
+  },
+
+  async exportToMarkdown(transcription: string, outputPath: string, baseMediaUrl?: string): Promise<string> {
+    return invoke('export_to_markdown', { transcription, outputPath, baseMediaUrl });
+  },
+
+  async exportToSrt(transcription: string, outputPath: string, layout?: SubtitleLayout): Promise<string> {
+    return invoke('export_to_srt', { transcription, outputPath, layout });
+  },
+
+  async exportToVtt(transcription: string, outputPath: string): Promise<string> {
+    return invoke('export_to_vtt', { transcription, outputPath });
   },
 
   async exportToFcpxml(transcription: string, outputPath: string): Promise<string> {
@@ -121,6 +632,10 @@ export const whisperApi = {
     return invoke('check_system_requirements');
   },
 
+  async getAppInfo(): Promise<AppInfo> {
+    return invoke('get_app_info');
+  },
+
   async getWhisperOptions(): Promise<WhisperOptions> {
     return invoke('get_whisper_options');
   },
@@ -129,6 +644,50 @@ export const whisperApi = {
     return invoke('start_transcription_with_options', { config });
   },
 
+  async enqueueTranscription(config: WhisperConfig): Promise<string> {
+    return invoke('enqueue_transcription', { config });
+  },
+
+  async startBatchTranscription(dirPath: string, config: WhisperConfig): Promise<string[]> {
+    return invoke('start_batch_transcription', { dirPath, config });
+  },
+
+  async getQueue(): Promise<QueueEntry[]> {
+    return invoke('get_queue');
+  },
+
+  async pauseQueue(): Promise<QueueStatus> {
+    return invoke('pause_queue');
+  },
+
+  async resumeQueue(): Promise<QueueStatus> {
+    return invoke('resume_queue');
+  },
+
+  async getQueueStatus(): Promise<QueueStatus> {
+    return invoke('get_queue_status');
+  },
+
+  async scheduleTranscription(config: WhisperConfig, runAt: string): Promise<ScheduledTranscription> {
+    return invoke('schedule_transcription', { config, runAt });
+  },
+
+  async listScheduled(): Promise<ScheduledTranscription[]> {
+    return invoke('list_scheduled');
+  },
+
+  async cancelScheduled(id: string): Promise<void> {
+    return invoke('cancel_scheduled', { id });
+  },
+
+  async transcribeScreenRecording(
+    filePath: string,
+    config: WhisperConfig,
+    trackPreference: AudioTrackPreference
+  ): Promise<string> {
+    return invoke('transcribe_screen_recording', { filePath, config, trackPreference });
+  },
+
   async downloadModelWithProgress(modelName: string): Promise<string> {
     return invoke('download_model_with_progress', { modelName });
   },
@@ -145,6 +704,38 @@ export const whisperApi = {
     return invoke('repair_model', { modelName });
   },
 
+  async getDownloadCapabilities(): Promise<DownloadCapabilities> {
+    return invoke('get_download_capabilities');
+  },
+
+  async getModelDownloadSize(modelName: string): Promise<ModelDownloadSize> {
+    return invoke('get_model_download_size', { modelName });
+  },
+
+  async getModelProvenance(modelName: string): Promise<ModelProvenance> {
+    return invoke('get_model_provenance', { modelName });
+  },
+
+  async readModelHeader(modelNameOrPath: string): Promise<ModelHeader> {
+    return invoke('read_model_header', { modelNameOrPath });
+  },
+
+  async analyzeAudio(filePath: string): Promise<AudioAnalysis> {
+    return invoke('analyze_audio', { filePath });
+  },
+
+  async suggestOutputFormats(inputPath: string): Promise<OutputFormatSuggestion> {
+    return invoke('suggest_output_formats', { inputPath });
+  },
+
+  async validateAllModels(): Promise<ModelValidation[]> {
+    return invoke('validate_all_models');
+  },
+
+  async repairAllInvalidModels(): Promise<ModelRepairSummary> {
+    return invoke('repair_all_invalid_models');
+  },
+
   // ===== 히스토리 관련 API =====
   
   async listTranscriptionHistory(query: HistoryQuery = {}): Promise<HistoryListResponse> {
@@ -163,6 +754,22 @@ export const whisperApi = {
     return invoke('update_history_tags', { historyId, tags });
   },
 
+  async bulkTag(query: HistoryQuery, tagsToAdd: string[], tagsToRemove: string[]): Promise<number> {
+    return invoke('bulk_tag', { query, tagsToAdd, tagsToRemove });
+  },
+
+  async setHistoryCustomField(historyId: string, key: string, value: string): Promise<TranscriptionHistory> {
+    return invoke('set_history_custom_field', { historyId, key, value });
+  },
+
+  async updateHistoryCustomFields(historyId: string, customFields: Record<string, string>): Promise<TranscriptionHistory> {
+    return invoke('update_history_custom_fields', { historyId, customFields });
+  },
+
+  async removeHistoryCustomField(historyId: string, key: string): Promise<TranscriptionHistory> {
+    return invoke('remove_history_custom_field', { historyId, key });
+  },
+
   async updateHistoryNotes(historyId: string, notes?: string): Promise<TranscriptionHistory> {
     return invoke('update_history_notes', { historyId, notes });
   },
@@ -174,4 +781,217 @@ export const whisperApi = {
   async getResultFileInfo(historyId: string): Promise<TranscriptionResult[]> {
     return invoke('get_result_file_info', { historyId });
   },
+
+  async diffHistoryOptions(historyIdA: string, historyIdB: string): Promise<HistoryOptionsDiff> {
+    return invoke('diff_history_options', { historyIdA, historyIdB });
+  },
+
+  async getResultFilePaths(historyId: string): Promise<Record<string, string>> {
+    return invoke('get_result_file_paths', { historyId });
+  },
+
+  async getRerunConfig(historyId: string): Promise<WhisperConfig> {
+    return invoke('get_rerun_config', { historyId });
+  },
+
+  async checkLanguageMismatch(historyId: string): Promise<LanguageCheck | null> {
+    return invoke('check_language_mismatch', { historyId });
+  },
+
+  async checkEncodingIssues(historyId: string): Promise<EncodingCheck> {
+    return invoke('check_encoding_issues', { historyId });
+  },
+
+  async tailResult(historyId: string, format: string, lines: number): Promise<TailResult> {
+    return invoke('tail_result', { historyId, format, lines });
+  },
+
+  async getAppSettings(): Promise<AppSettings> {
+    return invoke('get_app_settings');
+  },
+
+  async migrateDataDirectory(newPath: string): Promise<AppSettings> {
+    return invoke('migrate_data_directory', { newPath });
+  },
+
+  async setMediaToolPaths(ffmpegPath?: string, ffprobePath?: string): Promise<AppSettings> {
+    return invoke('set_media_tool_paths', { ffmpegPath, ffprobePath });
+  },
+
+  async setDefaultOutputFormats(formats: string[]): Promise<AppSettings> {
+    return invoke('set_default_output_formats', { formats });
+  },
+
+  async setVerifyAfterDownload(enabled: boolean): Promise<AppSettings> {
+    return invoke('set_verify_after_download', { enabled });
+  },
+
+  async verifyModelWithSample(modelName: string): Promise<ModelVerification> {
+    return invoke('verify_model_with_sample', { modelName });
+  },
+
+  async checkBatchLimits(batchSize: number): Promise<BatchLimitCheck> {
+    return invoke('check_batch_limits', { batchSize });
+  },
+
+  async quantizeModel(sourceModel: string, quantType: string): Promise<string> {
+    return invoke('quantize_model', { sourceModel, quantType });
+  },
+
+  async verifyModelChecksum(modelName: string): Promise<ModelChecksum> {
+    return invoke('verify_model_checksum', { modelName });
+  },
+
+  async setPostHookCommand(command?: string): Promise<AppSettings> {
+    return invoke('set_post_hook_command', { command });
+  },
+
+  async setCustomBinaryPath(path?: string): Promise<AppSettings> {
+    return invoke('set_custom_binary_path', { path });
+  },
+
+  async setTranslatorCommand(command?: string): Promise<AppSettings> {
+    return invoke('set_translator_command', { command });
+  },
+
+  async setMaxConcurrent(maxConcurrent: number): Promise<AppSettings> {
+    return invoke('set_max_concurrent', { maxConcurrent });
+  },
+
+  async setExportFilenameTemplate(template: string): Promise<AppSettings> {
+    return invoke('set_export_filename_template', { template });
+  },
+
+  async setLanguageRules(rules: Record<string, Record<string, string>>): Promise<AppSettings> {
+    return invoke('set_language_rules', { rules });
+  },
+
+  async deduplicateSegments(historyId: string, format: string, similarityThreshold?: number): Promise<DeduplicationReport> {
+    return invoke('deduplicate_segments', { historyId, format, similarityThreshold });
+  },
+
+  async mergeSegments(historyId: string, minDurationSeconds: number, maxGapSeconds: number): Promise<SegmentMergeReport> {
+    return invoke('merge_segments', { historyId, minDurationSeconds, maxGapSeconds });
+  },
+
+  async mergeShortSegments(historyId: string, format: string, minDurationMs: number, maxMergedChars: number): Promise<SegmentMergeReport> {
+    return invoke('merge_short_segments', { historyId, format, minDurationMs, maxMergedChars });
+  },
+
+  async analyzeSubtitleCoverage(historyId: string): Promise<SubtitleCoverage> {
+    return invoke('analyze_subtitle_coverage', { historyId });
+  },
+
+  async getSpeakingRate(historyId: string): Promise<SpeakingRate> {
+    return invoke('get_speaking_rate', { historyId });
+  },
+
+  async exportHighConfidence(
+    historyId: string,
+    format: string,
+    minConfidence: number,
+    outputPath: string
+  ): Promise<ConfidenceExportReport> {
+    return invoke('export_high_confidence', { historyId, format, minConfidence, outputPath });
+  },
+
+  async splitResult(historyId: string, format: string, options: SplitOptions): Promise<string[]> {
+    return invoke('split_result', { historyId, format, options });
+  },
+
+  async transcribeAppend(historyId: string, filePath: string): Promise<void> {
+    return invoke('transcribe_append', { historyId, filePath });
+  },
+
+  async listExportFormats(): Promise<ExportFormatInfo[]> {
+    return invoke('list_export_formats');
+  },
+
+  async getSegmentTiming(historyId: string): Promise<SegmentTiming[]> {
+    return invoke('get_segment_timing', { historyId });
+  },
+
+  async searchAllTranscripts(term: string, options?: TranscriptSearchOptions): Promise<TranscriptSearchResponse> {
+    return invoke('search_all_transcripts', { term, options });
+  },
+
+  async exportKaraokeAss(historyId: string, outputPath: string): Promise<string> {
+    return invoke('export_karaoke_ass', { historyId, outputPath });
+  },
+
+  async cancelTranscription(historyId: string): Promise<void> {
+    return invoke('cancel_transcription', { historyId });
+  },
+
+  async forceFailHistory(historyId: string, reason?: string, cancelled?: boolean): Promise<TranscriptionHistory> {
+    return invoke('force_fail_history', { historyId, reason, cancelled });
+  },
+
+  async getTranscriptionEta(historyId: string): Promise<TranscriptionEta> {
+    return invoke('get_transcription_eta', { historyId });
+  },
+
+  async getTranscriptionResources(historyId: string): Promise<TranscriptionResources> {
+    return invoke('get_transcription_resources', { historyId });
+  },
+
+  async regenerateExports(historyId: string, formats: string[]): Promise<TranscriptionHistory> {
+    return invoke('regenerate_exports', { historyId, formats });
+  },
+
+  async checkDownloadScript(): Promise<DownloadScriptStatus> {
+    return invoke('check_download_script');
+  },
+
+  async repairDownloadScript(): Promise<DownloadScriptStatus> {
+    return invoke('repair_download_script');
+  },
+
+  async exportHistoryReport(query: HistoryQuery, outputPath: string): Promise<number> {
+    return invoke('export_history_report', { query, outputPath });
+  },
+
+  async findDuplicateHistories(): Promise<DuplicateHistoryGroup[]> {
+    return invoke('find_duplicate_histories');
+  },
+
+  async generateBatchSummary(historyIds: string[], format: SummaryFormat): Promise<string> {
+    return invoke('generate_batch_summary', { historyIds, format });
+  },
+
+  async batchExport(historyIds: string[], format: string, destDir: string): Promise<BatchExportResult[]> {
+    return invoke('batch_export', { historyIds, format, destDir });
+  },
+
+  async exportDeliveryPackage(historyId: string, formats: string[], outputPath: string): Promise<DeliveryPackage> {
+    return invoke('export_delivery_package', { historyId, formats, outputPath });
+  },
+
+  async translateResult(historyId: string, format: string, targetLanguage: string): Promise<TranscriptionHistory> {
+    return invoke('translate_result', { historyId, format, targetLanguage });
+  },
+
+  async getHistoryLogs(historyId: string): Promise<string> {
+    return invoke('get_history_logs', { historyId });
+  },
+
+  async checkResultQuality(historyId: string, format: string): Promise<ResultQuality> {
+    return invoke('check_result_quality', { historyId, format });
+  },
+
+  async regeneratePreviews(force?: boolean): Promise<number> {
+    return invoke('regenerate_previews', { force });
+  },
+
+  async exportChapters(historyId: string, outputPath: string, minGapSeconds: number): Promise<string> {
+    return invoke('export_chapters', { historyId, outputPath, minGapSeconds });
+  },
+
+  async exportProofreadingDoc(historyId: string, intervalSeconds: number, outputPath: string): Promise<string> {
+    return invoke('export_proofreading_doc', { historyId, intervalSeconds, outputPath });
+  },
+
+  async exportHtmlPlayer(historyId: string, mediaReference: string): Promise<string> {
+    return invoke('export_html_player', { historyId, mediaReference });
+  },
 };
\ No newline at end of file